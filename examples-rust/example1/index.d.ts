@@ -0,0 +1,4 @@
+// This file is generated by napi-build. Do not edit it manually.
+
+export function hello(): void;
+export function add(first: number, second: number): number;
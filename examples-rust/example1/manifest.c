@@ -1,5 +1 @@
-#define FUNCTIONS_MAP(V) \
-  V(hello, example_hello) \
-  V(add, example_add)
-
 #include <napi_rs_entry.h>
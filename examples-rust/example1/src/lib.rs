@@ -10,3 +10,11 @@ fn add(env: napi::Env, first: napi::Number, second: napi::Number) -> napi::Resul
     let second = second.to_i32()?;
     napi::Number::from_i32(env, first + second)
 }
+
+#[napi::module_init]
+fn init(_env: napi::Env, exports: napi::Object) -> napi::Result<()> {
+    exports.define_properties(&[
+        napi::PropertyDescriptor::method("hello", example_hello),
+        napi::PropertyDescriptor::method("add", example_add),
+    ])
+}
@@ -0,0 +1,3 @@
+fn main() {
+    napi_build::typedefs("index.d.ts");
+}
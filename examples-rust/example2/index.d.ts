@@ -0,0 +1,4 @@
+// This file is generated by napi-build. Do not edit it manually.
+
+export function sumOfSquaresPar(array: Float64Array): number;
+export function sumOfSquaresSeq(array: Float64Array): number;
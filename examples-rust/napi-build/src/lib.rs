@@ -0,0 +1,121 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub mod typedef;
+
+use typedef::CallbackDef;
+
+pub const WRITE_ENV_VAR: &str = "NAPI_TYPEDEFS_WRITE";
+
+#[derive(Debug)]
+pub enum TypedefError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, syn::Error),
+    Stale(PathBuf),
+}
+
+impl fmt::Display for TypedefError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypedefError::Io(path, error) => write!(formatter, "{}: {}", path.display(), error),
+            TypedefError::Parse(path, error) => {
+                write!(formatter, "{}: {}", path.display(), error)
+            }
+            TypedefError::Stale(path) => write!(
+                formatter,
+                "{} is out of date, rebuild with {} set to regenerate it",
+                path.display(),
+                WRITE_ENV_VAR,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TypedefError {}
+
+/// Checks that `output` holds the declarations generated from the crate's
+/// sources and fails the build if it doesn't. The file is only rewritten
+/// when `NAPI_TYPEDEFS_WRITE` is set, so a stale checked-in file is caught
+/// by any build.
+pub fn typedefs<P: AsRef<Path>>(output: P) {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let src_dir = manifest_dir.join("src");
+    let output = manifest_dir.join(output);
+
+    println!("cargo:rerun-if-changed={}", src_dir.display());
+    println!("cargo:rerun-if-changed={}", output.display());
+    println!("cargo:rerun-if-env-changed={}", WRITE_ENV_VAR);
+
+    let result = if env::var_os(WRITE_ENV_VAR).is_some() {
+        write_typedefs(&src_dir, &output)
+    } else {
+        check_typedefs(&src_dir, &output)
+    };
+
+    if let Err(error) = result {
+        panic!("{}", error);
+    }
+}
+
+pub fn generate_typedefs(src_dir: &Path) -> Result<String, TypedefError> {
+    let mut files = Vec::new();
+    find_sources(src_dir, &mut files)?;
+    files.sort();
+
+    let mut callbacks: Vec<CallbackDef> = Vec::new();
+
+    for path in files {
+        let source =
+            fs::read_to_string(&path).map_err(|error| TypedefError::Io(path.clone(), error))?;
+        let file =
+            syn::parse_file(&source).map_err(|error| TypedefError::Parse(path.clone(), error))?;
+        typedef::collect_callbacks(&file.items, &mut callbacks)
+            .map_err(|error| TypedefError::Parse(path.clone(), error))?;
+    }
+
+    Ok(typedef::render(&callbacks))
+}
+
+pub fn write_typedefs(src_dir: &Path, output: &Path) -> Result<(), TypedefError> {
+    let typedefs = generate_typedefs(src_dir)?;
+
+    if fs::read_to_string(output).ok().as_ref() == Some(&typedefs) {
+        return Ok(());
+    }
+
+    fs::write(output, typedefs).map_err(|error| TypedefError::Io(output.to_owned(), error))
+}
+
+pub fn check_typedefs(src_dir: &Path, output: &Path) -> Result<(), TypedefError> {
+    let typedefs = generate_typedefs(src_dir)?;
+
+    match fs::read_to_string(output) {
+        Ok(ref existing) if *existing == typedefs => Ok(()),
+        Ok(_) => Err(TypedefError::Stale(output.to_owned())),
+        Err(ref error) if error.kind() == io::ErrorKind::NotFound => {
+            Err(TypedefError::Stale(output.to_owned()))
+        }
+        Err(error) => Err(TypedefError::Io(output.to_owned(), error)),
+    }
+}
+
+fn find_sources(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), TypedefError> {
+    let entries = fs::read_dir(dir).map_err(|error| TypedefError::Io(dir.to_owned(), error))?;
+
+    for entry in entries {
+        let path = entry
+            .map_err(|error| TypedefError::Io(dir.to_owned(), error))?
+            .path();
+
+        if path.is_dir() {
+            find_sources(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,440 @@
+use syn::parse::{Parse, ParseStream};
+
+pub struct CallbackArgs {
+    pub c_name: syn::Ident,
+    pub js_name: Option<syn::LitStr>,
+    /// The class the callback is a member of. Members receive `this` as the
+    /// argument after `env`.
+    pub class: Option<syn::LitStr>,
+    pub constructor: bool,
+}
+
+impl Parse for CallbackArgs {
+    fn parse(input: ParseStream) -> syn::Result<CallbackArgs> {
+        let c_name = input.parse()?;
+        let mut js_name = None;
+        let mut class = None;
+        let mut constructor = None;
+
+        while !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let key = input.parse::<syn::Ident>()?;
+            if key == "constructor" {
+                constructor = Some(key);
+                continue;
+            }
+
+            input.parse::<syn::Token![=]>()?;
+
+            if key == "js_name" {
+                js_name = Some(input.parse()?);
+            } else if key == "class" {
+                class = Some(input.parse()?);
+            } else {
+                return Err(syn::Error::new(key.span(), "unknown callback attribute"));
+            }
+        }
+
+        if let Some(ref key) = constructor {
+            if class.is_none() {
+                return Err(syn::Error::new(key.span(), "constructor requires a class"));
+            }
+        }
+
+        Ok(CallbackArgs {
+            c_name,
+            js_name,
+            class,
+            constructor: constructor.is_some(),
+        })
+    }
+}
+
+impl CallbackArgs {
+    /// The number of leading Rust arguments that aren't passed from
+    /// JavaScript: `env`, and `this` for class members.
+    pub fn receiver_args(&self) -> usize {
+        if self.class.is_some() {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub ty: String,
+    pub optional: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallbackDef {
+    pub js_name: String,
+    pub params: Vec<Param>,
+    pub return_type: String,
+    pub class: Option<String>,
+    pub constructor: bool,
+}
+
+impl CallbackDef {
+    pub fn new(args: &CallbackArgs, sig: &syn::Signature) -> CallbackDef {
+        let js_name = match args.js_name {
+            Some(ref js_name) => js_name.value(),
+            None => camel_case(&sig.ident.to_string()),
+        };
+
+        let receiver_args = args.receiver_args();
+        let mut params = sig
+            .inputs
+            .iter()
+            .skip(receiver_args)
+            .filter_map(|arg| match arg {
+                syn::FnArg::Typed(typed) => Some(typed),
+                syn::FnArg::Receiver(_) => None,
+            })
+            .map(|typed| {
+                let name = match *typed.pat {
+                    syn::Pat::Ident(ref pat_ident) => camel_case(&pat_ident.ident.to_string()),
+                    _ => "arg".to_owned(),
+                };
+                Param {
+                    name,
+                    ty: ts_type(&typed.ty),
+                    optional: false,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let required = required_args(sig.inputs.iter().skip(receiver_args));
+        for param in params.iter_mut().skip(required) {
+            param.optional = true;
+        }
+
+        let return_type = match sig.output {
+            syn::ReturnType::Default => "void".to_owned(),
+            syn::ReturnType::Type(_, ref ty) => match ts_type(ty).as_str() {
+                "undefined" => "void".to_owned(),
+                ty => ty.to_owned(),
+            },
+        };
+
+        CallbackDef {
+            js_name,
+            params,
+            return_type,
+            class: args.class.as_ref().map(syn::LitStr::value),
+            constructor: args.constructor,
+        }
+    }
+
+    pub fn to_declaration(&self) -> String {
+        let params = self
+            .params
+            .iter()
+            .map(|param| {
+                let marker = if param.optional { "?" } else { "" };
+                format!("{}{}: {}", param.name, marker, param.ty)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if self.constructor {
+            format!("constructor({});", params)
+        } else if self.class.is_some() {
+            format!("{}({}): {};", self.js_name, params, self.return_type)
+        } else {
+            format!(
+                "export function {}({}): {};",
+                self.js_name, params, self.return_type
+            )
+        }
+    }
+}
+
+pub fn ts_type(ty: &syn::Type) -> String {
+    match ty {
+        syn::Type::Reference(reference) => ts_type(&reference.elem),
+        syn::Type::Paren(paren) => ts_type(&paren.elem),
+        syn::Type::Group(group) => ts_type(&group.elem),
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => "undefined".to_owned(),
+        syn::Type::Slice(slice) => array_of(ts_type(&slice.elem)),
+        syn::Type::Path(path) => match path.path.segments.last() {
+            Some(segment) => path_type(segment),
+            None => "unknown".to_owned(),
+        },
+        _ => "unknown".to_owned(),
+    }
+}
+
+fn path_type(segment: &syn::PathSegment) -> String {
+    let name = segment.ident.to_string();
+
+    let ty = match name.as_str() {
+        "Number" | "f64" | "f32" | "i64" | "i32" | "i16" | "i8" | "u64" | "u32" | "u16" | "u8" => {
+            "number"
+        }
+        "BigInt" | "i128" | "u128" => "bigint",
+        "String" | "str" => "string",
+        "Boolean" | "bool" => "boolean",
+        "Null" => "null",
+        "Undefined" => "undefined",
+        "Any" => "unknown",
+        "Object" => "object",
+        "Array" => "unknown[]",
+        "Buffer" => "Buffer",
+        "ArrayBuffer" => "ArrayBuffer",
+        "Int8Array" => "Int8Array",
+        "UInt8Array" => "Uint8Array",
+        "UInt8ClampedArray" => "Uint8ClampedArray",
+        "Int16Array" => "Int16Array",
+        "UInt16Array" => "Uint16Array",
+        "Int32Array" => "Int32Array",
+        "UInt32Array" => "Uint32Array",
+        "Float32Array" => "Float32Array",
+        "Float64Array" => "Float64Array",
+        "BigInt64Array" => "BigInt64Array",
+        "BigUInt64Array" => "BigUint64Array",
+        "Option" => {
+            return match first_type_argument(segment) {
+                Some(inner) => format!("{} | undefined", ts_type(inner)),
+                None => "unknown".to_owned(),
+            }
+        }
+        "Vec" => {
+            return match first_type_argument(segment) {
+                Some(inner) => array_of(ts_type(inner)),
+                None => "unknown[]".to_owned(),
+            }
+        }
+        "Result" => {
+            return match first_type_argument(segment) {
+                Some(inner) => ts_type(inner),
+                None => "unknown".to_owned(),
+            }
+        }
+        _ => "unknown",
+    };
+
+    ty.to_owned()
+}
+
+pub fn required_args<'a>(args: impl Iterator<Item = &'a syn::FnArg>) -> usize {
+    let args = args.collect::<Vec<_>>();
+    let optional = args
+        .iter()
+        .rev()
+        .take_while(|arg| match arg {
+            syn::FnArg::Typed(typed) => is_option(&typed.ty),
+            syn::FnArg::Receiver(_) => false,
+        })
+        .count();
+
+    args.len() - optional
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => match path.path.segments.last() {
+            Some(segment) => segment.ident == "Option",
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+fn first_type_argument(segment: &syn::PathSegment) -> Option<&syn::Type> {
+    match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref arguments) => {
+            arguments.args.iter().find_map(|argument| match argument {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn array_of(element: String) -> String {
+    if element.contains(' ') {
+        format!("({})[]", element)
+    } else {
+        format!("{}[]", element)
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut capitalize = false;
+
+    for ch in name.trim_start_matches('_').chars() {
+        if ch == '_' {
+            capitalize = true;
+        } else if capitalize {
+            result.extend(ch.to_uppercase());
+            capitalize = false;
+        } else {
+            result.push(ch);
+        }
+    }
+
+    result
+}
+
+pub fn collect_callbacks(items: &[syn::Item], callbacks: &mut Vec<CallbackDef>) -> syn::Result<()> {
+    for item in items {
+        match item {
+            syn::Item::Fn(item_fn) => {
+                for attr in &item_fn.attrs {
+                    if is_callback_attr(&attr.path) {
+                        let args = attr.parse_args::<CallbackArgs>()?;
+                        callbacks.push(CallbackDef::new(&args, &item_fn.sig));
+                    }
+                }
+            }
+            syn::Item::Mod(item_mod) => {
+                if let Some((_, ref items)) = item_mod.content {
+                    collect_callbacks(items, callbacks)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn is_callback_attr(path: &syn::Path) -> bool {
+    let segments = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+
+    segments == ["napi", "callback"] || segments == ["callback"]
+}
+
+pub fn render(callbacks: &[CallbackDef]) -> String {
+    let mut output =
+        String::from("// This file is generated by napi-build. Do not edit it manually.\n\n");
+
+    for callback in callbacks.iter().filter(|callback| callback.class.is_none()) {
+        output.push_str(&callback.to_declaration());
+        output.push('\n');
+    }
+
+    let mut classes: Vec<&str> = Vec::new();
+    for class in callbacks
+        .iter()
+        .filter_map(|callback| callback.class.as_deref())
+    {
+        if !classes.contains(&class) {
+            classes.push(class);
+        }
+    }
+
+    for class in classes {
+        let mut members = callbacks
+            .iter()
+            .filter(|callback| callback.class.as_deref() == Some(class))
+            .collect::<Vec<_>>();
+        members.sort_by_key(|callback| !callback.constructor);
+
+        output.push_str(&format!("\nexport declare class {} {{\n", class));
+        for member in members {
+            output.push_str("  ");
+            output.push_str(&member.to_declaration());
+            output.push('\n');
+        }
+        output.push_str("}\n");
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declaration(attr: &str, item: &str) -> String {
+        let args = syn::parse_str::<CallbackArgs>(attr).unwrap();
+        let item = syn::parse_str::<syn::ItemFn>(item).unwrap();
+        CallbackDef::new(&args, &item.sig).to_declaration()
+    }
+
+    #[test]
+    fn maps_value_types() {
+        assert_eq!(
+            declaration(
+                "sum_of_squares_js",
+                "fn sum_of_squares(env: napi::Env, array: napi::Float64Array<'_>) -> napi::Result<napi::Number> {}",
+            ),
+            "export function sumOfSquares(array: Float64Array): number;",
+        );
+    }
+
+    #[test]
+    fn maps_options_and_vectors() {
+        assert_eq!(
+            declaration(
+                "example_join, js_name = \"join\"",
+                "fn join_all(env: napi::Env, parts: Vec<String>, separator: Option<String>) -> napi::Result<Option<napi::String>> {}",
+            ),
+            "export function join(parts: string[], separator?: string | undefined): string | undefined;",
+        );
+    }
+
+    #[test]
+    fn renders_classes() {
+        let callbacks = [
+            ("example_version", "fn version(env: napi::Env) -> napi::Result<u32> {}"),
+            (
+                "counter_increment, class = \"Counter\"",
+                "fn increment(env: napi::Env, this: napi::Object, by: Option<u32>) -> napi::Result<u32> {}",
+            ),
+            (
+                "counter_new, class = \"Counter\", constructor",
+                "fn new(env: napi::Env, this: napi::Object, start: u32) -> napi::Result<()> {}",
+            ),
+        ]
+        .iter()
+        .map(|(attr, item)| {
+            let args = syn::parse_str::<CallbackArgs>(attr).unwrap();
+            let item = syn::parse_str::<syn::ItemFn>(item).unwrap();
+            CallbackDef::new(&args, &item.sig)
+        })
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            render(&callbacks),
+            "// This file is generated by napi-build. Do not edit it manually.\n\n\
+             export function version(): number;\n\
+             \n\
+             export declare class Counter {\n  \
+             constructor(start: number);\n  \
+             increment(by?: number | undefined): number;\n\
+             }\n",
+        );
+    }
+
+    #[test]
+    fn rejects_constructors_outside_classes() {
+        assert!(syn::parse_str::<CallbackArgs>("counter_new, constructor").is_err());
+    }
+
+    #[test]
+    fn maps_undefined_to_void() {
+        assert_eq!(
+            declaration(
+                "example_hello",
+                "fn hello(env: napi::Env) -> napi::Result<napi::Undefined> {}",
+            ),
+            "export function hello(): void;",
+        );
+    }
+}
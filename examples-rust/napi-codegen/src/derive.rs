@@ -0,0 +1,705 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+
+pub fn from_js(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let container = ContainerAttrs::parse(&input.attrs)?;
+    let name = &input.ident;
+    let generics = add_bounds(&input.generics, quote!(napi::FromJs));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match input.data {
+        syn::Data::Struct(ref data) => from_js_struct(&container, data)?,
+        syn::Data::Enum(ref data) => from_js_enum(&container, name, data)?,
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new(input.span(), "unions are not supported"));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics napi::FromJs for #name #ty_generics #where_clause {
+            fn from_js(value: napi::Any) -> napi::Result<Self> {
+                use napi::Value;
+
+                let env = value.env();
+
+                #body
+            }
+        }
+    })
+}
+
+pub fn into_js(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let container = ContainerAttrs::parse(&input.attrs)?;
+    let name = &input.ident;
+    let generics = add_bounds(&input.generics, quote!(napi::IntoJs));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match input.data {
+        syn::Data::Struct(ref data) => into_js_struct(&container, data)?,
+        syn::Data::Enum(ref data) => into_js_enum(&container, name, data)?,
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new(input.span(), "unions are not supported"));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics napi::IntoJs for #name #ty_generics #where_clause {
+            fn into_js(self, env: napi::Env) -> napi::Result<napi::Any> {
+                use napi::Value;
+
+                #body
+            }
+        }
+    })
+}
+
+fn from_js_struct(container: &ContainerAttrs, data: &syn::DataStruct) -> syn::Result<TokenStream> {
+    match data.fields {
+        syn::Fields::Named(ref fields) => {
+            let fields = get_fields(container, fields)?;
+            Ok(quote! {
+                let __napi_object = value.as_object()?;
+                Ok(Self { #fields })
+            })
+        }
+        syn::Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => Ok(quote! {
+            napi::FromJs::from_js(value).map(Self)
+        }),
+        syn::Fields::Unnamed(ref fields) => {
+            let elements = fields.unnamed.iter().enumerate().map(|(index, field)| {
+                let ty = &field.ty;
+                let index = index as u32;
+                let key = index.to_string();
+                quote! {
+                    array
+                        .get(#index)
+                        .and_then(<#ty as napi::FromJs>::from_js)
+                        .map_err(|error| error.in_property(env, #key))?
+                }
+            });
+
+            Ok(quote! {
+                let array = value.as_array()?;
+                Ok(Self(#(#elements),*))
+            })
+        }
+        syn::Fields::Unit => Ok(quote! {
+            Ok(Self)
+        }),
+    }
+}
+
+fn into_js_struct(container: &ContainerAttrs, data: &syn::DataStruct) -> syn::Result<TokenStream> {
+    match data.fields {
+        syn::Fields::Named(ref fields) => {
+            let bindings = field_bindings(fields);
+            let set_fields = set_fields(container, fields)?;
+            Ok(quote! {
+                let Self { #bindings } = self;
+                let __napi_object = napi::Object::new(env)?;
+                #set_fields
+                Ok(__napi_object.as_napi_any())
+            })
+        }
+        syn::Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => Ok(quote! {
+            napi::IntoJs::into_js(self.0, env)
+        }),
+        syn::Fields::Unnamed(ref fields) => {
+            let count = fields.unnamed.len();
+            let elements = (0..count).map(|index| {
+                let member = syn::Index::from(index);
+                let index = index as u32;
+                let key = index.to_string();
+                quote! {
+                    let element = napi::IntoJs::into_js(self.#member, env)
+                        .map_err(|error| error.in_property(env, #key))?;
+                    array.set(#index, &element)?;
+                }
+            });
+
+            Ok(quote! {
+                let array = napi::Array::with_len(env, #count)?;
+                #(#elements)*
+                Ok(array.as_napi_any())
+            })
+        }
+        syn::Fields::Unit => Ok(quote! {
+            napi::IntoJs::into_js((), env)
+        }),
+    }
+}
+
+fn from_js_enum(
+    container: &ContainerAttrs,
+    name: &syn::Ident,
+    data: &syn::DataEnum,
+) -> syn::Result<TokenStream> {
+    let variants = parse_variants(container, data)?;
+
+    if container.tag.is_none() && variants.iter().all(|variant| variant.is_unit()) {
+        let arms = variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let js_name = &variant.js_name;
+            quote! { #js_name => Ok(#name::#ident), }
+        });
+        let expected = expected_variants(&variants);
+
+        return Ok(quote! {
+            let variant = value.as_string()?.to_string()?;
+            match variant.as_str() {
+                #(#arms)*
+                _ => {
+                    let message = napi::String::from_str(
+                        env,
+                        &format!("{} expected, got {:?}", #expected, variant),
+                    )?;
+                    Err(napi::Error::type_error(env, &message))
+                }
+            }
+        });
+    }
+
+    let tag = container.tag();
+    let get_tag = get_property(&tag, quote!(std::string::String), false);
+
+    let arms = variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            let js_name = &variant.js_name;
+
+            let construct = match variant.fields {
+                syn::Fields::Named(ref fields) => {
+                    let fields = get_fields(&variant.attrs, fields)?;
+                    quote! { #name::#ident { #fields } }
+                }
+                syn::Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                    let ty = &fields.unnamed[0].ty;
+                    let value = get_property(&variant.attrs.content(), quote!(#ty), false);
+                    quote! { #name::#ident(#value) }
+                }
+                syn::Fields::Unnamed(ref fields) => {
+                    return Err(syn::Error::new(
+                        fields.span(),
+                        "tuple variants with more than one field are not supported",
+                    ));
+                }
+                syn::Fields::Unit => quote! { #name::#ident },
+            };
+
+            Ok(quote! { #js_name => Ok(#construct), })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let expected = expected_variants(&variants);
+
+    Ok(quote! {
+        let __napi_object = value.as_object()?;
+        let variant: std::string::String = #get_tag;
+        match variant.as_str() {
+            #(#arms)*
+            _ => {
+                let message = napi::String::from_str(
+                    env,
+                    &format!("{} expected, got {:?}", #expected, variant),
+                )?;
+                Err(napi::Error::type_error(env, &message).in_property(env, #tag))
+            }
+        }
+    })
+}
+
+fn into_js_enum(
+    container: &ContainerAttrs,
+    name: &syn::Ident,
+    data: &syn::DataEnum,
+) -> syn::Result<TokenStream> {
+    let variants = parse_variants(container, data)?;
+
+    if container.tag.is_none() && variants.iter().all(|variant| variant.is_unit()) {
+        let arms = variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let js_name = &variant.js_name;
+            quote! { #name::#ident => #js_name, }
+        });
+
+        return Ok(quote! {
+            let variant = match self {
+                #(#arms)*
+            };
+            napi::IntoJs::into_js(variant, env)
+        });
+    }
+
+    let tag = container.tag();
+
+    let arms = variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            let js_name = &variant.js_name;
+            let set_tag = set_property(&tag, quote!(#js_name));
+
+            Ok(match variant.fields {
+                syn::Fields::Named(ref fields) => {
+                    let bindings = field_bindings(fields);
+                    let set_fields = set_fields(&variant.attrs, fields)?;
+                    quote! {
+                        #name::#ident { #bindings } => {
+                            #set_tag
+                            #set_fields
+                        }
+                    }
+                }
+                syn::Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                    let set_value = set_property(&variant.attrs.content(), quote!(__napi_value));
+                    quote! {
+                        #name::#ident(__napi_value) => {
+                            #set_tag
+                            #set_value
+                        }
+                    }
+                }
+                syn::Fields::Unnamed(ref fields) => {
+                    return Err(syn::Error::new(
+                        fields.span(),
+                        "tuple variants with more than one field are not supported",
+                    ));
+                }
+                syn::Fields::Unit => quote! {
+                    #name::#ident => {
+                        #set_tag
+                    }
+                },
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        let __napi_object = napi::Object::new(env)?;
+        match self {
+            #(#arms)*
+        }
+        Ok(__napi_object.as_napi_any())
+    })
+}
+
+fn get_fields(container: &ContainerAttrs, fields: &syn::FieldsNamed) -> syn::Result<TokenStream> {
+    let fields = fields
+        .named
+        .iter()
+        .map(|field| {
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            let ident = field.ident.as_ref().unwrap();
+
+            let value = if attrs.skip {
+                quote! { std::default::Default::default() }
+            } else {
+                let ty = &field.ty;
+                let js_name = container.field_name(ident, &attrs);
+                get_property(&js_name, quote!(#ty), attrs.default)
+            };
+
+            Ok(quote! { #ident: #value, })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! { #(#fields)* })
+}
+
+fn set_fields(container: &ContainerAttrs, fields: &syn::FieldsNamed) -> syn::Result<TokenStream> {
+    let fields = fields
+        .named
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            let ident = field.ident.as_ref().unwrap();
+            let binding = field_binding(index);
+
+            if attrs.skip {
+                return Ok(quote! { let _ = #binding; });
+            }
+
+            let js_name = container.field_name(ident, &attrs);
+            Ok(set_property(&js_name, quote!(#binding)))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! { #(#fields)* })
+}
+
+/// Binds the fields of `self` to names that can't shadow the locals of the
+/// generated code, e.g. a field named `env` or `object`.
+fn field_bindings(fields: &syn::FieldsNamed) -> TokenStream {
+    let bindings = fields.named.iter().enumerate().map(|(index, field)| {
+        let ident = &field.ident;
+        let binding = field_binding(index);
+        quote! { #ident: #binding }
+    });
+
+    quote! { #(#bindings),* }
+}
+
+fn field_binding(index: usize) -> syn::Ident {
+    syn::Ident::new(
+        &format!("__napi_field_{}", index),
+        proc_macro2::Span::call_site(),
+    )
+}
+
+fn get_property(js_name: &str, ty: TokenStream, default: bool) -> TokenStream {
+    let convert = if default {
+        quote! {
+            if __napi_property.value_type()? == napi::ValueType::Undefined {
+                Ok(std::default::Default::default())
+            } else {
+                <#ty as napi::FromJs>::from_js(__napi_property)
+            }
+        }
+    } else {
+        quote! { <#ty as napi::FromJs>::from_js(__napi_property) }
+    };
+
+    quote! {
+        {
+            let mut __napi_property = std::ptr::null_mut();
+            env.handle_status(unsafe {
+                napi::sys::napi_get_named_property(
+                    env.as_sys_env(),
+                    __napi_object.as_sys_value(),
+                    concat!(#js_name, "\0").as_ptr() as *const std::os::raw::c_char,
+                    &mut __napi_property,
+                )
+            })?;
+            let __napi_property = napi::Any::with_value(env, __napi_property);
+            let value: napi::Result<#ty> = #convert;
+            value.map_err(|error| error.in_property(env, #js_name))?
+        }
+    }
+}
+
+fn set_property(js_name: &str, value: TokenStream) -> TokenStream {
+    quote! {
+        let __napi_property = napi::IntoJs::into_js(#value, env)
+            .map_err(|error| error.in_property(env, #js_name))?;
+        env.handle_status(unsafe {
+            napi::sys::napi_set_named_property(
+                env.as_sys_env(),
+                __napi_object.as_sys_value(),
+                concat!(#js_name, "\0").as_ptr() as *const std::os::raw::c_char,
+                __napi_property.as_sys_value(),
+            )
+        })?;
+    }
+}
+
+fn expected_variants(variants: &[Variant]) -> String {
+    let names = variants
+        .iter()
+        .map(|variant| format!("{:?}", variant.js_name))
+        .collect::<Vec<_>>();
+
+    format!("one of {}", names.join(", "))
+}
+
+fn add_bounds(generics: &syn::Generics, bound: TokenStream) -> syn::Generics {
+    let mut generics = generics.clone();
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+
+    let where_clause = generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#param: #bound));
+    }
+
+    generics
+}
+
+struct Variant<'a> {
+    ident: &'a syn::Ident,
+    js_name: String,
+    fields: &'a syn::Fields,
+    attrs: ContainerAttrs,
+}
+
+impl<'a> Variant<'a> {
+    fn is_unit(&self) -> bool {
+        matches!(self.fields, syn::Fields::Unit)
+    }
+}
+
+fn parse_variants<'a>(
+    container: &ContainerAttrs,
+    data: &'a syn::DataEnum,
+) -> syn::Result<Vec<Variant<'a>>> {
+    data.variants
+        .iter()
+        .map(|variant| {
+            let mut field_attrs = FieldAttrs::default();
+            let mut attrs = ContainerAttrs::default();
+
+            // Variants are renamed like fields and hold their own fields and
+            // content like containers, but have no tag or field flags.
+            for meta in napi_metas(&variant.attrs)? {
+                let known = match meta {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(ref name_value))
+                        if name_value.path.is_ident("tag") =>
+                    {
+                        false
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(ref name_value))
+                        if name_value.path.is_ident("rename") =>
+                    {
+                        field_attrs.parse_meta(&meta)?
+                    }
+                    _ => attrs.parse_meta(&meta)?,
+                };
+
+                if !known {
+                    return Err(unknown_attribute(&meta));
+                }
+            }
+            attrs.content = attrs.content.or_else(|| container.content.clone());
+
+            Ok(Variant {
+                ident: &variant.ident,
+                js_name: container.field_name(&variant.ident, &field_attrs),
+                fields: &variant.fields,
+                attrs,
+            })
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct ContainerAttrs {
+    rename_all: Option<RenameRule>,
+    tag: Option<String>,
+    content: Option<String>,
+}
+
+impl ContainerAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<ContainerAttrs> {
+        let mut result = ContainerAttrs::default();
+
+        for meta in napi_metas(attrs)? {
+            if !result.parse_meta(&meta)? {
+                return Err(unknown_attribute(&meta));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Returns `false` if `meta` isn't a container attribute.
+    fn parse_meta(&mut self, meta: &syn::NestedMeta) -> syn::Result<bool> {
+        match meta {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(ref name_value))
+                if name_value.path.is_ident("rename_all") =>
+            {
+                let rule = lit_str(&name_value.lit)?;
+                self.rename_all = Some(RenameRule::parse(&rule, name_value.lit.span())?);
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(ref name_value))
+                if name_value.path.is_ident("tag") =>
+            {
+                self.tag = Some(lit_str(&name_value.lit)?);
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(ref name_value))
+                if name_value.path.is_ident("content") =>
+            {
+                self.content = Some(lit_str(&name_value.lit)?);
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn tag(&self) -> String {
+        self.tag.clone().unwrap_or_else(|| "type".to_owned())
+    }
+
+    fn content(&self) -> String {
+        self.content.clone().unwrap_or_else(|| "value".to_owned())
+    }
+
+    fn field_name(&self, ident: &syn::Ident, attrs: &FieldAttrs) -> String {
+        if let Some(ref rename) = attrs.rename {
+            return rename.clone();
+        }
+
+        let name = ident.to_string();
+        let name = name.trim_start_matches("r#");
+
+        match self.rename_all {
+            Some(rule) => rule.apply(name),
+            None => name.to_owned(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    default: bool,
+    skip: bool,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<FieldAttrs> {
+        let mut result = FieldAttrs::default();
+
+        for meta in napi_metas(attrs)? {
+            if !result.parse_meta(&meta)? {
+                return Err(unknown_attribute(&meta));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Returns `false` if `meta` isn't a field attribute.
+    fn parse_meta(&mut self, meta: &syn::NestedMeta) -> syn::Result<bool> {
+        match meta {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(ref name_value))
+                if name_value.path.is_ident("rename") =>
+            {
+                self.rename = Some(lit_str(&name_value.lit)?);
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(ref path)) if path.is_ident("default") => {
+                self.default = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(ref path)) if path.is_ident("skip") => {
+                self.skip = true;
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+}
+
+fn napi_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::NestedMeta>> {
+    let mut metas = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("napi")) {
+        match attr.parse_meta()? {
+            syn::Meta::List(list) => metas.extend(list.nested),
+            meta => {
+                return Err(syn::Error::new(
+                    meta.span(),
+                    "expected #[napi(...)] attribute",
+                ));
+            }
+        }
+    }
+
+    Ok(metas)
+}
+
+fn unknown_attribute(meta: &syn::NestedMeta) -> syn::Error {
+    syn::Error::new_spanned(meta, "unknown napi attribute")
+}
+
+fn lit_str(lit: &syn::Lit) -> syn::Result<String> {
+    match lit {
+        syn::Lit::Str(lit) => Ok(lit.value()),
+        _ => Err(syn::Error::new(lit.span(), "expected a string literal")),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+}
+
+impl RenameRule {
+    fn parse(rule: &str, span: proc_macro2::Span) -> syn::Result<RenameRule> {
+        match rule {
+            "lowercase" => Ok(RenameRule::Lower),
+            "UPPERCASE" => Ok(RenameRule::Upper),
+            "PascalCase" => Ok(RenameRule::Pascal),
+            "camelCase" => Ok(RenameRule::Camel),
+            "snake_case" => Ok(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            "kebab-case" => Ok(RenameRule::Kebab),
+            _ => Err(syn::Error::new(
+                span,
+                format!("unknown rename rule {:?}", rule),
+            )),
+        }
+    }
+
+    fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+
+        match self {
+            RenameRule::Lower => words.concat(),
+            RenameRule::Upper => words.concat().to_uppercase(),
+            RenameRule::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            RenameRule::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| {
+                    if index == 0 {
+                        word.clone()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            RenameRule::Snake => words.join("_"),
+            RenameRule::ScreamingSnake => words.join("_").to_uppercase(),
+            RenameRule::Kebab => words.join("-"),
+        }
+    }
+}
+
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for ch in name.chars() {
+        if ch == '_' || ch == '-' {
+            if !current.is_empty() {
+                words.push(current.clone());
+                current.clear();
+            }
+        } else if ch.is_uppercase() {
+            if !current.is_empty() {
+                words.push(current.clone());
+                current.clear();
+            }
+            current.extend(ch.to_lowercase());
+        } else {
+            current.push(ch);
+        }
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -2,6 +2,9 @@
 
 extern crate proc_macro;
 
+mod derive;
+
+use napi_build::typedef::{self, CallbackArgs};
 use quote::quote;
 
 #[proc_macro_attribute]
@@ -9,10 +12,11 @@ pub fn callback(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let c_name = syn::parse_macro_input!(attr as proc_macro2::Ident);
+    let args = syn::parse_macro_input!(attr as CallbackArgs);
     let input = syn::parse_macro_input!(item as syn::ItemFn);
 
-    let callback = make_callback(c_name, input.clone().sig);
+    let callback =
+        make_callback(args, input.clone().sig).unwrap_or_else(|error| error.to_compile_error());
 
     let output = quote! {
         #input
@@ -22,50 +26,134 @@ pub fn callback(
     proc_macro::TokenStream::from(output)
 }
 
-fn make_callback(c_name: proc_macro2::Ident, sig: syn::Signature) -> proc_macro2::TokenStream {
-    let rs_name = sig.ident.clone();
-    let (get_args, pass_args) = make_args(sig.inputs);
+#[proc_macro_derive(FromJs, attributes(napi))]
+pub fn derive_from_js(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+
+    let output = derive::from_js(input).unwrap_or_else(|error| error.to_compile_error());
+
+    proc_macro::TokenStream::from(output)
+}
+
+#[proc_macro_derive(IntoJs, attributes(napi))]
+pub fn derive_into_js(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+
+    let output = derive::into_js(input).unwrap_or_else(|error| error.to_compile_error());
+
+    proc_macro::TokenStream::from(output)
+}
+
+#[proc_macro_attribute]
+pub fn module_init(
+    _attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(item as syn::ItemFn);
+
+    let init = make_module_init(input.sig.ident.clone());
+
+    let output = quote! {
+        #input
+        #init
+    };
+
+    proc_macro::TokenStream::from(output)
+}
+
+fn make_module_init(rs_name: proc_macro2::Ident) -> proc_macro2::TokenStream {
     let error = return_error();
 
     quote! {
+        #[no_mangle]
+        pub extern "C" fn napi_rs_init(
+            env: napi::sys::napi_env,
+            exports: napi::sys::napi_value,
+        ) -> napi::sys::napi_value {
+            use napi::Value;
+
+            let env_wrapper = napi::Env::from(env);
+
+            let exports_wrapper = match napi::Object::from_sys_checked(env_wrapper, exports) {
+                Ok(value) => value,
+                Err(error) => {
+                    #error
+                }
+            };
+
+            fn typecheck_result(_: &napi::Result<()>) {}
+            let result = #rs_name(env_wrapper, exports_wrapper);
+            typecheck_result(&result);
+
+            match result {
+                Ok(()) => exports,
+                Err(error) => {
+                    #error
+                }
+            }
+        }
+    }
+}
+
+fn make_callback(args: CallbackArgs, sig: syn::Signature) -> syn::Result<proc_macro2::TokenStream> {
+    let rs_name = sig.ident.clone();
+    let (get_args, pass_args) = make_args(&args, &sig)?;
+    let c_name = args.c_name;
+    let error = return_error();
+
+    Ok(quote! {
         #[no_mangle]
         pub extern "C" fn #c_name(
             env: napi::sys::napi_env,
             cb_info: napi::sys::napi_callback_info,
         ) -> napi::sys::napi_value {
-            use std::error::Error;
             use napi::Value;
 
             let env_wrapper = napi::Env::from(env);
 
             #get_args
 
-            fn typecheck_result<T: napi::Value>(_: &napi::Result<T>) {}
+            fn typecheck_result<T: napi::IntoJs>(_: &napi::Result<T>) {}
             let result = #rs_name(env_wrapper #pass_args);
             typecheck_result(&result);
 
-            match result {
+            match result.and_then(|value| napi::IntoJs::into_js(value, env_wrapper)) {
                 Ok(value) => value.as_sys_value(),
                 Err(error) => {
                     #error
                 }
             }
         }
-    }
+    })
 }
 
 fn make_args(
-    args: syn::punctuated::Punctuated<syn::FnArg, syn::token::Comma>,
-) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
-    let count = args.len() - 1;
+    callback: &CallbackArgs,
+    sig: &syn::Signature,
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let receiver_args = callback.receiver_args();
+    if sig.inputs.len() < receiver_args {
+        return Err(syn::Error::new(
+            sig.paren_token.span,
+            "class members take `this` as the argument after `env`",
+        ));
+    }
 
-    if count == 0 {
-        return (quote! {}, quote! {});
+    let count = sig.inputs.len() - receiver_args;
+    let required = typedef::required_args(sig.inputs.iter().skip(receiver_args));
+    let this_ty = match sig.inputs.iter().nth(1) {
+        Some(syn::FnArg::Typed(typed)) if callback.class.is_some() => Some(typed.ty.clone()),
+        _ => None,
+    };
+
+    if count == 0 && this_ty.is_none() {
+        return Ok((quote! {}, quote! {}));
     }
 
-    let args = args
+    let args = sig
+        .inputs
         .iter()
-        .skip(1)
+        .skip(receiver_args)
         .map(|arg| {
             let (ident, ty) = match arg {
                 syn::FnArg::Typed(typed) => {
@@ -85,11 +173,20 @@ fn make_args(
         })
         .collect::<Vec<_>>();
 
+    let (arity_mismatch, expected) = if required == count {
+        (quote! { argc != #count }, count.to_string())
+    } else {
+        (
+            quote! { !(#required..=#count).contains(&argc) },
+            format!("{} to {}", required, count),
+        )
+    };
+
     let error = return_error();
 
     let exprs = args.iter().enumerate().map(|(index, (ident, ty))| {
         quote! {
-            let #ident = match <#ty as napi::Value>::from_sys_checked(env_wrapper, argv[#index]) {
+            let #ident = match <#ty as napi::FromJs>::from_js(napi::Any::with_value(env_wrapper, argv[#index])) {
                 Ok(value) => value,
                 Err(error) => {
                     #error
@@ -98,9 +195,29 @@ fn make_args(
         }
     });
 
+    let this_name = this_ty.as_ref().map(|_| quote! { fn_this });
+    let (this_arg, declare_this, get_this) = match this_ty {
+        Some(ty) => (
+            quote! { &mut this_arg },
+            quote! {
+                let mut this_arg = std::ptr::null_mut();
+            },
+            quote! {
+                let fn_this = match <#ty as napi::FromJs>::from_js(napi::Any::with_value(env_wrapper, this_arg)) {
+                    Ok(value) => value,
+                    Err(error) => {
+                        #error
+                    }
+                };
+            },
+        ),
+        None => (quote! { std::ptr::null_mut() }, quote! {}, quote! {}),
+    };
+
     let get_args = quote! {
         let mut argc = #count;
         let mut argv = [std::ptr::null_mut(); #count];
+        #declare_this
 
         let status = env_wrapper.handle_status(unsafe {
             napi::sys::napi_get_cb_info(
@@ -108,7 +225,7 @@ fn make_args(
                 cb_info,
                 &mut argc,
                 argv.as_mut_ptr(),
-                std::ptr::null_mut(),
+                #this_arg,
                 std::ptr::null_mut(),
             )
         });
@@ -117,10 +234,10 @@ fn make_args(
             #error
         }
 
-        if argc != #count {
+        if #arity_mismatch {
             let message = match napi::String::from_str(env_wrapper, &format!(
                 "Expected {} arguments, but got {}",
-                #count,
+                #expected,
                 argc,
             )) {
                 Ok(msg) => msg,
@@ -132,37 +249,23 @@ fn make_args(
             #error
         }
 
+        #get_this
         #(#exprs);*
     };
 
-    let arg_names = args.iter().map(|(ident, _)| ident);
+    let arg_names = this_name
+        .into_iter()
+        .chain(args.iter().map(|(ident, _)| quote! { #ident }));
     let pass_args = quote! {
         , #(#arg_names),*
     };
 
-    (get_args, pass_args)
+    Ok((get_args, pass_args))
 }
 
 fn return_error() -> proc_macro2::TokenStream {
     quote! {
-        if let Some(exception) = error.exception {
-            unsafe {
-                napi::sys::napi_throw(env, exception);
-            }
-        } else {
-            let message = format!("{}", &error);
-            let c_string = std::ffi::CString::new(message)
-                .unwrap_or_else(|_| std::ffi::CString::new(error.to_string()).unwrap());
-
-            unsafe {
-                napi::sys::napi_throw_error(env, std::ptr::null(), c_string.as_ptr());
-            }
-        }
-
-        let mut result: napi::sys::napi_value = std::ptr::null_mut();
-        unsafe {
-            napi::sys::napi_get_undefined(env, &mut result);
-        }
-        return result;
+        error.throw(env_wrapper);
+        return std::ptr::null_mut();
     }
 }
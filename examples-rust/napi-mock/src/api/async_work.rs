@@ -0,0 +1,375 @@
+use std::os::raw::c_void;
+
+use super::*;
+use crate::event_loop::{AsyncWork, ThreadsafeFunction};
+use crate::value::{Class, Finalizer, ObjectData, PromiseData};
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_promise(
+    env: sys::napi_env,
+    deferred: *mut sys::napi_deferred,
+    promise: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        if deferred.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        let mut state = env.state.borrow_mut();
+        let prototype = state.object_prototype;
+        let object = state.alloc(ObjectData::new(
+            Class::Promise(PromiseData::Pending),
+            Some(prototype),
+        ));
+
+        *deferred = state.new_reference(JsValue::Object(object), 1) as sys::napi_deferred;
+        write(promise, state.push(JsValue::Object(object)))
+    })
+}
+
+unsafe fn settle(
+    env: sys::napi_env,
+    deferred: sys::napi_deferred,
+    value: sys::napi_value,
+    settled: fn(JsValue) -> PromiseData,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let value = super::value(env, value)?;
+        let reference = deferred as sys::napi_ref;
+
+        let mut state = env.state.borrow_mut();
+        let promise = state
+            .reference(reference)?
+            .value
+            .as_object()
+            .ok_or(napi_invalid_arg)?;
+        state.references[reference as usize - 1] = None;
+
+        if let Class::Promise(ref mut promise_data) = state.object_mut(promise).class {
+            *promise_data = settled(value);
+        }
+
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_resolve_deferred(
+    env: sys::napi_env,
+    deferred: sys::napi_deferred,
+    resolution: sys::napi_value,
+) -> sys::napi_status {
+    settle(env, deferred, resolution, PromiseData::Fulfilled)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_reject_deferred(
+    env: sys::napi_env,
+    deferred: sys::napi_deferred,
+    rejection: sys::napi_value,
+) -> sys::napi_status {
+    settle(env, deferred, rejection, PromiseData::Rejected)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_is_promise(
+    env: sys::napi_env,
+    promise: sys::napi_value,
+    is_promise: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let promise = is_class(env, promise, |class| matches!(class, Class::Promise(_)))?;
+        write(is_promise, promise)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_async_work(
+    env: sys::napi_env,
+    _async_resource: sys::napi_value,
+    _async_resource_name: sys::napi_value,
+    execute: sys::napi_async_execute_callback,
+    complete: sys::napi_async_complete_callback,
+    data: *mut c_void,
+    result: *mut sys::napi_async_work,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let execute = execute.ok_or(napi_invalid_arg)?;
+        let work = Box::new(AsyncWork {
+            execute,
+            complete,
+            data,
+            event_loop: env.event_loop.clone(),
+            queued: false,
+        });
+
+        write(result, Box::into_raw(work) as sys::napi_async_work)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_delete_async_work(
+    env: sys::napi_env,
+    work: sys::napi_async_work,
+) -> sys::napi_status {
+    napi_call(env, |_| {
+        if work.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        drop(Box::from_raw(work as *mut AsyncWork));
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_queue_async_work(
+    env: sys::napi_env,
+    work: sys::napi_async_work,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        if work.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        match AsyncWork::queue(work as *mut AsyncWork, env) {
+            napi_ok => Ok(()),
+            status => Err(status),
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_cancel_async_work(
+    env: sys::napi_env,
+    work: sys::napi_async_work,
+) -> sys::napi_status {
+    napi_call(env, |_| {
+        if work.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        Err(napi_generic_failure)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_async_init(
+    env: sys::napi_env,
+    _async_resource: sys::napi_value,
+    async_resource_name: sys::napi_value,
+    result: *mut sys::napi_async_context,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        value(env, async_resource_name)?;
+        write(
+            result,
+            Box::into_raw(Box::new(0u8)) as sys::napi_async_context,
+        )
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_async_destroy(
+    env: sys::napi_env,
+    async_context: sys::napi_async_context,
+) -> sys::napi_status {
+    napi_call(env, |_| {
+        if async_context.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        drop(Box::from_raw(async_context as *mut u8));
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_make_callback(
+    env: sys::napi_env,
+    _async_context: sys::napi_async_context,
+    recv: sys::napi_value,
+    func: sys::napi_value,
+    argc: usize,
+    argv: *const sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    super::functions::napi_call_function(env, recv, func, argc, argv, result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_open_callback_scope(
+    env: sys::napi_env,
+    _resource_object: sys::napi_value,
+    _context: sys::napi_async_context,
+    result: *mut sys::napi_callback_scope,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        state.callback_scopes += 1;
+        write(result, state.callback_scopes as sys::napi_callback_scope)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_close_callback_scope(
+    env: sys::napi_env,
+    scope: sys::napi_callback_scope,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        if state.callback_scopes == 0 || scope as usize != state.callback_scopes {
+            return Err(napi_callback_scope_mismatch);
+        }
+
+        state.callback_scopes -= 1;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_uv_event_loop(
+    env: sys::napi_env,
+    _loop: *mut *mut sys::uv_loop_s,
+) -> sys::napi_status {
+    napi_call(env, |_| Err(napi_generic_failure))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_threadsafe_function(
+    env: sys::napi_env,
+    func: sys::napi_value,
+    _async_resource: sys::napi_value,
+    async_resource_name: sys::napi_value,
+    max_queue_size: usize,
+    initial_thread_count: usize,
+    thread_finalize_data: *mut c_void,
+    thread_finalize_cb: sys::napi_finalize,
+    context: *mut c_void,
+    call_js_cb: sys::napi_threadsafe_function_call_js,
+    result: *mut sys::napi_threadsafe_function,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        if initial_thread_count == 0 || result.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        value(env, async_resource_name)?;
+
+        let function = if func.is_null() {
+            if call_js_cb.is_none() {
+                return Err(napi_invalid_arg);
+            }
+            std::ptr::null_mut()
+        } else {
+            let func = value(env, func)?;
+            env.function(&func)?;
+            env.state.borrow_mut().new_reference(func, 1)
+        };
+
+        let finalizer = thread_finalize_cb.map(|callback| Finalizer {
+            callback,
+            data: thread_finalize_data,
+            hint: context,
+        });
+
+        let function = ThreadsafeFunction::new(
+            env,
+            function,
+            context,
+            call_js_cb,
+            finalizer,
+            max_queue_size,
+            initial_thread_count,
+        );
+
+        write(
+            result,
+            Box::into_raw(Box::new(function)) as sys::napi_threadsafe_function,
+        )
+    })
+}
+
+unsafe fn threadsafe_function<'a>(
+    func: sys::napi_threadsafe_function,
+) -> Option<&'a ThreadsafeFunction> {
+    (func as *const ThreadsafeFunction).as_ref()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_threadsafe_function_context(
+    func: sys::napi_threadsafe_function,
+    result: *mut *mut c_void,
+) -> sys::napi_status {
+    match (threadsafe_function(func), result.is_null()) {
+        (Some(func), false) => {
+            *result = func.context;
+            napi_ok
+        }
+        _ => napi_invalid_arg,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_call_threadsafe_function(
+    func: sys::napi_threadsafe_function,
+    data: *mut c_void,
+    is_blocking: sys::napi_threadsafe_function_call_mode,
+) -> sys::napi_status {
+    match threadsafe_function(func) {
+        Some(func) => func.call(
+            data,
+            is_blocking == sys::napi_threadsafe_function_call_mode::napi_tsfn_blocking,
+        ),
+        None => napi_invalid_arg,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_acquire_threadsafe_function(
+    func: sys::napi_threadsafe_function,
+) -> sys::napi_status {
+    match threadsafe_function(func) {
+        Some(func) => func.acquire(),
+        None => napi_invalid_arg,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_release_threadsafe_function(
+    func: sys::napi_threadsafe_function,
+    mode: sys::napi_threadsafe_function_release_mode,
+) -> sys::napi_status {
+    match threadsafe_function(func) {
+        Some(func) => {
+            func.release(mode == sys::napi_threadsafe_function_release_mode::napi_tsfn_abort)
+        }
+        None => napi_invalid_arg,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_unref_threadsafe_function(
+    env: sys::napi_env,
+    func: sys::napi_threadsafe_function,
+) -> sys::napi_status {
+    napi_call(env, |_| {
+        threadsafe_function(func)
+            .ok_or(napi_invalid_arg)?
+            .set_referenced(false);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_ref_threadsafe_function(
+    env: sys::napi_env,
+    func: sys::napi_threadsafe_function,
+) -> sys::napi_status {
+    napi_call(env, |_| {
+        threadsafe_function(func)
+            .ok_or(napi_invalid_arg)?
+            .set_referenced(true);
+        Ok(())
+    })
+}
@@ -0,0 +1,384 @@
+use std::os::raw::c_void;
+use std::ptr;
+
+use super::*;
+use crate::runtime::ErrorKind;
+use crate::value::{
+    element_size, element_type_name, is_element_type, ArrayBufferData, Class, ObjectData, ViewData,
+};
+
+fn new_array_buffer(
+    env: &Env,
+    buffer: ArrayBufferData,
+    finalizer: Option<crate::value::Finalizer>,
+) -> ObjectId {
+    let mut state = env.state.borrow_mut();
+    let prototype = state.object_prototype;
+    let mut object = ObjectData::new(Class::ArrayBuffer(buffer), Some(prototype));
+    object.finalizers.extend(finalizer);
+    state.alloc(object)
+}
+
+fn owned_array_buffer(env: &Env, byte_length: usize) -> (ObjectId, *mut u8) {
+    let mut storage = vec![0; byte_length].into_boxed_slice();
+    let data = storage.as_mut_ptr();
+    let buffer = ArrayBufferData {
+        data,
+        byte_length,
+        _storage: Some(storage),
+    };
+
+    (new_array_buffer(env, buffer, None), data)
+}
+
+fn external_array_buffer(
+    env: &Env,
+    data: *mut c_void,
+    byte_length: usize,
+    finalize_cb: sys::napi_finalize,
+    finalize_hint: *mut c_void,
+) -> ObjectId {
+    let buffer = ArrayBufferData {
+        data: data as *mut u8,
+        byte_length,
+        _storage: None,
+    };
+    let finalizer = finalize_cb.map(|callback| crate::value::Finalizer {
+        callback,
+        data,
+        hint: finalize_hint,
+    });
+
+    new_array_buffer(env, buffer, finalizer)
+}
+
+fn new_view(env: &Env, class: Class) -> sys::napi_value {
+    let mut state = env.state.borrow_mut();
+    let prototype = state.object_prototype;
+    let view = state.alloc(ObjectData::new(class, Some(prototype)));
+    state.push(JsValue::Object(view))
+}
+
+fn array_buffer(env: &Env, value: sys::napi_value) -> NapiResult<(ObjectId, *mut u8, usize)> {
+    let value = super::value(env, value)?;
+    let id = value.as_object().ok_or(napi_invalid_arg)?;
+    let state = env.state.borrow();
+    let buffer = state.array_buffer(id).ok_or(napi_invalid_arg)?;
+    Ok((id, buffer.data, buffer.byte_length))
+}
+
+fn view(env: &Env, value: sys::napi_value) -> NapiResult<(Class, ViewData, *mut u8)> {
+    let value = super::value(env, value)?;
+    let id = value.as_object().ok_or(napi_invalid_arg)?;
+    let state = env.state.borrow();
+
+    let (class, view) = match state.object(id).class {
+        Class::TypedArray(array_type, view) => (Class::TypedArray(array_type, view), view),
+        Class::DataView(view) => (Class::DataView(view), view),
+        _ => return Err(napi_invalid_arg),
+    };
+
+    let data = state
+        .array_buffer(view.buffer)
+        .map_or(ptr::null_mut(), |buffer| unsafe {
+            buffer.data.add(view.byte_offset)
+        });
+
+    Ok((class, view, data))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_is_arraybuffer(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let is_array_buffer = is_class(env, value, |class| matches!(class, Class::ArrayBuffer(_)))?;
+        write(result, is_array_buffer)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_arraybuffer(
+    env: sys::napi_env,
+    byte_length: usize,
+    data: *mut *mut c_void,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (buffer, buffer_data) = owned_array_buffer(env, byte_length);
+        write_opt(data, buffer_data as *mut c_void);
+        write(result, push(env, JsValue::Object(buffer)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_external_arraybuffer(
+    env: sys::napi_env,
+    external_data: *mut c_void,
+    byte_length: usize,
+    finalize_cb: sys::napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let buffer =
+            external_array_buffer(env, external_data, byte_length, finalize_cb, finalize_hint);
+        write(result, push(env, JsValue::Object(buffer)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_arraybuffer_info(
+    env: sys::napi_env,
+    arraybuffer: sys::napi_value,
+    data: *mut *mut c_void,
+    byte_length: *mut usize,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let (_, buffer_data, buffer_length) = array_buffer(env, arraybuffer)?;
+        write_opt(data, buffer_data as *mut c_void);
+        write_opt(byte_length, buffer_length);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_is_typedarray(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let is_typed_array = is_class(env, value, |class| matches!(class, Class::TypedArray(..)))?;
+        write(result, is_typed_array)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_typedarray(
+    env: sys::napi_env,
+    type_: sys::napi_typedarray_type,
+    length: usize,
+    arraybuffer: sys::napi_value,
+    byte_offset: usize,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        if !is_element_type(type_) {
+            return Err(napi_invalid_arg);
+        }
+
+        let (buffer, _, buffer_length) = array_buffer(env, arraybuffer)?;
+        let size = element_size(type_);
+
+        if !byte_offset.is_multiple_of(size) {
+            let message = format!(
+                "start offset of {} should be a multiple of {}",
+                element_type_name(type_),
+                size
+            );
+            return Err(env.throw_error(ErrorKind::RangeError, &message));
+        }
+
+        if length * size + byte_offset > buffer_length {
+            return Err(env.throw_error(ErrorKind::RangeError, "Invalid typed array length"));
+        }
+
+        let view = ViewData {
+            buffer,
+            byte_offset,
+            byte_length: length * size,
+        };
+        write(result, new_view(env, Class::TypedArray(type_, view)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_typedarray_info(
+    env: sys::napi_env,
+    typedarray: sys::napi_value,
+    type_: *mut sys::napi_typedarray_type,
+    length: *mut usize,
+    data: *mut *mut c_void,
+    arraybuffer: *mut sys::napi_value,
+    byte_offset: *mut usize,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let (array_type, view, view_data) = match view(env, typedarray)? {
+            (Class::TypedArray(array_type, view), _, data) => (array_type, view, data),
+            _ => return Err(napi_invalid_arg),
+        };
+
+        write_opt(type_, array_type);
+        write_opt(length, view.byte_length / element_size(array_type));
+        write_opt(data, view_data as *mut c_void);
+        if !arraybuffer.is_null() {
+            *arraybuffer = push(env, JsValue::Object(view.buffer));
+        }
+        write_opt(byte_offset, view.byte_offset);
+
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_dataview(
+    env: sys::napi_env,
+    length: usize,
+    arraybuffer: sys::napi_value,
+    byte_offset: usize,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (buffer, _, buffer_length) = array_buffer(env, arraybuffer)?;
+
+        if byte_offset + length > buffer_length {
+            return Err(env.throw_error(
+                ErrorKind::RangeError,
+                "byte_offset + byte_length should be less than or equal to the size in bytes \
+                 of the array passed in",
+            ));
+        }
+
+        let view = ViewData {
+            buffer,
+            byte_offset,
+            byte_length: length,
+        };
+        write(result, new_view(env, Class::DataView(view)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_is_dataview(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let is_data_view = is_class(env, value, |class| matches!(class, Class::DataView(_)))?;
+        write(result, is_data_view)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_dataview_info(
+    env: sys::napi_env,
+    dataview: sys::napi_value,
+    bytelength: *mut usize,
+    data: *mut *mut c_void,
+    arraybuffer: *mut sys::napi_value,
+    byte_offset: *mut usize,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let (view, view_data) = match view(env, dataview)? {
+            (Class::DataView(view), _, data) => (view, data),
+            _ => return Err(napi_invalid_arg),
+        };
+
+        write_opt(bytelength, view.byte_length);
+        write_opt(data, view_data as *mut c_void);
+        if !arraybuffer.is_null() {
+            *arraybuffer = push(env, JsValue::Object(view.buffer));
+        }
+        write_opt(byte_offset, view.byte_offset);
+
+        Ok(())
+    })
+}
+
+fn new_buffer(env: &Env, buffer: ObjectId, length: usize) -> sys::napi_value {
+    let view = ViewData {
+        buffer,
+        byte_offset: 0,
+        byte_length: length,
+    };
+
+    new_view(
+        env,
+        Class::TypedArray(sys::napi_typedarray_type::napi_uint8_array, view),
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_buffer(
+    env: sys::napi_env,
+    length: usize,
+    data: *mut *mut c_void,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (buffer, buffer_data) = owned_array_buffer(env, length);
+        write_opt(data, buffer_data as *mut c_void);
+        write(result, new_buffer(env, buffer, length))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_external_buffer(
+    env: sys::napi_env,
+    length: usize,
+    data: *mut c_void,
+    finalize_cb: sys::napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let buffer = external_array_buffer(env, data, length, finalize_cb, finalize_hint);
+        write(result, new_buffer(env, buffer, length))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_buffer_copy(
+    env: sys::napi_env,
+    length: usize,
+    data: *const c_void,
+    result_data: *mut *mut c_void,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        if length > 0 && data.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        let (buffer, buffer_data) = owned_array_buffer(env, length);
+        if length > 0 {
+            ptr::copy_nonoverlapping(data as *const u8, buffer_data, length);
+        }
+
+        write_opt(result_data, buffer_data as *mut c_void);
+        write(result, new_buffer(env, buffer, length))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_is_buffer(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let is_buffer = is_class(env, value, |class| {
+            matches!(class, Class::TypedArray(..) | Class::DataView(_))
+        })?;
+        write(result, is_buffer)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_buffer_info(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    data: *mut *mut c_void,
+    length: *mut usize,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let (_, view, view_data) = view(env, value)?;
+        write_opt(data, view_data as *mut c_void);
+        write_opt(length, view.byte_length);
+        Ok(())
+    })
+}
@@ -0,0 +1,200 @@
+use std::os::raw::c_char;
+
+use super::*;
+use crate::runtime::ErrorKind;
+use crate::value::Class;
+
+unsafe fn create_error(
+    env: sys::napi_env,
+    kind: ErrorKind,
+    code: sys::napi_value,
+    msg: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let message = match value(env, msg)? {
+            message @ JsValue::String(_) => message,
+            _ => return Err(napi_string_expected),
+        };
+
+        let code = if code.is_null() {
+            None
+        } else {
+            match value(env, code)? {
+                code @ JsValue::String(_) => Some(code),
+                _ => return Err(napi_string_expected),
+            }
+        };
+
+        let mut state = env.state.borrow_mut();
+        let error = state.new_error(kind, code, message);
+        write(result, state.push(JsValue::Object(error)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_error(
+    env: sys::napi_env,
+    code: sys::napi_value,
+    msg: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    create_error(env, ErrorKind::Error, code, msg, result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_type_error(
+    env: sys::napi_env,
+    code: sys::napi_value,
+    msg: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    create_error(env, ErrorKind::TypeError, code, msg, result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_range_error(
+    env: sys::napi_env,
+    code: sys::napi_value,
+    msg: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    create_error(env, ErrorKind::RangeError, code, msg, result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_throw(
+    env: sys::napi_env,
+    error: sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let error = value(env, error)?;
+        env.throw(error);
+        Ok(())
+    })
+}
+
+unsafe fn throw_error(
+    env: sys::napi_env,
+    kind: ErrorKind,
+    code: *const c_char,
+    msg: *const c_char,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let message = JsValue::from_str(&c_string(msg, NAPI_AUTO_LENGTH)?);
+        let code = if code.is_null() {
+            None
+        } else {
+            Some(JsValue::from_str(&c_string(code, NAPI_AUTO_LENGTH)?))
+        };
+
+        let error = env.state.borrow_mut().new_error(kind, code, message);
+        env.throw(JsValue::Object(error));
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_throw_error(
+    env: sys::napi_env,
+    code: *const c_char,
+    msg: *const c_char,
+) -> sys::napi_status {
+    throw_error(env, ErrorKind::Error, code, msg)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_throw_type_error(
+    env: sys::napi_env,
+    code: *const c_char,
+    msg: *const c_char,
+) -> sys::napi_status {
+    throw_error(env, ErrorKind::TypeError, code, msg)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_throw_range_error(
+    env: sys::napi_env,
+    code: *const c_char,
+    msg: *const c_char,
+) -> sys::napi_status {
+    throw_error(env, ErrorKind::RangeError, code, msg)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_is_error(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let is_error = is_class(env, value, |class| matches!(class, Class::Error))?;
+        write(result, is_error)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_is_exception_pending(
+    env: sys::napi_env,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let pending = env.state.borrow().exception.is_some();
+        write(result, pending)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_and_clear_last_exception(
+    env: sys::napi_env,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        let exception = state.exception.take().unwrap_or(JsValue::Undefined);
+        write(result, state.push(exception))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_last_error_info(
+    env: sys::napi_env,
+    result: *mut *const sys::napi_extended_error_info,
+) -> sys::napi_status {
+    let env = match Env::from_sys(env) {
+        Some(env) => env,
+        None => return napi_invalid_arg,
+    };
+
+    match write(result, env.last_error()) {
+        Ok(()) => napi_ok,
+        Err(status) => status,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_fatal_error(
+    location: *const c_char,
+    location_len: usize,
+    message: *const c_char,
+    message_len: usize,
+) {
+    let location = c_string(location, location_len).unwrap_or_default();
+    let message = c_string(message, message_len).unwrap_or_default();
+    panic!("FATAL ERROR: {} {}", location, message);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_fatal_exception(
+    env: sys::napi_env,
+    err: sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let error = value(env, err)?;
+        let message = env.state.borrow().to_string(&error);
+        let message = message
+            .map(|message| String::from_utf16_lossy(&message))
+            .unwrap_or_default();
+        panic!("uncaught exception: {}", message);
+    })
+}
@@ -0,0 +1,196 @@
+use std::os::raw::{c_char, c_void};
+
+use super::objects::descriptor_property;
+use super::*;
+use crate::runtime::{CallbackInfo, ErrorKind};
+use crate::value::{Function, PropertyKey};
+
+unsafe fn args(env: &Env, argc: usize, argv: *const sys::napi_value) -> NapiResult<Vec<JsValue>> {
+    if argc > 0 && argv.is_null() {
+        return Err(napi_invalid_arg);
+    }
+
+    (0..argc)
+        .map(|index| value(env, *argv.add(index)))
+        .collect()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_function(
+    env: sys::napi_env,
+    utf8name: *const c_char,
+    length: usize,
+    cb: sys::napi_callback,
+    data: *mut c_void,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let callback = cb.ok_or(napi_invalid_arg)?;
+        let name = c_string(utf8name, if utf8name.is_null() { 0 } else { length })?;
+
+        let mut state = env.state.borrow_mut();
+        let function = state.new_function(&name, Function { callback, data });
+        write(result, state.push(JsValue::Object(function)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_call_function(
+    env: sys::napi_env,
+    recv: sys::napi_value,
+    func: sys::napi_value,
+    argc: usize,
+    argv: *const sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let this = value(env, recv)?;
+        let function = env.function(&value(env, func)?)?;
+        let args = args(env, argc, argv)?;
+
+        let value = env.call(function, this, &args, None)?;
+        write_opt(result, push(env, value));
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_new_instance(
+    env: sys::napi_env,
+    constructor: sys::napi_value,
+    argc: usize,
+    argv: *const sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let constructor = value(env, constructor)?;
+        let args = args(env, argc, argv)?;
+
+        let instance = env.construct(&constructor, &args)?;
+        write(result, push(env, instance))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_instanceof(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    constructor: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let object = value(env, object)?;
+        let constructor = value(env, constructor)?;
+
+        if env.function(&constructor).is_err() {
+            env.throw_error(ErrorKind::TypeError, "Constructor must be a function");
+            return Err(napi_function_expected);
+        }
+
+        let prototype = env.get(&constructor, &PropertyKey::from_str("prototype"))?;
+        let is_instance = match (object.as_object(), prototype.as_object()) {
+            (Some(object), Some(prototype)) => env.state.borrow().is_instance(object, prototype),
+            _ => false,
+        };
+
+        write(result, is_instance)
+    })
+}
+
+unsafe fn callback_info<'a>(cbinfo: sys::napi_callback_info) -> NapiResult<&'a CallbackInfo> {
+    (cbinfo as *const CallbackInfo)
+        .as_ref()
+        .ok_or(napi_invalid_arg)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_cb_info(
+    env: sys::napi_env,
+    cbinfo: sys::napi_callback_info,
+    argc: *mut usize,
+    argv: *mut sys::napi_value,
+    this_arg: *mut sys::napi_value,
+    data: *mut *mut c_void,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let info = callback_info(cbinfo)?;
+
+        if !argv.is_null() {
+            if argc.is_null() {
+                return Err(napi_invalid_arg);
+            }
+
+            for index in 0..*argc {
+                let arg = match info.args.get(index) {
+                    Some(&arg) => arg,
+                    None => push(env, JsValue::Undefined),
+                };
+                *argv.add(index) = arg;
+            }
+        }
+
+        write_opt(argc, info.args.len());
+        write_opt(this_arg, info.this);
+        write_opt(data, info.data);
+
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_new_target(
+    env: sys::napi_env,
+    cbinfo: sys::napi_callback_info,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |_| {
+        let info = callback_info(cbinfo)?;
+        write(result, info.new_target)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_define_class(
+    env: sys::napi_env,
+    utf8name: *const c_char,
+    length: usize,
+    constructor: sys::napi_callback,
+    data: *mut c_void,
+    property_count: usize,
+    properties: *const sys::napi_property_descriptor,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let callback = constructor.ok_or(napi_invalid_arg)?;
+        let name = c_string(utf8name, length)?;
+        if property_count > 0 && properties.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        let class = env
+            .state
+            .borrow_mut()
+            .new_function(&name, Function { callback, data });
+        let prototype = env
+            .state
+            .borrow()
+            .lookup_value(class, &PropertyKey::from_str("prototype"))
+            .as_object()
+            .ok_or(napi_generic_failure)?;
+
+        for index in 0..property_count {
+            let descriptor = &*properties.add(index);
+            let is_static = descriptor.attributes & sys::napi_property_attributes::napi_static
+                == sys::napi_property_attributes::napi_static;
+            let target = if is_static { class } else { prototype };
+
+            let (key, property) = descriptor_property(env, descriptor)?;
+            env.state
+                .borrow_mut()
+                .object_mut(target)
+                .define(key, property);
+        }
+
+        write(result, push(env, JsValue::Object(class)))
+    })
+}
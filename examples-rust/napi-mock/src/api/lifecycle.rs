@@ -0,0 +1,406 @@
+use std::os::raw::c_void;
+
+use super::*;
+use crate::value::{Class, Finalizer, ObjectData};
+
+fn finalizer(
+    callback: sys::napi_finalize,
+    data: *mut c_void,
+    hint: *mut c_void,
+) -> Option<Finalizer> {
+    callback.map(|callback| Finalizer {
+        callback,
+        data,
+        hint,
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_reference(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    initial_refcount: u32,
+    result: *mut sys::napi_ref,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let value = super::value(env, value)?;
+        match value {
+            JsValue::Object(_) | JsValue::Symbol(_) => {}
+            _ => return Err(napi_invalid_arg),
+        }
+
+        let reference = env
+            .state
+            .borrow_mut()
+            .new_reference(value, initial_refcount);
+        write(result, reference)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_delete_reference(
+    env: sys::napi_env,
+    reference: sys::napi_ref,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        state.reference(reference)?;
+        let deleted = state.references[reference as usize - 1].take().unwrap();
+
+        if let (JsValue::Object(id), Some(data)) = (deleted.value, deleted.finalizer) {
+            if let Some(object) = state.objects[id].as_mut() {
+                object.finalizers.retain(|finalizer| finalizer.data != data);
+            }
+        }
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_reference_ref(
+    env: sys::napi_env,
+    reference: sys::napi_ref,
+    result: *mut u32,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        let reference = state.reference(reference)?;
+        reference.count += 1;
+        write_opt(result, reference.count);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_reference_unref(
+    env: sys::napi_env,
+    reference: sys::napi_ref,
+    result: *mut u32,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        let reference = state.reference(reference)?;
+        if reference.count == 0 {
+            return Err(napi_generic_failure);
+        }
+
+        reference.count -= 1;
+        write_opt(result, reference.count);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_reference_value(
+    env: sys::napi_env,
+    reference: sys::napi_ref,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        let value = state.reference(reference)?.value.clone();
+        let handle = match value {
+            JsValue::Undefined => std::ptr::null_mut(),
+            value => state.push(value),
+        };
+        write(result, handle)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_open_handle_scope(
+    env: sys::napi_env,
+    result: *mut sys::napi_handle_scope,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let scope = env.state.borrow_mut().open_scope(false);
+        write(result, scope as sys::napi_handle_scope)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_close_handle_scope(
+    env: sys::napi_env,
+    scope: sys::napi_handle_scope,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        env.state.borrow_mut().close_scope(scope as usize)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_open_escapable_handle_scope(
+    env: sys::napi_env,
+    result: *mut sys::napi_escapable_handle_scope,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let scope = env.state.borrow_mut().open_scope(true);
+        write(result, scope as sys::napi_escapable_handle_scope)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_close_escapable_handle_scope(
+    env: sys::napi_env,
+    scope: sys::napi_escapable_handle_scope,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        env.state.borrow_mut().close_scope(scope as usize)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_escape_handle(
+    env: sys::napi_env,
+    scope: sys::napi_escapable_handle_scope,
+    escapee: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let value = super::value(env, escapee)?;
+        let mut state = env.state.borrow_mut();
+
+        let index = (scope as usize).checked_sub(1).ok_or(napi_invalid_arg)?;
+        let scope = state.scopes.get_mut(index).ok_or(napi_invalid_arg)?;
+        let slot = scope.escape_slot.ok_or(napi_invalid_arg)?;
+        if scope.escaped {
+            return Err(napi_escape_called_twice);
+        }
+
+        scope.escaped = true;
+        state.handles[slot] = value;
+        write(result, (slot + 1) as sys::napi_value)
+    })
+}
+
+unsafe fn weak_reference(env: &Env, object: JsValue, result: *mut sys::napi_ref) {
+    if !result.is_null() {
+        *result = env.state.borrow_mut().new_reference(object, 0);
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_wrap(
+    env: sys::napi_env,
+    js_object: sys::napi_value,
+    native_object: *mut c_void,
+    finalize_cb: sys::napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut sys::napi_ref,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (object, id) = super::object(env, js_object)?;
+
+        {
+            let mut state = env.state.borrow_mut();
+            let object = state.object_mut(id);
+            if object.wrap.is_some() {
+                return Err(napi_invalid_arg);
+            }
+            object.wrap = Some((
+                native_object,
+                finalizer(finalize_cb, native_object, finalize_hint),
+            ));
+        }
+
+        weak_reference(env, object, result);
+        Ok(())
+    })
+}
+
+unsafe fn unwrap(
+    env: sys::napi_env,
+    js_object: sys::napi_value,
+    result: *mut *mut c_void,
+    remove: bool,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, js_object)?;
+        let mut state = env.state.borrow_mut();
+        let object = state.object_mut(id);
+
+        let (native_object, _) = object.wrap.ok_or(napi_invalid_arg)?;
+        if remove {
+            object.wrap = None;
+        }
+
+        write_opt(result, native_object);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_unwrap(
+    env: sys::napi_env,
+    js_object: sys::napi_value,
+    result: *mut *mut c_void,
+) -> sys::napi_status {
+    if result.is_null() {
+        return napi_call(env, |_| Err(napi_invalid_arg));
+    }
+
+    unwrap(env, js_object, result, false)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_remove_wrap(
+    env: sys::napi_env,
+    js_object: sys::napi_value,
+    result: *mut *mut c_void,
+) -> sys::napi_status {
+    unwrap(env, js_object, result, true)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_type_tag_object(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    type_tag: *const sys::napi_type_tag,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let (_, id) = super::object(env, value)?;
+        let type_tag = type_tag.as_ref().ok_or(napi_invalid_arg)?;
+        let mut state = env.state.borrow_mut();
+        let object = state.object_mut(id);
+
+        if object.type_tag.is_some() {
+            return Err(napi_invalid_arg);
+        }
+        object.type_tag = Some((type_tag.lower, type_tag.upper));
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_check_object_type_tag(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    type_tag: *const sys::napi_type_tag,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let (_, id) = super::object(env, value)?;
+        let type_tag = type_tag.as_ref().ok_or(napi_invalid_arg)?;
+        let tagged =
+            env.state.borrow().object(id).type_tag == Some((type_tag.lower, type_tag.upper));
+        write(result, tagged)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_external(
+    env: sys::napi_env,
+    data: *mut c_void,
+    finalize_cb: sys::napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        let mut external = ObjectData::new(Class::External(data), None);
+        external
+            .finalizers
+            .extend(finalizer(finalize_cb, data, finalize_hint));
+
+        let external = state.alloc(external);
+        write(result, state.push(JsValue::Object(external)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_external(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut *mut c_void,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let value = super::value(env, value)?;
+        let state = env.state.borrow();
+        match value.as_object().map(|id| &state.object(id).class) {
+            Some(Class::External(data)) => write(result, *data),
+            _ => Err(napi_invalid_arg),
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_add_finalizer(
+    env: sys::napi_env,
+    js_object: sys::napi_value,
+    native_object: *mut c_void,
+    finalize_cb: sys::napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut sys::napi_ref,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let (object, id) = super::object(env, js_object)?;
+        let finalizer =
+            finalizer(finalize_cb, native_object, finalize_hint).ok_or(napi_invalid_arg)?;
+
+        let mut state = env.state.borrow_mut();
+        state.object_mut(id).finalizers.push(finalizer);
+
+        if !result.is_null() {
+            let reference = state.new_reference(object, 0);
+            state.reference(reference)?.finalizer = Some(native_object);
+            *result = reference;
+        }
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_add_env_cleanup_hook(
+    env: sys::napi_env,
+    fun: Option<unsafe extern "C" fn(arg: *mut c_void)>,
+    arg: *mut c_void,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let fun = fun.ok_or(napi_invalid_arg)?;
+        env.state.borrow_mut().cleanup_hooks.push((fun, arg));
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_remove_env_cleanup_hook(
+    env: sys::napi_env,
+    fun: Option<unsafe extern "C" fn(arg: *mut c_void)>,
+    arg: *mut c_void,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let fun = fun.ok_or(napi_invalid_arg)?;
+        env.state
+            .borrow_mut()
+            .cleanup_hooks
+            .retain(|&(hook, hook_arg)| hook as usize != fun as usize || hook_arg != arg);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_set_instance_data(
+    env: sys::napi_env,
+    data: *mut c_void,
+    finalize_cb: sys::napi_finalize,
+    finalize_hint: *mut c_void,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        env.state.borrow_mut().instance_data = (data, finalizer(finalize_cb, data, finalize_hint));
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_instance_data(
+    env: sys::napi_env,
+    data: *mut *mut c_void,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let (instance_data, _) = env.state.borrow().instance_data;
+        write(data, instance_data)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_module_register(_module: *mut sys::napi_module) {}
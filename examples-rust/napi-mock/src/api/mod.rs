@@ -0,0 +1,100 @@
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use crate::consts::napi_status::*;
+use crate::runtime::{Env, NapiResult};
+use crate::sys;
+use crate::value::{Class, JsValue, ObjectId};
+
+mod async_work;
+mod buffers;
+mod errors;
+mod functions;
+mod lifecycle;
+mod objects;
+mod values;
+
+const NAPI_AUTO_LENGTH: usize = usize::MAX;
+
+unsafe fn napi_call<F>(env: sys::napi_env, f: F) -> sys::napi_status
+where
+    F: FnOnce(&Env) -> NapiResult<()>,
+{
+    let env = match Env::from_sys(env) {
+        Some(env) => env,
+        None => return napi_invalid_arg,
+    };
+
+    let status = match f(env) {
+        Ok(()) => napi_ok,
+        Err(status) => status,
+    };
+
+    env.set_last_error(status);
+    status
+}
+
+unsafe fn napi_call_js<F>(env: sys::napi_env, f: F) -> sys::napi_status
+where
+    F: FnOnce(&Env) -> NapiResult<()>,
+{
+    napi_call(env, |env| {
+        env.check_pending_exception()?;
+        f(env)
+    })
+}
+
+unsafe fn write<T>(result: *mut T, value: T) -> NapiResult<()> {
+    if result.is_null() {
+        return Err(napi_invalid_arg);
+    }
+
+    result.write(value);
+    Ok(())
+}
+
+unsafe fn write_opt<T>(result: *mut T, value: T) {
+    if !result.is_null() {
+        result.write(value);
+    }
+}
+
+fn value(env: &Env, handle: sys::napi_value) -> NapiResult<JsValue> {
+    env.state.borrow().value(handle)
+}
+
+fn push(env: &Env, value: JsValue) -> sys::napi_value {
+    env.state.borrow_mut().push(value)
+}
+
+fn object(env: &Env, handle: sys::napi_value) -> NapiResult<(JsValue, ObjectId)> {
+    let value = value(env, handle)?;
+    let id = value.as_object().ok_or(napi_object_expected)?;
+    Ok((value, id))
+}
+
+fn is_class(env: &Env, value: sys::napi_value, f: impl Fn(&Class) -> bool) -> NapiResult<bool> {
+    let value = self::value(env, value)?;
+    let state = env.state.borrow();
+    Ok(value
+        .as_object()
+        .is_some_and(|id| f(&state.object(id).class)))
+}
+
+unsafe fn c_string(string: *const c_char, length: usize) -> NapiResult<String> {
+    if string.is_null() {
+        return if length == 0 {
+            Ok(String::new())
+        } else {
+            Err(napi_invalid_arg)
+        };
+    }
+
+    let bytes = if length == NAPI_AUTO_LENGTH {
+        CStr::from_ptr(string).to_bytes()
+    } else {
+        std::slice::from_raw_parts(string as *const u8, length)
+    };
+
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
@@ -0,0 +1,375 @@
+use std::os::raw::c_char;
+
+use super::*;
+use crate::value::{Class, Function, Property, PropertyKey};
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_object(
+    env: sys::napi_env,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        let object = state.new_object();
+        write(result, state.push(JsValue::Object(object)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_array(
+    env: sys::napi_env,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_create_array_with_length(env, 0, result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_array_with_length(
+    env: sys::napi_env,
+    length: usize,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        let array = state.new_array(vec![None; length]);
+        write(result, state.push(JsValue::Object(array)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_is_array(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let is_array = is_class(env, value, |class| matches!(class, Class::Array(_)))?;
+        write(result, is_array)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_array_length(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut u32,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let value = super::value(env, value)?;
+        let state = env.state.borrow();
+        match value.as_object().map(|id| &state.object(id).class) {
+            Some(Class::Array(elements)) => write(result, elements.len() as u32),
+            _ => Err(napi_array_expected),
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_prototype(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, object)?;
+        let prototype = env.state.borrow().object(id).prototype;
+        let prototype = prototype.map_or(JsValue::Null, JsValue::Object);
+        write(result, push(env, prototype))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_property_names(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, object)?;
+        let mut state = env.state.borrow_mut();
+        let names = state
+            .property_names(id)
+            .iter()
+            .map(|key| Some(key.to_value()))
+            .collect();
+        let array = state.new_array(names);
+        write(result, state.push(JsValue::Object(array)))
+    })
+}
+
+fn key(env: &Env, key: sys::napi_value) -> NapiResult<PropertyKey> {
+    let key = super::value(env, key)?;
+    env.state.borrow().to_property_key(&key)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_set_property(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    key: sys::napi_value,
+    value: sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (object, _) = super::object(env, object)?;
+        let key = self::key(env, key)?;
+        let value = super::value(env, value)?;
+        env.set(&object, key, value)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_has_property(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    key: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, object)?;
+        let key = self::key(env, key)?;
+        let has = env.state.borrow().has(id, &key);
+        write(result, has)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_property(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    key: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (object, _) = super::object(env, object)?;
+        let key = self::key(env, key)?;
+        let value = env.get(&object, &key)?;
+        write(result, push(env, value))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_delete_property(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    key: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, object)?;
+        let key = self::key(env, key)?;
+        let deleted = env.state.borrow_mut().delete(id, &key);
+        write_opt(result, deleted);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_has_own_property(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    key: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, object)?;
+        let key = match super::value(env, key)? {
+            JsValue::String(key) => PropertyKey::String(key),
+            JsValue::Symbol(symbol) => PropertyKey::Symbol(symbol),
+            _ => return Err(napi_name_expected),
+        };
+        let has = env.state.borrow().has_own(id, &key);
+        write(result, has)
+    })
+}
+
+unsafe fn named_key(name: *const c_char) -> NapiResult<PropertyKey> {
+    if name.is_null() {
+        return Err(napi_invalid_arg);
+    }
+
+    Ok(PropertyKey::from_str(&c_string(name, NAPI_AUTO_LENGTH)?))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_set_named_property(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    utf8name: *const c_char,
+    value: sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (object, _) = super::object(env, object)?;
+        let key = named_key(utf8name)?;
+        let value = super::value(env, value)?;
+        env.set(&object, key, value)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_has_named_property(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    utf8name: *const c_char,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, object)?;
+        let key = named_key(utf8name)?;
+        let has = env.state.borrow().has(id, &key);
+        write(result, has)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_named_property(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    utf8name: *const c_char,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (object, _) = super::object(env, object)?;
+        let key = named_key(utf8name)?;
+        let value = env.get(&object, &key)?;
+        write(result, push(env, value))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_set_element(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    index: u32,
+    value: sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (object, _) = super::object(env, object)?;
+        let value = super::value(env, value)?;
+        env.set(&object, PropertyKey::from_index(index), value)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_has_element(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    index: u32,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, object)?;
+        let has = env.state.borrow().has(id, &PropertyKey::from_index(index));
+        write(result, has)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_element(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    index: u32,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (object, _) = super::object(env, object)?;
+        let value = env.get(&object, &PropertyKey::from_index(index))?;
+        write(result, push(env, value))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_delete_element(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    index: u32,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, object)?;
+        let deleted = env
+            .state
+            .borrow_mut()
+            .delete(id, &PropertyKey::from_index(index));
+        write_opt(result, deleted);
+        Ok(())
+    })
+}
+
+pub(super) unsafe fn descriptor_property(
+    env: &Env,
+    descriptor: &sys::napi_property_descriptor,
+) -> NapiResult<(PropertyKey, Property)> {
+    let key = if descriptor.utf8name.is_null() {
+        match super::value(env, descriptor.name)? {
+            JsValue::String(key) => PropertyKey::String(key),
+            JsValue::Symbol(symbol) => PropertyKey::Symbol(symbol),
+            _ => return Err(napi_name_expected),
+        }
+    } else {
+        named_key(descriptor.utf8name)?
+    };
+
+    let attributes = descriptor.attributes;
+    let has = |flag: sys::napi_property_attributes| attributes & flag == flag;
+    let writable = has(sys::napi_property_attributes::napi_writable);
+    let enumerable = has(sys::napi_property_attributes::napi_enumerable);
+    let configurable = has(sys::napi_property_attributes::napi_configurable);
+
+    let function = |callback: sys::napi_callback| {
+        callback.map(|callback| Function {
+            callback,
+            data: descriptor.data,
+        })
+    };
+
+    let property = if descriptor.getter.is_some() || descriptor.setter.is_some() {
+        Property::Accessor {
+            getter: function(descriptor.getter),
+            setter: function(descriptor.setter),
+            enumerable,
+            configurable,
+        }
+    } else {
+        let value = match function(descriptor.method) {
+            Some(method) => {
+                let name = match key {
+                    PropertyKey::String(ref name) => String::from_utf16_lossy(name),
+                    PropertyKey::Symbol(_) => String::new(),
+                };
+                JsValue::Object(env.state.borrow_mut().new_function(&name, method))
+            }
+            None => super::value(env, descriptor.value)?,
+        };
+
+        Property::Data {
+            value,
+            writable,
+            enumerable,
+            configurable,
+        }
+    };
+
+    Ok((key, property))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_define_properties(
+    env: sys::napi_env,
+    object: sys::napi_value,
+    property_count: usize,
+    properties: *const sys::napi_property_descriptor,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let (_, id) = super::object(env, object)?;
+        if property_count > 0 && properties.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        for index in 0..property_count {
+            let (key, property) = descriptor_property(env, &*properties.add(index))?;
+            env.state.borrow_mut().object_mut(id).define(key, property);
+        }
+
+        Ok(())
+    })
+}
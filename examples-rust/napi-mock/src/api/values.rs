@@ -0,0 +1,654 @@
+use std::os::raw::{c_char, c_int};
+use std::slice;
+
+use super::*;
+use crate::runtime::ErrorKind;
+use crate::script;
+use crate::value::{BigIntValue, Class, ObjectData};
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_undefined(
+    env: sys::napi_env,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| write(result, push(env, JsValue::Undefined)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_null(
+    env: sys::napi_env,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| write(result, push(env, JsValue::Null)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_global(
+    env: sys::napi_env,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let global = env.state.borrow().global;
+        write(result, push(env, JsValue::Object(global)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_boolean(
+    env: sys::napi_env,
+    value: bool,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| write(result, push(env, JsValue::Boolean(value))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_double(
+    env: sys::napi_env,
+    value: f64,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| write(result, push(env, JsValue::Number(value))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_int32(
+    env: sys::napi_env,
+    value: i32,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_create_double(env, f64::from(value), result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_uint32(
+    env: sys::napi_env,
+    value: u32,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_create_double(env, f64::from(value), result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_int64(
+    env: sys::napi_env,
+    value: i64,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_create_double(env, value as f64, result)
+}
+
+unsafe fn create_string(
+    env: sys::napi_env,
+    result: *mut sys::napi_value,
+    f: impl FnOnce() -> NapiResult<Vec<u16>>,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let string = f()?;
+        write(result, push(env, JsValue::String(string.into())))
+    })
+}
+
+unsafe fn input<'a, T>(
+    string: *const T,
+    length: usize,
+    len: impl Fn() -> usize,
+) -> NapiResult<&'a [T]> {
+    if string.is_null() {
+        return if length == 0 {
+            Ok(&[])
+        } else {
+            Err(napi_invalid_arg)
+        };
+    }
+
+    let length = if length == NAPI_AUTO_LENGTH {
+        len()
+    } else {
+        length
+    };
+
+    Ok(slice::from_raw_parts(string, length))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_string_latin1(
+    env: sys::napi_env,
+    string: *const c_char,
+    length: usize,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    create_string(env, result, || {
+        let bytes = input(string as *const u8, length, || {
+            CStr::from_ptr(string).to_bytes().len()
+        })?;
+        Ok(bytes.iter().map(|&byte| u16::from(byte)).collect())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_string_utf8(
+    env: sys::napi_env,
+    string: *const c_char,
+    length: usize,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    create_string(env, result, || {
+        Ok(c_string(string, length)?.encode_utf16().collect())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_string_utf16(
+    env: sys::napi_env,
+    string: *const u16,
+    length: usize,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    create_string(env, result, || {
+        let units = input(string, length, || {
+            let mut length = 0;
+            while *string.add(length) != 0 {
+                length += 1;
+            }
+            length
+        })?;
+        Ok(units.to_vec())
+    })
+}
+
+unsafe fn get_string<T: Copy + Default>(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    buf: *mut T,
+    bufsize: usize,
+    result: *mut usize,
+    encode: impl FnOnce(&[u16], Option<usize>) -> Vec<T>,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let string = match super::value(env, value)? {
+            JsValue::String(string) => string,
+            _ => return Err(napi_string_expected),
+        };
+
+        if buf.is_null() {
+            return write(result, encode(&string, None).len());
+        }
+
+        if bufsize == 0 {
+            write_opt(result, 0);
+            return Ok(());
+        }
+
+        let encoded = encode(&string, Some(bufsize - 1));
+        std::ptr::copy_nonoverlapping(encoded.as_ptr(), buf, encoded.len());
+        *buf.add(encoded.len()) = T::default();
+        write_opt(result, encoded.len());
+
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_string_latin1(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    buf: *mut c_char,
+    bufsize: usize,
+    result: *mut usize,
+) -> sys::napi_status {
+    get_string(
+        env,
+        value,
+        buf as *mut u8,
+        bufsize,
+        result,
+        |string, limit| {
+            let limit = limit.unwrap_or(string.len());
+            string.iter().take(limit).map(|&unit| unit as u8).collect()
+        },
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_string_utf8(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    buf: *mut c_char,
+    bufsize: usize,
+    result: *mut usize,
+) -> sys::napi_status {
+    get_string(
+        env,
+        value,
+        buf as *mut u8,
+        bufsize,
+        result,
+        |string, limit| {
+            let string = String::from_utf16_lossy(string);
+            let mut end = limit.unwrap_or(string.len()).min(string.len());
+            while !string.is_char_boundary(end) {
+                end -= 1;
+            }
+            string.as_bytes()[..end].to_vec()
+        },
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_string_utf16(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    buf: *mut u16,
+    bufsize: usize,
+    result: *mut usize,
+) -> sys::napi_status {
+    get_string(env, value, buf, bufsize, result, |string, limit| {
+        let limit = limit.unwrap_or(string.len());
+        string.iter().take(limit).copied().collect()
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_symbol(
+    env: sys::napi_env,
+    description: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let description = if description.is_null() {
+            None
+        } else {
+            match super::value(env, description)? {
+                JsValue::String(description) => Some(description),
+                _ => return Err(napi_string_expected),
+            }
+        };
+
+        let mut state = env.state.borrow_mut();
+        state.symbols.push(description);
+        let symbol = state.symbols.len() - 1;
+        write(result, state.push(JsValue::Symbol(symbol)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_typeof(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut sys::napi_valuetype,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let value = super::value(env, value)?;
+        let value_type = env.state.borrow().type_of(&value);
+        write(result, value_type)
+    })
+}
+
+fn number(env: &Env, value: sys::napi_value) -> NapiResult<f64> {
+    match super::value(env, value)? {
+        JsValue::Number(number) => Ok(number),
+        _ => Err(napi_number_expected),
+    }
+}
+
+fn to_uint32(number: f64) -> u32 {
+    if number.is_finite() {
+        number.trunc().rem_euclid(4_294_967_296.0) as u32
+    } else {
+        0
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_double(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut f64,
+) -> sys::napi_status {
+    napi_call(env, |env| write(result, number(env, value)?))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_int32(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut i32,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        write(result, to_uint32(number(env, value)?) as i32)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_uint32(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut u32,
+) -> sys::napi_status {
+    napi_call(env, |env| write(result, to_uint32(number(env, value)?)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_int64(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut i64,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let number = number(env, value)?;
+        write(result, if number.is_finite() { number as i64 } else { 0 })
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_bool(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| match super::value(env, value)? {
+        JsValue::Boolean(value) => write(result, value),
+        _ => Err(napi_boolean_expected),
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_coerce_to_bool(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let value = super::value(env, value)?;
+        write(result, push(env, JsValue::Boolean(value.is_truthy())))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_coerce_to_number(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let value = super::value(env, value)?;
+        let number = env.state.borrow().to_number(&value);
+        match number {
+            Some(number) => write(result, push(env, JsValue::Number(number))),
+            None => Err(env.throw_error(ErrorKind::TypeError, "Cannot convert value to a number")),
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_coerce_to_object(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let value = super::value(env, value)?;
+        let object = match value {
+            JsValue::Undefined | JsValue::Null => {
+                return Err(env.throw_error(
+                    ErrorKind::TypeError,
+                    "Cannot convert undefined or null to object",
+                ))
+            }
+            JsValue::Object(_) => value,
+            _ => JsValue::Object(env.state.borrow_mut().new_object()),
+        };
+        write(result, push(env, object))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_coerce_to_string(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let value = super::value(env, value)?;
+        let string = env.state.borrow().to_string(&value);
+        match string {
+            Some(string) => write(result, push(env, JsValue::String(string))),
+            None => Err(env.throw_error(
+                ErrorKind::TypeError,
+                "Cannot convert a Symbol value to a string",
+            )),
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_strict_equals(
+    env: sys::napi_env,
+    lhs: sys::napi_value,
+    rhs: sys::napi_value,
+    result: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let lhs = super::value(env, lhs)?;
+        let rhs = super::value(env, rhs)?;
+        write(result, lhs.strict_equals(&rhs))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_bigint_int64(
+    env: sys::napi_env,
+    value: i64,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let bigint = BigIntValue::new(value < 0, &[value.unsigned_abs()]);
+        write(result, push(env, JsValue::BigInt(bigint)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_bigint_uint64(
+    env: sys::napi_env,
+    value: u64,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let bigint = BigIntValue::new(false, &[value]);
+        write(result, push(env, JsValue::BigInt(bigint)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_bigint_words(
+    env: sys::napi_env,
+    sign_bit: c_int,
+    word_count: usize,
+    words: *const u64,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        if word_count > c_int::MAX as usize {
+            return Err(napi_invalid_arg);
+        }
+
+        let words = input(words, word_count, || word_count)?;
+        let bigint = BigIntValue::new(sign_bit != 0, words);
+        write(result, push(env, JsValue::BigInt(bigint)))
+    })
+}
+
+fn bigint(env: &Env, value: sys::napi_value) -> NapiResult<BigIntValue> {
+    match super::value(env, value)? {
+        JsValue::BigInt(bigint) => Ok(bigint),
+        _ => Err(napi_bigint_expected),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_bigint_int64(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut i64,
+    lossless: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let bigint = bigint(env, value)?;
+        let magnitude = bigint.words.first().copied().unwrap_or(0);
+        let fits = bigint.words.len() <= 1
+            && if bigint.negative {
+                magnitude <= 1 << 63
+            } else {
+                magnitude < 1 << 63
+            };
+
+        write(result, bigint.low_word() as i64)?;
+        write(lossless, fits)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_bigint_uint64(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut u64,
+    lossless: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let bigint = bigint(env, value)?;
+
+        write(result, bigint.low_word())?;
+        write(lossless, !bigint.negative && bigint.words.len() <= 1)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_value_bigint_words(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    sign_bit: *mut c_int,
+    word_count: *mut usize,
+    words: *mut u64,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let bigint = bigint(env, value)?;
+
+        if word_count.is_null() {
+            return Err(napi_invalid_arg);
+        }
+
+        if words.is_null() {
+            *word_count = bigint.words.len();
+            return Ok(());
+        }
+
+        let count = (*word_count).min(bigint.words.len());
+        std::ptr::copy_nonoverlapping(bigint.words.as_ptr(), words, count);
+        *word_count = count;
+        write_opt(sign_bit, c_int::from(bigint.negative));
+
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_create_date(
+    env: sys::napi_env,
+    time: f64,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let mut state = env.state.borrow_mut();
+        let prototype = state.object_prototype;
+        let date = state.alloc(ObjectData::new(Class::Date(time), Some(prototype)));
+        write(result, state.push(JsValue::Object(date)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_is_date(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    is_date: *mut bool,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let date = is_class(env, value, |class| matches!(class, Class::Date(_)))?;
+        write(is_date, date)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_date_value(
+    env: sys::napi_env,
+    value: sys::napi_value,
+    result: *mut f64,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let value = super::value(env, value)?;
+        let state = env.state.borrow();
+        match value.as_object().map(|id| &state.object(id).class) {
+            Some(Class::Date(time)) => write(result, *time),
+            _ => Err(napi_date_expected),
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_version(
+    env: sys::napi_env,
+    result: *mut u32,
+) -> sys::napi_status {
+    napi_call(env, |_| write(result, 8))
+}
+
+struct NodeVersion(sys::napi_node_version);
+
+unsafe impl Sync for NodeVersion {}
+
+static NODE_VERSION: NodeVersion = NodeVersion(sys::napi_node_version {
+    major: 14,
+    minor: 0,
+    patch: 0,
+    release: b"napi-mock\0".as_ptr() as *const c_char,
+});
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_get_node_version(
+    env: sys::napi_env,
+    version: *mut *const sys::napi_node_version,
+) -> sys::napi_status {
+    napi_call(env, |_| {
+        write(version, &NODE_VERSION.0 as *const sys::napi_node_version)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_adjust_external_memory(
+    env: sys::napi_env,
+    change_in_bytes: i64,
+    adjusted_value: *mut i64,
+) -> sys::napi_status {
+    napi_call(env, |env| {
+        let mut state = env.state.borrow_mut();
+        state.external_memory = (state.external_memory + change_in_bytes).max(0);
+        write(adjusted_value, state.external_memory)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn napi_run_script(
+    env: sys::napi_env,
+    script: sys::napi_value,
+    result: *mut sys::napi_value,
+) -> sys::napi_status {
+    napi_call_js(env, |env| {
+        let source = match super::value(env, script)? {
+            JsValue::String(source) => String::from_utf16_lossy(&source),
+            _ => return Err(napi_string_expected),
+        };
+
+        let value = script::run(env, &source)?;
+        write(result, push(env, value))
+    })
+}
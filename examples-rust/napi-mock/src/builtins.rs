@@ -0,0 +1,444 @@
+// Native implementations of the `Symbol`, `Map` and `Set` globals, of the iterators returned
+// by `entries`, `keys` and `values`, and of `Reflect.getOwnPropertyDescriptor`. Iterators take a
+// snapshot of the collection.
+
+use std::ptr;
+
+use crate::runtime::{CallbackInfo, Env, ErrorKind, State};
+use crate::sys;
+use crate::value::*;
+
+type Callback = unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value;
+
+pub fn install(state: &mut State) {
+    let symbol = state.new_function("Symbol", function(symbol_constructor));
+    let iterator = new_symbol(state, "Symbol.iterator");
+    let async_iterator = new_symbol(state, "Symbol.asyncIterator");
+    let object = state.object_mut(symbol);
+    object.define_value("iterator", iterator.clone(), false);
+    object.define_value("asyncIterator", async_iterator, false);
+    let global = state.global;
+    state
+        .object_mut(global)
+        .define_value("Symbol", JsValue::Object(symbol), false);
+
+    state.iterator_prototype = state.new_object();
+    let iterator_prototype = state.iterator_prototype;
+    define_method(state, iterator_prototype, "next", iterator_next);
+
+    let method = state.new_function("[Symbol.iterator]", function(iterator_self));
+    let key = state.to_property_key(&iterator).unwrap();
+    state.object_mut(iterator_prototype).define(
+        key,
+        Property::Data {
+            value: JsValue::Object(method),
+            writable: true,
+            enumerable: false,
+            configurable: true,
+        },
+    );
+
+    install_class(
+        state,
+        "Map",
+        map_constructor,
+        &[
+            ("get", map_get),
+            ("set", map_set),
+            ("has", collection_has),
+            ("delete", collection_delete),
+            ("clear", collection_clear),
+            ("entries", collection_entries),
+            ("keys", collection_keys),
+            ("values", collection_values),
+        ],
+    );
+
+    install_class(
+        state,
+        "Set",
+        set_constructor,
+        &[
+            ("add", set_add),
+            ("has", collection_has),
+            ("delete", collection_delete),
+            ("clear", collection_clear),
+            ("entries", collection_entries),
+            ("keys", collection_values),
+            ("values", collection_values),
+        ],
+    );
+
+    let reflect = state.new_object();
+    define_method(
+        state,
+        reflect,
+        "getOwnPropertyDescriptor",
+        get_own_property_descriptor,
+    );
+    let global = state.global;
+    state
+        .object_mut(global)
+        .define_value("Reflect", JsValue::Object(reflect), false);
+}
+
+fn install_class(
+    state: &mut State,
+    name: &str,
+    constructor: Callback,
+    methods: &[(&str, Callback)],
+) {
+    let constructor = state.new_function(name, function(constructor));
+    let prototype = state
+        .lookup_value(constructor, &PropertyKey::from_str("prototype"))
+        .as_object()
+        .unwrap();
+
+    for &(name, callback) in methods {
+        define_method(state, prototype, name, callback);
+    }
+
+    state.object_mut(prototype).define(
+        PropertyKey::from_str("size"),
+        Property::Accessor {
+            getter: Some(function(collection_size)),
+            setter: None,
+            enumerable: false,
+            configurable: true,
+        },
+    );
+
+    let global = state.global;
+    state
+        .object_mut(global)
+        .define_value(name, JsValue::Object(constructor), false);
+}
+
+fn define_method(state: &mut State, object: ObjectId, name: &str, callback: Callback) {
+    let method = state.new_function(name, function(callback));
+    state
+        .object_mut(object)
+        .define_value(name, JsValue::Object(method), false);
+}
+
+fn function(callback: Callback) -> Function {
+    Function {
+        callback,
+        data: ptr::null_mut(),
+    }
+}
+
+// Runs a native method with the receiver and arguments resolved, throwing a `TypeError` with
+// the returned message on failure.
+unsafe fn method(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+    body: impl FnOnce(&mut State, JsValue, &[JsValue]) -> Result<JsValue, &'static str>,
+) -> sys::napi_value {
+    let env = Env::from_sys(env).unwrap();
+    let info = &*(info as *const CallbackInfo);
+
+    let result = {
+        let mut state = env.state.borrow_mut();
+        let this = state.value(info.this).unwrap_or(JsValue::Undefined);
+        let args = info
+            .args
+            .iter()
+            .map(|&arg| state.value(arg).unwrap_or(JsValue::Undefined))
+            .collect::<Vec<_>>();
+
+        body(&mut state, this, &args).map(|value| state.push(value))
+    };
+
+    result.unwrap_or_else(|message| {
+        env.throw_error(ErrorKind::TypeError, message);
+        ptr::null_mut()
+    })
+}
+
+fn new_symbol(state: &mut State, description: &str) -> JsValue {
+    state
+        .symbols
+        .push(Some(description.encode_utf16().collect()));
+    JsValue::Symbol(state.symbols.len() - 1)
+}
+
+fn arg(args: &[JsValue], index: usize) -> JsValue {
+    args.get(index).cloned().unwrap_or(JsValue::Undefined)
+}
+
+// `SameValueZero`, the equality used for `Map` keys and `Set` values.
+fn same_value_zero(lhs: &JsValue, rhs: &JsValue) -> bool {
+    match (lhs, rhs) {
+        (JsValue::Number(lhs), JsValue::Number(rhs)) if lhs.is_nan() && rhs.is_nan() => true,
+        (lhs, rhs) => lhs.strict_equals(rhs),
+    }
+}
+
+fn entries<'a>(
+    state: &'a mut State,
+    this: &JsValue,
+) -> Result<&'a mut Vec<(JsValue, JsValue)>, &'static str> {
+    let id = this.as_object().ok_or("Map or Set expected")?;
+    match state.object_mut(id).class {
+        Class::Map(ref mut entries) | Class::Set(ref mut entries) => Ok(entries),
+        _ => Err("Map or Set expected"),
+    }
+}
+
+fn position(entries: &[(JsValue, JsValue)], key: &JsValue) -> Option<usize> {
+    entries
+        .iter()
+        .position(|(entry_key, _)| same_value_zero(entry_key, key))
+}
+
+fn initialize(
+    state: &mut State,
+    this: &JsValue,
+    class: fn(Vec<(JsValue, JsValue)>) -> Class,
+) -> Result<JsValue, &'static str> {
+    let id = this.as_object().ok_or("Constructor requires 'new'")?;
+    state.object_mut(id).class = class(Vec::new());
+    Ok(JsValue::Undefined)
+}
+
+unsafe extern "C" fn symbol_constructor(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, _, args| {
+        let description = match arg(args, 0) {
+            JsValue::Undefined => None,
+            description => Some(
+                state
+                    .to_string(&description)
+                    .ok_or("Cannot convert to string")?,
+            ),
+        };
+        state.symbols.push(description);
+        Ok(JsValue::Symbol(state.symbols.len() - 1))
+    })
+}
+
+unsafe extern "C" fn map_constructor(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, _| {
+        initialize(state, &this, Class::Map)
+    })
+}
+
+unsafe extern "C" fn set_constructor(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, _| {
+        initialize(state, &this, Class::Set)
+    })
+}
+
+unsafe extern "C" fn map_get(env: sys::napi_env, info: sys::napi_callback_info) -> sys::napi_value {
+    method(env, info, |state, this, args| {
+        let entries = entries(state, &this)?;
+        Ok(position(entries, &arg(args, 0))
+            .map_or(JsValue::Undefined, |index| entries[index].1.clone()))
+    })
+}
+
+unsafe extern "C" fn map_set(env: sys::napi_env, info: sys::napi_callback_info) -> sys::napi_value {
+    method(env, info, |state, this, args| {
+        let entries = entries(state, &this)?;
+        let (key, value) = (arg(args, 0), arg(args, 1));
+        match position(entries, &key) {
+            Some(index) => entries[index].1 = value,
+            None => entries.push((key, value)),
+        }
+        Ok(this)
+    })
+}
+
+unsafe extern "C" fn set_add(env: sys::napi_env, info: sys::napi_callback_info) -> sys::napi_value {
+    method(env, info, |state, this, args| {
+        let entries = entries(state, &this)?;
+        let value = arg(args, 0);
+        if position(entries, &value).is_none() {
+            entries.push((value.clone(), value));
+        }
+        Ok(this)
+    })
+}
+
+unsafe extern "C" fn collection_has(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, args| {
+        let entries = entries(state, &this)?;
+        Ok(JsValue::Boolean(position(entries, &arg(args, 0)).is_some()))
+    })
+}
+
+unsafe extern "C" fn collection_delete(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, args| {
+        let entries = entries(state, &this)?;
+        let index = position(entries, &arg(args, 0));
+        if let Some(index) = index {
+            entries.remove(index);
+        }
+        Ok(JsValue::Boolean(index.is_some()))
+    })
+}
+
+unsafe extern "C" fn collection_clear(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, _| {
+        entries(state, &this)?.clear();
+        Ok(JsValue::Undefined)
+    })
+}
+
+unsafe extern "C" fn collection_size(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, _| {
+        Ok(JsValue::Number(entries(state, &this)?.len() as f64))
+    })
+}
+
+unsafe extern "C" fn collection_entries(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, _| {
+        let entries = entries(state, &this)?.clone();
+        let values = entries
+            .into_iter()
+            .map(|(key, value)| JsValue::Object(state.new_array(vec![Some(key), Some(value)])))
+            .collect();
+        Ok(new_iterator(state, values))
+    })
+}
+
+unsafe extern "C" fn collection_keys(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, _| {
+        let keys = entries(state, &this)?
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect();
+        Ok(new_iterator(state, keys))
+    })
+}
+
+unsafe extern "C" fn collection_values(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, _| {
+        let values = entries(state, &this)?
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect();
+        Ok(new_iterator(state, values))
+    })
+}
+
+unsafe extern "C" fn get_own_property_descriptor(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, _, args| {
+        let target = arg(args, 0)
+            .as_object()
+            .ok_or("Reflect target must be an object")?;
+        let key = state
+            .to_property_key(&arg(args, 1))
+            .map_err(|_| "Cannot convert to property key")?;
+        let property = match state.object(target).own_property(&key) {
+            Some(property) => property.clone(),
+            None => return Ok(JsValue::Undefined),
+        };
+
+        let descriptor = state.new_object();
+        let fields = match property {
+            Property::Data {
+                value,
+                writable,
+                enumerable,
+                configurable,
+            } => vec![
+                ("value", value),
+                ("writable", JsValue::Boolean(writable)),
+                ("enumerable", JsValue::Boolean(enumerable)),
+                ("configurable", JsValue::Boolean(configurable)),
+            ],
+            Property::Accessor {
+                getter,
+                setter,
+                enumerable,
+                configurable,
+            } => {
+                let mut accessor = |name, function: Option<Function>| match function {
+                    Some(function) => JsValue::Object(state.new_function(name, function)),
+                    None => JsValue::Undefined,
+                };
+                vec![
+                    ("get", accessor("get", getter)),
+                    ("set", accessor("set", setter)),
+                    ("enumerable", JsValue::Boolean(enumerable)),
+                    ("configurable", JsValue::Boolean(configurable)),
+                ]
+            }
+        };
+
+        for (name, value) in fields {
+            state.object_mut(descriptor).define_value(name, value, true);
+        }
+        Ok(JsValue::Object(descriptor))
+    })
+}
+
+fn new_iterator(state: &mut State, values: Vec<JsValue>) -> JsValue {
+    let prototype = state.iterator_prototype;
+    let iterator = ObjectData::new(Class::Iterator(values, 0), Some(prototype));
+    JsValue::Object(state.alloc(iterator))
+}
+
+unsafe extern "C" fn iterator_next(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |state, this, _| {
+        let id = this.as_object().ok_or("Iterator expected")?;
+        let next = match state.object_mut(id).class {
+            Class::Iterator(ref values, ref mut index) => {
+                let next = values.get(*index).cloned();
+                *index += usize::from(next.is_some());
+                next
+            }
+            _ => return Err("Iterator expected"),
+        };
+
+        let result = state.new_object();
+        let object = state.object_mut(result);
+        object.define_value("done", JsValue::Boolean(next.is_none()), true);
+        object.define_value("value", next.unwrap_or(JsValue::Undefined), true);
+        Ok(JsValue::Object(result))
+    })
+}
+
+unsafe extern "C" fn iterator_self(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    method(env, info, |_, this, _| Ok(this))
+}
@@ -0,0 +1,69 @@
+//! The values of the N-API enums as plain constants, so that they can be
+//! glob-imported and matched on like the variants of a Rust enum. napi-sys
+//! binds the enums as newtypes because the values aren't exhaustive.
+
+macro_rules! constants {
+    ($($ty:ident { $($name:ident,)* })*) => {
+        $(
+            pub mod $ty {
+                use crate::sys::$ty;
+
+                $(pub const $name: $ty = $ty::$name;)*
+            }
+        )*
+    };
+}
+
+constants! {
+    napi_status {
+        napi_ok,
+        napi_invalid_arg,
+        napi_object_expected,
+        napi_string_expected,
+        napi_name_expected,
+        napi_function_expected,
+        napi_number_expected,
+        napi_boolean_expected,
+        napi_array_expected,
+        napi_generic_failure,
+        napi_pending_exception,
+        napi_cancelled,
+        napi_escape_called_twice,
+        napi_handle_scope_mismatch,
+        napi_callback_scope_mismatch,
+        napi_queue_full,
+        napi_closing,
+        napi_bigint_expected,
+        napi_date_expected,
+        napi_arraybuffer_expected,
+        napi_detachable_arraybuffer_expected,
+        napi_would_deadlock,
+        napi_no_external_buffers_allowed,
+        napi_cannot_run_js,
+    }
+    napi_valuetype {
+        napi_undefined,
+        napi_null,
+        napi_boolean,
+        napi_number,
+        napi_string,
+        napi_symbol,
+        napi_object,
+        napi_function,
+        napi_external,
+        napi_bigint,
+    }
+    napi_typedarray_type {
+        napi_int8_array,
+        napi_uint8_array,
+        napi_uint8_clamped_array,
+        napi_int16_array,
+        napi_uint16_array,
+        napi_int32_array,
+        napi_uint32_array,
+        napi_float32_array,
+        napi_float64_array,
+        napi_bigint64_array,
+        napi_biguint64_array,
+    }
+}
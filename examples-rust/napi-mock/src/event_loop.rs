@@ -0,0 +1,321 @@
+use std::collections::VecDeque;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+use crate::consts::napi_status::*;
+use crate::runtime::Env;
+use crate::sys;
+use crate::value::Finalizer;
+
+struct SendPtr<T>(*mut T);
+
+unsafe impl<T> Send for SendPtr<T> {}
+
+enum Task {
+    CompleteWork(SendPtr<AsyncWork>),
+    CallThreadsafe(SendPtr<ThreadsafeFunction>, SendPtr<c_void>),
+    FinalizeThreadsafe(SendPtr<ThreadsafeFunction>),
+}
+
+struct LoopState {
+    tasks: VecDeque<Task>,
+    active_handles: usize,
+}
+
+pub struct EventLoop {
+    state: Mutex<LoopState>,
+    condvar: Condvar,
+}
+
+impl EventLoop {
+    pub fn new() -> EventLoop {
+        EventLoop {
+            state: Mutex::new(LoopState {
+                tasks: VecDeque::new(),
+                active_handles: 0,
+            }),
+            condvar: Condvar::new(),
+        }
+    }
+
+    fn push(&self, task: Task) {
+        self.state.lock().unwrap().tasks.push_back(task);
+        self.condvar.notify_all();
+    }
+
+    fn ref_handle(&self) {
+        self.state.lock().unwrap().active_handles += 1;
+    }
+
+    fn unref_handle(&self) {
+        self.state.lock().unwrap().active_handles -= 1;
+        self.condvar.notify_all();
+    }
+
+    pub fn run(&self, env: &Env) {
+        loop {
+            let task = {
+                let mut state = self.state.lock().unwrap();
+                loop {
+                    if let Some(task) = state.tasks.pop_front() {
+                        break task;
+                    }
+                    if state.active_handles == 0 {
+                        return;
+                    }
+                    state = self.condvar.wait(state).unwrap();
+                }
+            };
+
+            unsafe {
+                match task {
+                    Task::CompleteWork(work) => (*work.0).complete(env),
+                    Task::CallThreadsafe(function, data) => (*function.0).call_js(env, data.0),
+                    Task::FinalizeThreadsafe(function) => {
+                        // Finalized in place: the releasing thread may still hold the state lock.
+                        let function = Box::from_raw(function.0);
+                        function.finalize(env);
+                    }
+                }
+            }
+
+            if env.state.borrow_mut().exception.take().is_some() {
+                panic!("uncaught JavaScript exception thrown from an event loop callback");
+            }
+        }
+    }
+}
+
+pub struct AsyncWork {
+    pub execute: unsafe extern "C" fn(sys::napi_env, *mut c_void),
+    pub complete: sys::napi_async_complete_callback,
+    pub data: *mut c_void,
+    pub event_loop: Arc<EventLoop>,
+    pub queued: bool,
+}
+
+impl AsyncWork {
+    pub fn queue(work: *mut AsyncWork, env: &Env) -> sys::napi_status {
+        let (execute, data, event_loop) = unsafe {
+            if (*work).queued {
+                return napi_generic_failure;
+            }
+            (*work).queued = true;
+            ((*work).execute, (*work).data, (*work).event_loop.clone())
+        };
+
+        event_loop.ref_handle();
+
+        let env = SendPtr(env.as_sys());
+        let data = SendPtr(data);
+        let work = SendPtr(work);
+
+        thread::spawn(move || {
+            let (env, data, work) = (env, data, work);
+            unsafe { execute(env.0, data.0) };
+            event_loop.push(Task::CompleteWork(work));
+        });
+
+        napi_ok
+    }
+
+    fn complete(&mut self, env: &Env) {
+        self.queued = false;
+        self.event_loop.unref_handle();
+
+        if let Some(complete) = self.complete {
+            with_scope(env, || unsafe {
+                complete(env.as_sys(), napi_ok, self.data)
+            });
+        }
+    }
+}
+
+struct ThreadsafeState {
+    thread_count: usize,
+    queued: usize,
+    closing: bool,
+    aborted: bool,
+    referenced: bool,
+}
+
+pub struct ThreadsafeFunction {
+    pub function: sys::napi_ref,
+    pub context: *mut c_void,
+    pub call_js: sys::napi_threadsafe_function_call_js,
+    pub finalizer: Option<Finalizer>,
+    pub max_queue_size: usize,
+    event_loop: Arc<EventLoop>,
+    state: Mutex<ThreadsafeState>,
+    space: Condvar,
+}
+
+unsafe impl Send for ThreadsafeFunction {}
+unsafe impl Sync for ThreadsafeFunction {}
+
+impl ThreadsafeFunction {
+    pub fn new(
+        env: &Env,
+        function: sys::napi_ref,
+        context: *mut c_void,
+        call_js: sys::napi_threadsafe_function_call_js,
+        finalizer: Option<Finalizer>,
+        max_queue_size: usize,
+        initial_thread_count: usize,
+    ) -> ThreadsafeFunction {
+        env.event_loop.ref_handle();
+
+        ThreadsafeFunction {
+            function,
+            context,
+            call_js,
+            finalizer,
+            max_queue_size,
+            event_loop: env.event_loop.clone(),
+            state: Mutex::new(ThreadsafeState {
+                thread_count: initial_thread_count,
+                queued: 0,
+                closing: false,
+                aborted: false,
+                referenced: true,
+            }),
+            space: Condvar::new(),
+        }
+    }
+
+    pub fn call(&self, data: *mut c_void, blocking: bool) -> sys::napi_status {
+        let mut state = self.state.lock().unwrap();
+
+        loop {
+            if state.closing {
+                return napi_closing;
+            }
+            if self.max_queue_size == 0 || state.queued < self.max_queue_size {
+                break;
+            }
+            if !blocking {
+                return napi_queue_full;
+            }
+            state = self.space.wait(state).unwrap();
+        }
+
+        state.queued += 1;
+        drop(state);
+
+        let function = SendPtr(self as *const ThreadsafeFunction as *mut ThreadsafeFunction);
+        self.event_loop
+            .push(Task::CallThreadsafe(function, SendPtr(data)));
+
+        napi_ok
+    }
+
+    pub fn acquire(&self) -> sys::napi_status {
+        let mut state = self.state.lock().unwrap();
+        if state.closing {
+            return napi_closing;
+        }
+
+        state.thread_count += 1;
+        napi_ok
+    }
+
+    pub fn release(&self, abort: bool) -> sys::napi_status {
+        let mut state = self.state.lock().unwrap();
+        if state.thread_count == 0 {
+            return napi_invalid_arg;
+        }
+
+        state.thread_count -= 1;
+
+        if (state.thread_count == 0 || abort) && !state.closing {
+            state.closing = true;
+            state.aborted = abort;
+
+            let function = SendPtr(self as *const ThreadsafeFunction as *mut ThreadsafeFunction);
+            self.event_loop.push(Task::FinalizeThreadsafe(function));
+        }
+
+        self.space.notify_all();
+        napi_ok
+    }
+
+    pub fn set_referenced(&self, referenced: bool) {
+        let mut state = self.state.lock().unwrap();
+        if state.referenced == referenced {
+            return;
+        }
+
+        state.referenced = referenced;
+        if referenced {
+            self.event_loop.ref_handle();
+        } else {
+            self.event_loop.unref_handle();
+        }
+    }
+
+    fn call_js(&self, env: &Env, data: *mut c_void) {
+        let aborted = {
+            let mut state = self.state.lock().unwrap();
+            state.queued -= 1;
+            state.aborted
+        };
+        self.space.notify_all();
+
+        if aborted {
+            if let Some(call_js) = self.call_js {
+                unsafe { call_js(ptr::null_mut(), ptr::null_mut(), self.context, data) };
+            }
+            return;
+        }
+
+        with_scope(env, || unsafe {
+            let mut function = ptr::null_mut();
+            if !self.function.is_null() {
+                sys::napi_get_reference_value(env.as_sys(), self.function, &mut function);
+            }
+
+            match self.call_js {
+                Some(call_js) => call_js(env.as_sys(), function, self.context, data),
+                None if !function.is_null() => {
+                    let mut undefined = ptr::null_mut();
+                    let mut result = ptr::null_mut();
+                    sys::napi_get_undefined(env.as_sys(), &mut undefined);
+                    sys::napi_call_function(
+                        env.as_sys(),
+                        undefined,
+                        function,
+                        0,
+                        ptr::null(),
+                        &mut result,
+                    );
+                }
+                None => {}
+            }
+        });
+    }
+
+    fn finalize(&self, env: &Env) {
+        if let Some(finalizer) = self.finalizer {
+            with_scope(env, || unsafe {
+                (finalizer.callback)(env.as_sys(), finalizer.data, finalizer.hint)
+            });
+        }
+
+        if !self.function.is_null() {
+            unsafe { sys::napi_delete_reference(env.as_sys(), self.function) };
+        }
+
+        if self.state.lock().unwrap().referenced {
+            self.event_loop.unref_handle();
+        }
+    }
+}
+
+fn with_scope<F: FnOnce()>(env: &Env, f: F) {
+    let mut scope = ptr::null_mut();
+    unsafe { sys::napi_open_handle_scope(env.as_sys(), &mut scope) };
+    f();
+    unsafe { sys::napi_close_handle_scope(env.as_sys(), scope) };
+}
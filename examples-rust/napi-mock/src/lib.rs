@@ -0,0 +1,24 @@
+//! An in-process implementation of the N-API C interface for running addon
+//! code under `cargo test` without Node.js.
+//!
+//! Values live in a simple heap owned by a [`Runtime`]; nothing is collected
+//! until [`Runtime::collect_garbage`] is called, and asynchronous work and
+//! thread-safe function calls only run inside [`Runtime::run_until_idle`].
+//! `napi_run_script` evaluates a small subset of JavaScript: literals,
+//! arithmetic, member access, calls, `new`, `throw` and function expressions.
+//! `napi_get_uv_event_loop` is not supported.
+
+// N-API constants keep their C names, also where they are matched on.
+#![allow(non_upper_case_globals)]
+
+use napi_sys as sys;
+
+mod api;
+mod builtins;
+mod consts;
+mod event_loop;
+mod runtime;
+mod script;
+mod value;
+
+pub use runtime::{PromiseState, Runtime};
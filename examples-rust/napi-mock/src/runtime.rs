@@ -0,0 +1,1098 @@
+use std::cell::{Cell, RefCell};
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::builtins;
+use crate::consts::napi_status::*;
+use crate::event_loop::EventLoop;
+use crate::script::Closure;
+use crate::sys;
+use crate::value::*;
+
+pub type NapiResult<T> = Result<T, sys::napi_status>;
+
+pub struct Runtime {
+    env: Box<Env>,
+}
+
+#[derive(Debug)]
+pub enum PromiseState {
+    Pending,
+    Fulfilled(sys::napi_value),
+    Rejected(sys::napi_value),
+}
+
+impl Runtime {
+    pub fn new() -> Runtime {
+        Runtime {
+            env: Box::new(Env {
+                state: RefCell::new(State::new()),
+                last_error: Cell::new(sys::napi_extended_error_info {
+                    error_message: ptr::null(),
+                    engine_reserved: ptr::null_mut(),
+                    engine_error_code: 0,
+                    error_code: napi_ok,
+                }),
+                event_loop: Arc::new(EventLoop::new()),
+            }),
+        }
+    }
+
+    pub fn env(&self) -> sys::napi_env {
+        self.env.as_sys()
+    }
+
+    /// Runs a module init function (such as the one `#[napi::module_init]` generates) with a fresh
+    /// exports object.
+    /// Returns the thrown exception as `Err` if the init function left one pending.
+    pub fn init_module(
+        &self,
+        init: unsafe extern "C" fn(sys::napi_env, sys::napi_value) -> sys::napi_value,
+    ) -> Result<sys::napi_value, sys::napi_value> {
+        let exports = {
+            let mut state = self.env.state.borrow_mut();
+            let exports = state.new_object();
+            state.push(JsValue::Object(exports))
+        };
+
+        let result = unsafe { init(self.env(), exports) };
+        self.take_exception()?;
+
+        Ok(if result.is_null() { exports } else { result })
+    }
+
+    /// Invokes a native callback with `undefined` as `this`, returning the thrown exception as
+    /// `Err` if the callback left one pending.
+    pub fn call(
+        &self,
+        callback: unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value,
+        args: &[sys::napi_value],
+    ) -> Result<sys::napi_value, sys::napi_value> {
+        let function = Function {
+            callback,
+            data: ptr::null_mut(),
+        };
+
+        let args = {
+            let state = self.env.state.borrow();
+            args.iter()
+                .map(|&arg| {
+                    state
+                        .value(arg)
+                        .expect("invalid napi_value passed to Runtime::call")
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let result = self.env.call(function, JsValue::Undefined, &args, None);
+        self.take_exception()?;
+
+        let value = result.unwrap_or(JsValue::Undefined);
+        Ok(self.env.state.borrow_mut().push(value))
+    }
+
+    pub fn promise_state(&self, promise: sys::napi_value) -> Option<PromiseState> {
+        let mut state = self.env.state.borrow_mut();
+        let id = state.value(promise).ok()?.as_object()?;
+
+        let promise_data = match state.object(id).class {
+            Class::Promise(ref promise_data) => promise_data.clone(),
+            _ => return None,
+        };
+
+        Some(match promise_data {
+            PromiseData::Pending => PromiseState::Pending,
+            PromiseData::Fulfilled(value) => PromiseState::Fulfilled(state.push(value)),
+            PromiseData::Rejected(value) => PromiseState::Rejected(state.push(value)),
+        })
+    }
+
+    pub fn collect_garbage(&self) {
+        let finalizers = self.env.state.borrow_mut().collect_garbage();
+        self.env.run_finalizers(finalizers);
+    }
+
+    /// Completes queued async work and thread-safe function calls until no task or referenced
+    /// handle is left.
+    pub fn run_until_idle(&self) {
+        self.env.event_loop.run(&self.env);
+    }
+
+    fn take_exception(&self) -> Result<(), sys::napi_value> {
+        let mut state = self.env.state.borrow_mut();
+        match state.exception.take() {
+            Some(exception) => Err(state.push(exception)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for Runtime {
+    fn default() -> Runtime {
+        Runtime::new()
+    }
+}
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        loop {
+            let hook = self.env.state.borrow_mut().cleanup_hooks.pop();
+            match hook {
+                Some((hook, arg)) => unsafe { hook(arg) },
+                None => break,
+            }
+        }
+
+        let finalizers = self.env.state.borrow_mut().take_all_finalizers();
+        self.env.run_finalizers(finalizers);
+
+        let finalizer = self.env.state.borrow_mut().instance_data.1.take();
+        self.env.run_finalizers(finalizer.into_iter().collect());
+    }
+}
+
+pub struct Env {
+    pub state: RefCell<State>,
+    last_error: Cell<sys::napi_extended_error_info>,
+    pub event_loop: Arc<EventLoop>,
+}
+
+pub struct CallbackInfo {
+    pub this: sys::napi_value,
+    pub args: Vec<sys::napi_value>,
+    pub new_target: sys::napi_value,
+    pub data: *mut c_void,
+}
+
+enum Lookup {
+    Value(JsValue),
+    Getter(Function),
+}
+
+enum Assign {
+    Done,
+    Setter(Function),
+}
+
+impl Env {
+    pub unsafe fn from_sys<'a>(env: sys::napi_env) -> Option<&'a Env> {
+        (env as *const Env).as_ref()
+    }
+
+    pub fn as_sys(&self) -> sys::napi_env {
+        self as *const Env as sys::napi_env
+    }
+
+    pub fn set_last_error(&self, status: sys::napi_status) {
+        self.last_error.set(sys::napi_extended_error_info {
+            error_message: error_message(status),
+            engine_reserved: ptr::null_mut(),
+            engine_error_code: 0,
+            error_code: status,
+        });
+    }
+
+    pub fn last_error(&self) -> *const sys::napi_extended_error_info {
+        self.last_error.as_ptr()
+    }
+
+    pub fn throw(&self, value: JsValue) {
+        self.state.borrow_mut().exception = Some(value);
+    }
+
+    pub fn throw_error(&self, kind: ErrorKind, message: &str) -> sys::napi_status {
+        let mut state = self.state.borrow_mut();
+        let error = state.new_error(kind, None, JsValue::from_str(message));
+        state.exception = Some(JsValue::Object(error));
+        napi_pending_exception
+    }
+
+    pub fn check_pending_exception(&self) -> NapiResult<()> {
+        if self.state.borrow().exception.is_some() {
+            Err(napi_pending_exception)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn function(&self, value: &JsValue) -> NapiResult<Function> {
+        let state = self.state.borrow();
+        match value.as_object().map(|id| &state.object(id).class) {
+            Some(Class::Function(function)) => Ok(*function),
+            _ => Err(napi_function_expected),
+        }
+    }
+
+    pub fn call(
+        &self,
+        function: Function,
+        this: JsValue,
+        args: &[JsValue],
+        new_target: Option<JsValue>,
+    ) -> NapiResult<JsValue> {
+        self.check_pending_exception()?;
+
+        let (depth, handles_len, info) = {
+            let mut state = self.state.borrow_mut();
+            let depth = state.scopes.len();
+            let handles_len = state.handles.len();
+            state.scopes.push(Scope::new(handles_len, None));
+
+            let info = CallbackInfo {
+                this: state.push(this),
+                args: args.iter().map(|arg| state.push(arg.clone())).collect(),
+                new_target: match new_target {
+                    Some(new_target) => state.push(new_target),
+                    None => ptr::null_mut(),
+                },
+                data: function.data,
+            };
+
+            (depth, handles_len, info)
+        };
+
+        let result = unsafe {
+            (function.callback)(
+                self.as_sys(),
+                &info as *const CallbackInfo as sys::napi_callback_info,
+            )
+        };
+
+        let mut state = self.state.borrow_mut();
+        let value = state.value(result).unwrap_or(JsValue::Undefined);
+        state.scopes.truncate(depth);
+        state.handles.truncate(handles_len);
+
+        if state.exception.is_some() {
+            Err(napi_pending_exception)
+        } else {
+            Ok(value)
+        }
+    }
+
+    pub fn construct(&self, constructor: &JsValue, args: &[JsValue]) -> NapiResult<JsValue> {
+        let function = self.function(constructor)?;
+        let prototype = self.get(constructor, &PropertyKey::from_str("prototype"))?;
+
+        let instance = {
+            let mut state = self.state.borrow_mut();
+            let prototype = prototype.as_object().unwrap_or(state.object_prototype);
+            JsValue::Object(state.alloc(ObjectData::new(Class::Object, Some(prototype))))
+        };
+
+        let result = self.call(function, instance.clone(), args, Some(constructor.clone()))?;
+        Ok(match result {
+            JsValue::Object(_) => result,
+            _ => instance,
+        })
+    }
+
+    pub fn get(&self, object: &JsValue, key: &PropertyKey) -> NapiResult<JsValue> {
+        let id = object.as_object().ok_or(napi_object_expected)?;
+        let lookup = self.state.borrow().lookup(id, key);
+
+        match lookup {
+            Lookup::Value(value) => Ok(value),
+            Lookup::Getter(getter) => self.call(getter, object.clone(), &[], None),
+        }
+    }
+
+    pub fn set(&self, object: &JsValue, key: PropertyKey, value: JsValue) -> NapiResult<()> {
+        let id = object.as_object().ok_or(napi_object_expected)?;
+        let assign = self.state.borrow_mut().assign(id, key, value.clone());
+
+        match assign {
+            Assign::Done => Ok(()),
+            Assign::Setter(setter) => self
+                .call(setter, object.clone(), &[value], None)
+                .map(|_| ()),
+        }
+    }
+
+    pub fn run_finalizers(&self, finalizers: Vec<Finalizer>) {
+        for finalizer in finalizers {
+            let (depth, handles_len) = {
+                let mut state = self.state.borrow_mut();
+                let handles_len = state.handles.len();
+                state.scopes.push(Scope::new(handles_len, None));
+                (state.scopes.len() - 1, handles_len)
+            };
+
+            unsafe {
+                (finalizer.callback)(self.as_sys(), finalizer.data, finalizer.hint);
+            }
+
+            let mut state = self.state.borrow_mut();
+            state.scopes.truncate(depth);
+            state.handles.truncate(handles_len);
+        }
+    }
+}
+
+// Backs the global error constructors. When called through
+// `new` on a subclass, the instance gets the subclass prototype.
+unsafe extern "C" fn error_constructor(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    let env = Env::from_sys(env).unwrap();
+    let info = &*(info as *const CallbackInfo);
+    let kind = [
+        ErrorKind::Error,
+        ErrorKind::TypeError,
+        ErrorKind::RangeError,
+        ErrorKind::SyntaxError,
+        ErrorKind::ReferenceError,
+    ][info.data as usize];
+
+    let mut state = env.state.borrow_mut();
+    let message = match info.args.first().map(|&arg| state.value(arg)) {
+        Some(Ok(JsValue::Undefined)) | None => JsValue::from_str(""),
+        Some(Ok(message)) => state
+            .to_string(&message)
+            .map_or(JsValue::from_str(""), JsValue::String),
+        Some(Err(_)) => JsValue::from_str(""),
+    };
+
+    let error = state.new_error(kind, None, message);
+    if let Ok(JsValue::Object(new_target)) = state.value(info.new_target) {
+        let prototype = state.lookup_value(new_target, &PropertyKey::from_str("prototype"));
+        if let JsValue::Object(prototype) = prototype {
+            state.object_mut(error).prototype = Some(prototype);
+        }
+    }
+
+    state.push(JsValue::Object(error))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorKind {
+    Error,
+    TypeError,
+    RangeError,
+    SyntaxError,
+    ReferenceError,
+}
+
+const ERROR_NAMES: [&str; 5] = [
+    "Error",
+    "TypeError",
+    "RangeError",
+    "SyntaxError",
+    "ReferenceError",
+];
+
+pub struct Scope {
+    pub start: usize,
+    pub escape_slot: Option<usize>,
+    pub escaped: bool,
+}
+
+impl Scope {
+    fn new(start: usize, escape_slot: Option<usize>) -> Scope {
+        Scope {
+            start,
+            escape_slot,
+            escaped: false,
+        }
+    }
+}
+
+pub struct Reference {
+    pub value: JsValue,
+    pub count: u32,
+    /// The data of the finalizer this reference was created for by
+    /// `napi_add_finalizer`, which is dropped if the reference is deleted
+    /// before the object is collected.
+    pub finalizer: Option<*mut c_void>,
+}
+
+type CleanupHook = (unsafe extern "C" fn(*mut c_void), *mut c_void);
+
+pub struct State {
+    pub handles: Vec<JsValue>,
+    pub scopes: Vec<Scope>,
+    pub objects: Vec<Option<ObjectData>>,
+    pub references: Vec<Option<Reference>>,
+    pub symbols: Vec<Option<Rc<[u16]>>>,
+    pub exception: Option<JsValue>,
+    pub cleanup_hooks: Vec<CleanupHook>,
+    pub instance_data: (*mut c_void, Option<Finalizer>),
+    pub callback_scopes: usize,
+    pub external_memory: i64,
+    pub closures: Vec<Rc<Closure>>,
+    pub global: ObjectId,
+    pub object_prototype: ObjectId,
+    pub function_prototype: ObjectId,
+    pub array_prototype: ObjectId,
+    pub iterator_prototype: ObjectId,
+    error_prototypes: [ObjectId; 5],
+}
+
+impl State {
+    fn new() -> State {
+        let mut state = State {
+            handles: Vec::new(),
+            scopes: Vec::new(),
+            objects: Vec::new(),
+            references: Vec::new(),
+            symbols: Vec::new(),
+            exception: None,
+            cleanup_hooks: Vec::new(),
+            instance_data: (ptr::null_mut(), None),
+            callback_scopes: 0,
+            external_memory: 0,
+            closures: Vec::new(),
+            global: 0,
+            object_prototype: 0,
+            function_prototype: 0,
+            array_prototype: 0,
+            iterator_prototype: 0,
+            error_prototypes: [0; 5],
+        };
+
+        state.object_prototype = state.alloc(ObjectData::new(Class::Object, None));
+        state.function_prototype = state.new_object();
+        state.array_prototype = state.new_object();
+
+        let error_prototype = state.new_object();
+        state.error_prototypes = [error_prototype; 5];
+
+        for (index, name) in ERROR_NAMES.iter().enumerate() {
+            let prototype = if index == 0 {
+                error_prototype
+            } else {
+                state.alloc(ObjectData::new(Class::Object, Some(error_prototype)))
+            };
+
+            let object = state.object_mut(prototype);
+            object.define_value("name", JsValue::from_str(name), false);
+            object.define_value("message", JsValue::from_str(""), false);
+            state.error_prototypes[index] = prototype;
+        }
+
+        state.global = state.new_object();
+        let global = JsValue::Object(state.global);
+        state
+            .object_mut(state.global)
+            .define_value("globalThis", global, false);
+
+        for (index, name) in ERROR_NAMES.iter().enumerate() {
+            let prototype = state.error_prototypes[index];
+            let constructor = state.new_function(
+                name,
+                Function {
+                    callback: error_constructor,
+                    data: index as *mut c_void,
+                },
+            );
+
+            state.object_mut(constructor).define_value(
+                "prototype",
+                JsValue::Object(prototype),
+                false,
+            );
+            state.object_mut(prototype).define_value(
+                "constructor",
+                JsValue::Object(constructor),
+                false,
+            );
+            state
+                .object_mut(state.global)
+                .define_value(name, JsValue::Object(constructor), false);
+        }
+
+        builtins::install(&mut state);
+        state
+    }
+
+    pub fn push(&mut self, value: JsValue) -> sys::napi_value {
+        self.handles.push(value);
+        self.handles.len() as sys::napi_value
+    }
+
+    pub fn value(&self, handle: sys::napi_value) -> NapiResult<JsValue> {
+        let index = (handle as usize).checked_sub(1).ok_or(napi_invalid_arg)?;
+        self.handles.get(index).cloned().ok_or(napi_invalid_arg)
+    }
+
+    pub fn alloc(&mut self, object: ObjectData) -> ObjectId {
+        self.objects.push(Some(object));
+        self.objects.len() - 1
+    }
+
+    pub fn object(&self, id: ObjectId) -> &ObjectData {
+        self.objects[id]
+            .as_ref()
+            .expect("object was garbage collected")
+    }
+
+    pub fn object_mut(&mut self, id: ObjectId) -> &mut ObjectData {
+        self.objects[id]
+            .as_mut()
+            .expect("object was garbage collected")
+    }
+
+    pub fn new_object(&mut self) -> ObjectId {
+        let prototype = self.object_prototype;
+        self.alloc(ObjectData::new(Class::Object, Some(prototype)))
+    }
+
+    pub fn new_array(&mut self, elements: Vec<Option<JsValue>>) -> ObjectId {
+        let prototype = self.array_prototype;
+        self.alloc(ObjectData::new(Class::Array(elements), Some(prototype)))
+    }
+
+    pub fn new_function(&mut self, name: &str, function: Function) -> ObjectId {
+        let function_prototype = self.function_prototype;
+        let mut object = ObjectData::new(Class::Function(function), Some(function_prototype));
+        object.define_value("name", JsValue::from_str(name), false);
+        let id = self.alloc(object);
+
+        let prototype = self.new_object();
+        self.object_mut(prototype)
+            .define_value("constructor", JsValue::Object(id), false);
+        self.object_mut(id)
+            .define_value("prototype", JsValue::Object(prototype), false);
+
+        id
+    }
+
+    pub fn new_error(
+        &mut self,
+        kind: ErrorKind,
+        code: Option<JsValue>,
+        message: JsValue,
+    ) -> ObjectId {
+        let prototype = self.error_prototypes[kind as usize];
+        let mut error = ObjectData::new(Class::Error, Some(prototype));
+        error.define_value("message", message, false);
+        if let Some(code) = code {
+            error.define_value("code", code, true);
+        }
+
+        self.alloc(error)
+    }
+
+    pub fn open_scope(&mut self, escapable: bool) -> usize {
+        let escape_slot = if escapable {
+            self.handles.push(JsValue::Undefined);
+            Some(self.handles.len() - 1)
+        } else {
+            None
+        };
+
+        let start = self.handles.len();
+        self.scopes.push(Scope::new(start, escape_slot));
+        self.scopes.len()
+    }
+
+    pub fn close_scope(&mut self, scope: usize) -> NapiResult<()> {
+        if scope == 0 || scope != self.scopes.len() {
+            return Err(napi_handle_scope_mismatch);
+        }
+
+        let scope = self.scopes.pop().unwrap();
+        self.handles.truncate(scope.start);
+
+        Ok(())
+    }
+
+    pub fn reference(&mut self, reference: sys::napi_ref) -> NapiResult<&mut Reference> {
+        let index = (reference as usize)
+            .checked_sub(1)
+            .ok_or(napi_invalid_arg)?;
+        self.references
+            .get_mut(index)
+            .and_then(Option::as_mut)
+            .ok_or(napi_invalid_arg)
+    }
+
+    pub fn new_reference(&mut self, value: JsValue, count: u32) -> sys::napi_ref {
+        self.references.push(Some(Reference {
+            value,
+            count,
+            finalizer: None,
+        }));
+        self.references.len() as sys::napi_ref
+    }
+
+    pub fn type_of(&self, value: &JsValue) -> sys::napi_valuetype {
+        use crate::consts::napi_valuetype::*;
+
+        match value {
+            JsValue::Undefined => napi_undefined,
+            JsValue::Null => napi_null,
+            JsValue::Boolean(_) => napi_boolean,
+            JsValue::Number(_) => napi_number,
+            JsValue::String(_) => napi_string,
+            JsValue::Symbol(_) => napi_symbol,
+            JsValue::BigInt(_) => napi_bigint,
+            JsValue::Object(id) => match self.object(*id).class {
+                Class::Function(_) => napi_function,
+                Class::External(_) => napi_external,
+                _ => napi_object,
+            },
+        }
+    }
+
+    pub fn to_string(&self, value: &JsValue) -> Option<Rc<[u16]>> {
+        let string = match value {
+            JsValue::String(value) => return Some(value.clone()),
+            JsValue::Symbol(_) => return None,
+            JsValue::Undefined => "undefined".to_owned(),
+            JsValue::Null => "null".to_owned(),
+            JsValue::Boolean(value) => value.to_string(),
+            JsValue::Number(value) => number_to_string(*value),
+            JsValue::BigInt(value) => value.to_decimal(),
+            JsValue::Object(id) => match self.object(*id).class {
+                Class::Array(ref elements) => {
+                    let elements = elements
+                        .iter()
+                        .map(|element| match element {
+                            None | Some(JsValue::Undefined) | Some(JsValue::Null) => String::new(),
+                            Some(element) => self
+                                .to_string(element)
+                                .map(|element| String::from_utf16_lossy(&element))
+                                .unwrap_or_default(),
+                        })
+                        .collect::<Vec<_>>();
+                    elements.join(",")
+                }
+                Class::Error => {
+                    let name =
+                        self.to_string(&self.lookup_value(*id, &PropertyKey::from_str("name")));
+                    let message =
+                        self.to_string(&self.lookup_value(*id, &PropertyKey::from_str("message")));
+                    let name = name
+                        .map(|name| String::from_utf16_lossy(&name))
+                        .unwrap_or_default();
+                    let message = message
+                        .map(|message| String::from_utf16_lossy(&message))
+                        .unwrap_or_default();
+
+                    if message.is_empty() {
+                        name
+                    } else {
+                        format!("{}: {}", name, message)
+                    }
+                }
+                _ => "[object Object]".to_owned(),
+            },
+        };
+
+        Some(string.encode_utf16().collect())
+    }
+
+    pub fn to_number(&self, value: &JsValue) -> Option<f64> {
+        match value {
+            JsValue::Undefined => Some(f64::NAN),
+            JsValue::Null => Some(0.0),
+            JsValue::Boolean(value) => Some(f64::from(u8::from(*value))),
+            JsValue::Number(value) => Some(*value),
+            JsValue::String(value) => Some(string_to_number(value)),
+            JsValue::Symbol(_) | JsValue::BigInt(_) => None,
+            JsValue::Object(_) => self.to_string(value).map(|value| string_to_number(&value)),
+        }
+    }
+
+    pub fn to_property_key(&self, value: &JsValue) -> NapiResult<PropertyKey> {
+        match value {
+            JsValue::Symbol(symbol) => Ok(PropertyKey::Symbol(*symbol)),
+            value => self
+                .to_string(value)
+                .map(PropertyKey::String)
+                .ok_or(napi_name_expected),
+        }
+    }
+
+    pub fn array_buffer(&self, id: ObjectId) -> Option<&ArrayBufferData> {
+        match self.object(id).class {
+            Class::ArrayBuffer(ref buffer) => Some(buffer),
+            _ => None,
+        }
+    }
+
+    fn element_pointer(
+        &self,
+        array_type: sys::napi_typedarray_type,
+        view: ViewData,
+        index: u32,
+    ) -> Option<*mut u8> {
+        let size = element_size(array_type);
+        let index = index as usize;
+
+        if index >= view.byte_length / size {
+            return None;
+        }
+
+        let buffer = self.array_buffer(view.buffer)?;
+        Some(unsafe { buffer.data.add(view.byte_offset + index * size) })
+    }
+
+    fn own_virtual_property(&self, object: &ObjectData, key: &PropertyKey) -> Option<JsValue> {
+        match object.class {
+            Class::Array(ref elements) => {
+                if key.is_str("length") {
+                    return Some(JsValue::Number(elements.len() as f64));
+                }
+
+                let index = key.array_index()? as usize;
+                elements.get(index).cloned().flatten()
+            }
+            Class::TypedArray(array_type, view) => {
+                if key.is_str("length") {
+                    return Some(JsValue::Number(
+                        (view.byte_length / element_size(array_type)) as f64,
+                    ));
+                }
+
+                let pointer = self.element_pointer(array_type, view, key.array_index()?)?;
+                Some(unsafe { read_element(array_type, pointer) })
+            }
+            Class::ArrayBuffer(ref buffer) if key.is_str("byteLength") => {
+                Some(JsValue::Number(buffer.byte_length as f64))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn has_own(&self, id: ObjectId, key: &PropertyKey) -> bool {
+        let object = self.object(id);
+        self.own_virtual_property(object, key).is_some() || object.own_property(key).is_some()
+    }
+
+    pub fn has(&self, id: ObjectId, key: &PropertyKey) -> bool {
+        let mut current = Some(id);
+
+        while let Some(id) = current {
+            if self.has_own(id, key) {
+                return true;
+            }
+            current = self.object(id).prototype;
+        }
+
+        false
+    }
+
+    fn lookup(&self, id: ObjectId, key: &PropertyKey) -> Lookup {
+        let mut current = Some(id);
+
+        while let Some(id) = current {
+            let object = self.object(id);
+
+            if let Some(value) = self.own_virtual_property(object, key) {
+                return Lookup::Value(value);
+            }
+
+            match object.own_property(key) {
+                Some(Property::Data { value, .. }) => return Lookup::Value(value.clone()),
+                Some(Property::Accessor { getter, .. }) => {
+                    return match getter {
+                        Some(getter) => Lookup::Getter(*getter),
+                        None => Lookup::Value(JsValue::Undefined),
+                    }
+                }
+                None => current = object.prototype,
+            }
+        }
+
+        Lookup::Value(JsValue::Undefined)
+    }
+
+    pub fn lookup_value(&self, id: ObjectId, key: &PropertyKey) -> JsValue {
+        match self.lookup(id, key) {
+            Lookup::Value(value) => value,
+            Lookup::Getter(_) => JsValue::Undefined,
+        }
+    }
+
+    fn assign(&mut self, id: ObjectId, key: PropertyKey, value: JsValue) -> Assign {
+        let to_number = self.to_number(&value);
+
+        match self.object(id).class {
+            Class::Array(_) => {
+                if key.is_str("length") {
+                    let length = to_number.unwrap_or(0.0).max(0.0) as usize;
+                    if let Class::Array(ref mut elements) = self.object_mut(id).class {
+                        elements.resize(length, None);
+                    }
+                    return Assign::Done;
+                }
+
+                if let Some(index) = key.array_index() {
+                    if let Class::Array(ref mut elements) = self.object_mut(id).class {
+                        let index = index as usize;
+                        if index >= elements.len() {
+                            elements.resize(index + 1, None);
+                        }
+                        elements[index] = Some(value);
+                    }
+                    return Assign::Done;
+                }
+            }
+            Class::TypedArray(array_type, view) => {
+                if let Some(index) = key.array_index() {
+                    if let Some(pointer) = self.element_pointer(array_type, view, index) {
+                        unsafe { write_element(array_type, pointer, &value) };
+                    }
+                    return Assign::Done;
+                }
+            }
+            _ => {}
+        }
+
+        let mut current = Some(id);
+        while let Some(current_id) = current {
+            let object = self.object(current_id);
+
+            match object.own_property(&key) {
+                Some(Property::Data {
+                    writable: false, ..
+                }) => return Assign::Done,
+                Some(Property::Data { .. }) if current_id == id => {
+                    if let Some((
+                        _,
+                        Property::Data {
+                            value: own_value, ..
+                        },
+                    )) = self
+                        .object_mut(id)
+                        .properties
+                        .iter_mut()
+                        .find(|(own_key, _)| *own_key == key)
+                    {
+                        *own_value = value;
+                    }
+                    return Assign::Done;
+                }
+                Some(Property::Data { .. }) => break,
+                Some(Property::Accessor { setter, .. }) => {
+                    return match setter {
+                        Some(setter) => Assign::Setter(*setter),
+                        None => Assign::Done,
+                    }
+                }
+                None => current = object.prototype,
+            }
+        }
+
+        self.object_mut(id).define(
+            key,
+            Property::Data {
+                value,
+                writable: true,
+                enumerable: true,
+                configurable: true,
+            },
+        );
+
+        Assign::Done
+    }
+
+    pub fn delete(&mut self, id: ObjectId, key: &PropertyKey) -> bool {
+        let object = self.object_mut(id);
+
+        if let Class::Array(ref mut elements) = object.class {
+            if key.is_str("length") {
+                return false;
+            }
+
+            if let Some(index) = key.array_index() {
+                if let Some(element) = elements.get_mut(index as usize) {
+                    *element = None;
+                }
+                return true;
+            }
+        }
+
+        let position = object
+            .properties
+            .iter()
+            .position(|(own_key, _)| own_key == key);
+        match position {
+            Some(position) => match object.properties[position].1 {
+                Property::Data { configurable, .. } | Property::Accessor { configurable, .. } => {
+                    if configurable {
+                        object.properties.remove(position);
+                    }
+                    configurable
+                }
+            },
+            None => true,
+        }
+    }
+
+    pub fn property_names(&self, id: ObjectId) -> Vec<PropertyKey> {
+        let mut names: Vec<PropertyKey> = Vec::new();
+        let mut current = Some(id);
+
+        while let Some(id) = current {
+            let object = self.object(id);
+
+            let indices = match object.class {
+                Class::Array(ref elements) => elements
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, element)| element.is_some())
+                    .map(|(index, _)| index)
+                    .collect(),
+                Class::TypedArray(array_type, view) => {
+                    (0..view.byte_length / element_size(array_type)).collect()
+                }
+                _ => Vec::new(),
+            };
+
+            let keys = indices
+                .into_iter()
+                .map(|index| PropertyKey::from_index(index as u32))
+                .chain(object.properties.iter().filter_map(|(key, property)| {
+                    let enumerable = match property {
+                        Property::Data { enumerable, .. }
+                        | Property::Accessor { enumerable, .. } => *enumerable,
+                    };
+
+                    match key {
+                        PropertyKey::String(_) if enumerable => Some(key.clone()),
+                        _ => None,
+                    }
+                }));
+
+            for key in keys {
+                if !names.contains(&key) {
+                    names.push(key);
+                }
+            }
+
+            current = object.prototype;
+        }
+
+        names
+    }
+
+    pub fn is_instance(&self, id: ObjectId, prototype: ObjectId) -> bool {
+        let mut current = self.object(id).prototype;
+
+        while let Some(id) = current {
+            if id == prototype {
+                return true;
+            }
+            current = self.object(id).prototype;
+        }
+
+        false
+    }
+
+    fn collect_garbage(&mut self) -> Vec<Finalizer> {
+        let mut marked = vec![false; self.objects.len()];
+        let mut stack = vec![
+            self.global,
+            self.object_prototype,
+            self.function_prototype,
+            self.array_prototype,
+            self.iterator_prototype,
+        ];
+        stack.extend(self.error_prototypes.iter());
+
+        let roots = self.handles.iter().chain(self.exception.iter()).chain(
+            self.references
+                .iter()
+                .flatten()
+                .filter(|reference| reference.count > 0)
+                .map(|reference| &reference.value),
+        );
+        stack.extend(roots.filter_map(JsValue::as_object));
+
+        while let Some(id) = stack.pop() {
+            if marked[id] {
+                continue;
+            }
+            marked[id] = true;
+
+            let object = self.object(id);
+            stack.extend(object.prototype);
+            stack.extend(object.children().into_iter().filter_map(JsValue::as_object));
+            stack.extend(object.view().map(|view| view.buffer));
+        }
+
+        let mut finalizers = Vec::new();
+
+        for (id, slot) in self.objects.iter_mut().enumerate() {
+            if marked[id] {
+                continue;
+            }
+
+            if let Some(object) = slot.take() {
+                finalizers.extend(object_finalizers(object));
+            }
+        }
+
+        for reference in self.references.iter_mut().flatten() {
+            if let JsValue::Object(id) = reference.value {
+                if self.objects[id].is_none() {
+                    reference.value = JsValue::Undefined;
+                }
+            }
+        }
+
+        finalizers
+    }
+
+    fn take_all_finalizers(&mut self) -> Vec<Finalizer> {
+        self.objects
+            .iter_mut()
+            .filter_map(|slot| slot.as_mut())
+            .flat_map(|object| {
+                let mut finalizers = Vec::new();
+                if let Some((_, Some(finalizer))) = object.wrap.take() {
+                    finalizers.push(finalizer);
+                }
+                finalizers.append(&mut object.finalizers);
+                finalizers
+            })
+            .collect()
+    }
+}
+
+fn object_finalizers(object: ObjectData) -> Vec<Finalizer> {
+    let mut finalizers = Vec::new();
+    if let Some((_, Some(finalizer))) = object.wrap {
+        finalizers.push(finalizer);
+    }
+    finalizers.extend(object.finalizers);
+    finalizers
+}
+
+fn error_message(status: sys::napi_status) -> *const c_char {
+    let message: &'static [u8] = match status {
+        napi_ok => return ptr::null(),
+        napi_invalid_arg => b"Invalid argument\0",
+        napi_object_expected => b"An object was expected\0",
+        napi_string_expected => b"A string was expected\0",
+        napi_name_expected => b"A string or symbol was expected\0",
+        napi_function_expected => b"A function was expected\0",
+        napi_number_expected => b"A number was expected\0",
+        napi_boolean_expected => b"A boolean was expected\0",
+        napi_array_expected => b"An array was expected\0",
+        napi_generic_failure => b"Unknown failure\0",
+        napi_pending_exception => b"An exception is pending\0",
+        napi_cancelled => b"The async work item was cancelled\0",
+        napi_escape_called_twice => b"napi_escape_handle already called on scope\0",
+        napi_handle_scope_mismatch => b"Invalid handle scope usage\0",
+        napi_callback_scope_mismatch => b"Invalid callback scope usage\0",
+        napi_queue_full => b"Thread-safe function queue is full\0",
+        napi_closing => b"Thread-safe function handle is closing\0",
+        napi_bigint_expected => b"A bigint was expected\0",
+        napi_date_expected => b"A date was expected\0",
+        napi_arraybuffer_expected => b"An arraybuffer was expected\0",
+        napi_detachable_arraybuffer_expected => b"A detachable arraybuffer was expected\0",
+        napi_would_deadlock => b"Main thread would deadlock\0",
+        napi_no_external_buffers_allowed => b"External buffers are not allowed\0",
+        napi_cannot_run_js => b"Cannot run JavaScript\0",
+        _ => b"Unknown status\0",
+    };
+
+    message.as_ptr() as *const c_char
+}
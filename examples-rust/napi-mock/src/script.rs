@@ -0,0 +1,611 @@
+// A small evaluator behind `napi_run_script`. It understands literals, identifiers, `+ - * /`,
+// member access, calls, `new`, `throw` and function expressions, whose bodies see their
+// parameters and the globals but no enclosing function's variables. Errors constructed with
+// `new` get a `stack` pointing at the `new` expression.
+
+use std::iter;
+use std::ptr;
+use std::rc::Rc;
+
+use crate::consts::napi_status::*;
+use crate::runtime::{CallbackInfo, Env, ErrorKind, NapiResult};
+use crate::sys;
+use crate::value::*;
+
+pub fn run(env: &Env, source: &str) -> NapiResult<JsValue> {
+    let program = match Parser::new(source).and_then(Parser::program) {
+        Ok(program) => program,
+        Err(message) => return Err(env.throw_error(ErrorKind::SyntaxError, &message)),
+    };
+
+    let mut completion = JsValue::Undefined;
+    for statement in &program {
+        match statement {
+            Statement::Expression(expr) => completion = evaluate(env, &[], expr)?,
+            _ => {
+                execute(env, &[], statement)?;
+            }
+        }
+    }
+
+    Ok(completion)
+}
+
+pub struct Closure {
+    params: Vec<String>,
+    body: Vec<Statement>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    String(String),
+    Identifier(String),
+    Punct(char),
+    End,
+}
+
+enum Literal {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+}
+
+enum Expr {
+    Literal(Literal),
+    Identifier(String),
+    Negate(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+    Member(Box<Expr>, String),
+    Call(Box<Expr>, Vec<Expr>),
+    New(Box<Expr>, Vec<Expr>, Position),
+    Function(String, Rc<Closure>),
+}
+
+enum Statement {
+    Expression(Expr),
+    Return(Option<Expr>),
+    Throw(Expr),
+}
+
+// Each token with its position and whether a line break precedes it, which ends a statement
+// without a semicolon.
+struct Parser {
+    tokens: Vec<(Token, Position, bool)>,
+    next: usize,
+    in_function: bool,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Parser, String> {
+        Ok(Parser {
+            tokens: tokenize(source)?,
+            next: 0,
+            in_function: false,
+        })
+    }
+
+    fn program(mut self) -> Result<Vec<Statement>, String> {
+        let mut statements = Vec::new();
+        while self.peek() != &Token::End {
+            statements.push(self.statement()?);
+        }
+        Ok(statements)
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.next].0
+    }
+
+    fn advance(&mut self) -> (Token, Position) {
+        let (token, position, _) = self.tokens[self.next].clone();
+        if token != Token::End {
+            self.next += 1;
+        }
+        (token, position)
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        if self.peek() == &Token::Punct(punct) {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: char) -> Result<(), String> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Identifier(name) if name == keyword)
+    }
+
+    fn unexpected(&self) -> String {
+        match self.peek() {
+            Token::End => "Unexpected end of input".to_owned(),
+            Token::Number(_) => "Unexpected number".to_owned(),
+            Token::String(_) => "Unexpected string".to_owned(),
+            Token::Identifier(name) => format!("Unexpected identifier '{}'", name),
+            Token::Punct(punct) => format!("Unexpected token '{}'", punct),
+        }
+    }
+
+    // A statement ends at a semicolon, a closing brace, the end of input or a line break.
+    fn at_statement_end(&self) -> bool {
+        let (token, _, newline) = &self.tokens[self.next];
+        *newline || matches!(token, Token::Punct(';') | Token::Punct('}') | Token::End)
+    }
+
+    fn statement(&mut self) -> Result<Statement, String> {
+        let statement = if self.is_keyword("return") {
+            if !self.in_function {
+                return Err("Illegal return statement".to_owned());
+            }
+            self.advance();
+            if self.at_statement_end() {
+                Statement::Return(None)
+            } else {
+                Statement::Return(Some(self.expression()?))
+            }
+        } else if self.is_keyword("throw") {
+            self.advance();
+            if self.at_statement_end() {
+                return Err("Illegal newline after throw".to_owned());
+            }
+            Statement::Throw(self.expression()?)
+        } else {
+            Statement::Expression(self.expression()?)
+        };
+
+        if !self.eat(';') && !self.at_statement_end() {
+            return Err(self.unexpected());
+        }
+        Ok(statement)
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        self.binary(&['+', '-'], Parser::term)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        self.binary(&['*', '/'], Parser::unary)
+    }
+
+    fn binary(
+        &mut self,
+        operators: &[char],
+        operand: fn(&mut Parser) -> Result<Expr, String>,
+    ) -> Result<Expr, String> {
+        let mut expr = operand(self)?;
+        while let Some(&operator) = operators
+            .iter()
+            .find(|&&operator| self.peek() == &Token::Punct(operator))
+        {
+            self.advance();
+            expr = Expr::Binary(operator, Box::new(expr), Box::new(operand(self)?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat('-') {
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+
+        if self.is_keyword("new") {
+            let (_, position) = self.advance();
+            let mut constructor = self.primary()?;
+            while self.eat('.') {
+                constructor = Expr::Member(Box::new(constructor), self.identifier()?);
+            }
+            let args = if self.eat('(') {
+                self.arguments()?
+            } else {
+                Vec::new()
+            };
+            let expr = Expr::New(Box::new(constructor), args, position);
+            return self.postfix(expr);
+        }
+
+        let expr = self.primary()?;
+        self.postfix(expr)
+    }
+
+    fn postfix(&mut self, mut expr: Expr) -> Result<Expr, String> {
+        loop {
+            if self.eat('.') {
+                expr = Expr::Member(Box::new(expr), self.identifier()?);
+            } else if self.eat('(') {
+                expr = Expr::Call(Box::new(expr), self.arguments()?);
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    // Parses the arguments after an opening parenthesis.
+    fn arguments(&mut self) -> Result<Vec<Expr>, String> {
+        let mut args = Vec::new();
+        while !self.eat(')') {
+            if !args.is_empty() {
+                self.expect(',')?;
+            }
+            args.push(self.expression()?);
+        }
+        Ok(args)
+    }
+
+    fn identifier(&mut self) -> Result<String, String> {
+        match self.peek().clone() {
+            Token::Identifier(name) => {
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        if self.eat('(') {
+            let expr = self.expression()?;
+            self.expect(')')?;
+            return Ok(expr);
+        }
+
+        let literal = match self.peek().clone() {
+            Token::Number(value) => Literal::Number(value),
+            Token::String(value) => Literal::String(value),
+            Token::Identifier(ref name) if name == "function" => return self.function(),
+            Token::Identifier(name) => match name.as_str() {
+                "undefined" => Literal::Undefined,
+                "null" => Literal::Null,
+                "true" => Literal::Boolean(true),
+                "false" => Literal::Boolean(false),
+                _ => {
+                    self.advance();
+                    return Ok(Expr::Identifier(name));
+                }
+            },
+            _ => return Err(self.unexpected()),
+        };
+
+        self.advance();
+        Ok(Expr::Literal(literal))
+    }
+
+    fn function(&mut self) -> Result<Expr, String> {
+        self.advance();
+        let name = match self.peek() {
+            Token::Identifier(_) => self.identifier()?,
+            _ => String::new(),
+        };
+
+        self.expect('(')?;
+        let mut params = Vec::new();
+        while !self.eat(')') {
+            if !params.is_empty() {
+                self.expect(',')?;
+            }
+            params.push(self.identifier()?);
+        }
+
+        self.expect('{')?;
+        let in_function = std::mem::replace(&mut self.in_function, true);
+        let mut body = Vec::new();
+        while !self.eat('}') {
+            body.push(self.statement()?);
+        }
+        self.in_function = in_function;
+
+        Ok(Expr::Function(name, Rc::new(Closure { params, body })))
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, Position, bool)>, String> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    let mut position = Position { line: 1, column: 1 };
+    let mut newline = false;
+
+    let advance = |index: &mut usize, position: &mut Position| {
+        if chars[*index] == '\n' {
+            position.line += 1;
+            position.column = 1;
+        } else {
+            position.column += 1;
+        }
+        *index += 1;
+    };
+
+    while index < chars.len() {
+        let start = position;
+        let c = chars[index];
+
+        let token = if c.is_whitespace() {
+            newline |= c == '\n';
+            advance(&mut index, &mut position);
+            continue;
+        } else if c.is_ascii_digit() {
+            let begin = index;
+            while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+                advance(&mut index, &mut position);
+            }
+            let literal = chars[begin..index].iter().collect::<String>();
+            Token::Number(literal.parse().map_err(|_| "Invalid or unexpected token")?)
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let begin = index;
+            while index < chars.len()
+                && (chars[index].is_alphanumeric() || chars[index] == '_' || chars[index] == '$')
+            {
+                advance(&mut index, &mut position);
+            }
+            Token::Identifier(chars[begin..index].iter().collect())
+        } else if c == '\'' || c == '"' {
+            advance(&mut index, &mut position);
+            let mut value = String::new();
+            loop {
+                match chars.get(index) {
+                    None | Some('\n') => return Err("Invalid or unexpected token".to_owned()),
+                    Some(&quote) if quote == c => break,
+                    Some('\\') => {
+                        advance(&mut index, &mut position);
+                        value.push(match chars.get(index) {
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some(&escaped) => escaped,
+                            None => return Err("Invalid or unexpected token".to_owned()),
+                        });
+                    }
+                    Some(&c) => value.push(c),
+                }
+                advance(&mut index, &mut position);
+            }
+            advance(&mut index, &mut position);
+            Token::String(value)
+        } else if "(){},;.+-*/".contains(c) {
+            advance(&mut index, &mut position);
+            Token::Punct(c)
+        } else {
+            return Err("Invalid or unexpected token".to_owned());
+        };
+
+        tokens.push((token, start, newline));
+        newline = false;
+    }
+
+    tokens.push((Token::End, position, true));
+    Ok(tokens)
+}
+
+enum Flow {
+    Normal,
+    Return(JsValue),
+}
+
+fn execute(env: &Env, scope: &[(String, JsValue)], statement: &Statement) -> NapiResult<Flow> {
+    match statement {
+        Statement::Expression(expr) => {
+            evaluate(env, scope, expr)?;
+            Ok(Flow::Normal)
+        }
+        Statement::Return(expr) => Ok(Flow::Return(match expr {
+            Some(expr) => evaluate(env, scope, expr)?,
+            None => JsValue::Undefined,
+        })),
+        Statement::Throw(expr) => {
+            let exception = evaluate(env, scope, expr)?;
+            env.throw(exception);
+            Err(napi_pending_exception)
+        }
+    }
+}
+
+fn evaluate(env: &Env, scope: &[(String, JsValue)], expr: &Expr) -> NapiResult<JsValue> {
+    match expr {
+        Expr::Literal(literal) => Ok(match literal {
+            Literal::Undefined => JsValue::Undefined,
+            Literal::Null => JsValue::Null,
+            Literal::Boolean(value) => JsValue::Boolean(*value),
+            Literal::Number(value) => JsValue::Number(*value),
+            Literal::String(value) => JsValue::from_str(value),
+        }),
+        Expr::Identifier(name) => {
+            if let Some((_, value)) = scope.iter().rev().find(|(param, _)| param == name) {
+                return Ok(value.clone());
+            }
+
+            let global = env.state.borrow().global;
+            let key = PropertyKey::from_str(name);
+            if env
+                .state
+                .borrow()
+                .object(global)
+                .own_property(&key)
+                .is_none()
+            {
+                let message = format!("{} is not defined", name);
+                return Err(env.throw_error(ErrorKind::ReferenceError, &message));
+            }
+            env.get(&JsValue::Object(global), &key)
+        }
+        Expr::Negate(operand) => {
+            let operand = evaluate(env, scope, operand)?;
+            Ok(JsValue::Number(-to_number(env, &operand)?))
+        }
+        Expr::Binary(operator, lhs, rhs) => {
+            let lhs = evaluate(env, scope, lhs)?;
+            let rhs = evaluate(env, scope, rhs)?;
+            binary(env, *operator, &lhs, &rhs)
+        }
+        Expr::Member(object, name) => {
+            let object = evaluate(env, scope, object)?;
+            member(env, &object, name)
+        }
+        Expr::Call(callee, args) => {
+            let (this, function) = match &**callee {
+                Expr::Member(object, name) => {
+                    let object = evaluate(env, scope, object)?;
+                    let function = member(env, &object, name)?;
+                    (object, function)
+                }
+                callee => (JsValue::Undefined, evaluate(env, scope, callee)?),
+            };
+
+            let args = arguments(env, scope, args)?;
+            let function = env.function(&function).map_err(|_| {
+                let message = format!("{} is not a function", describe(callee));
+                env.throw_error(ErrorKind::TypeError, &message)
+            })?;
+            env.call(function, this, &args, None)
+        }
+        Expr::New(constructor, args, position) => {
+            let constructor_value = evaluate(env, scope, constructor)?;
+            let args = arguments(env, scope, args)?;
+            if env.function(&constructor_value).is_err() {
+                let message = format!("{} is not a constructor", describe(constructor));
+                return Err(env.throw_error(ErrorKind::TypeError, &message));
+            }
+
+            let instance = env.construct(&constructor_value, &args)?;
+            capture_stack(env, &instance, *position);
+            Ok(instance)
+        }
+        Expr::Function(name, closure) => {
+            let mut state = env.state.borrow_mut();
+            state.closures.push(closure.clone());
+            let function = Function {
+                callback: call_closure,
+                data: (state.closures.len() - 1) as *mut _,
+            };
+            Ok(JsValue::Object(state.new_function(name, function)))
+        }
+    }
+}
+
+fn arguments(env: &Env, scope: &[(String, JsValue)], args: &[Expr]) -> NapiResult<Vec<JsValue>> {
+    args.iter().map(|arg| evaluate(env, scope, arg)).collect()
+}
+
+fn describe(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(name) => name.clone(),
+        Expr::Member(object, name) => format!("{}.{}", describe(object), name),
+        _ => "expression".to_owned(),
+    }
+}
+
+fn member(env: &Env, object: &JsValue, name: &str) -> NapiResult<JsValue> {
+    match object {
+        JsValue::Object(_) => env.get(object, &PropertyKey::from_str(name)),
+        JsValue::Undefined | JsValue::Null => {
+            let message = format!(
+                "Cannot read properties of {} (reading '{}')",
+                if let JsValue::Null = object {
+                    "null"
+                } else {
+                    "undefined"
+                },
+                name
+            );
+            Err(env.throw_error(ErrorKind::TypeError, &message))
+        }
+        _ => Ok(JsValue::Undefined),
+    }
+}
+
+fn binary(env: &Env, operator: char, lhs: &JsValue, rhs: &JsValue) -> NapiResult<JsValue> {
+    let concatenates = |value: &JsValue| matches!(value, JsValue::String(_) | JsValue::Object(_));
+    if operator == '+' && (concatenates(lhs) || concatenates(rhs)) {
+        let state = env.state.borrow();
+        let (lhs, rhs) = match (state.to_string(lhs), state.to_string(rhs)) {
+            (Some(lhs), Some(rhs)) => (lhs, rhs),
+            _ => {
+                drop(state);
+                let message = "Cannot convert a Symbol value to a string";
+                return Err(env.throw_error(ErrorKind::TypeError, message));
+            }
+        };
+        return Ok(JsValue::String(
+            lhs.iter().chain(rhs.iter()).copied().collect(),
+        ));
+    }
+
+    let (lhs, rhs) = (to_number(env, lhs)?, to_number(env, rhs)?);
+    Ok(JsValue::Number(match operator {
+        '+' => lhs + rhs,
+        '-' => lhs - rhs,
+        '*' => lhs * rhs,
+        _ => lhs / rhs,
+    }))
+}
+
+fn to_number(env: &Env, value: &JsValue) -> NapiResult<f64> {
+    let number = env.state.borrow().to_number(value);
+    number.ok_or_else(|| env.throw_error(ErrorKind::TypeError, "Cannot convert value to a number"))
+}
+
+// Gives errors the `stack` an engine would record where they are constructed.
+fn capture_stack(env: &Env, instance: &JsValue, position: Position) {
+    let mut state = env.state.borrow_mut();
+    let id = match instance {
+        JsValue::Object(id) if matches!(state.object(*id).class, Class::Error) => *id,
+        _ => return,
+    };
+
+    let header = state
+        .to_string(instance)
+        .map(|header| String::from_utf16_lossy(&header))
+        .unwrap_or_default();
+    let stack = format!(
+        "{}\n    at <anonymous>:{}:{}",
+        header, position.line, position.column
+    );
+    state
+        .object_mut(id)
+        .define_value("stack", JsValue::from_str(&stack), false);
+}
+
+unsafe extern "C" fn call_closure(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    let env = Env::from_sys(env).unwrap();
+    let info = &*(info as *const CallbackInfo);
+
+    let (closure, scope) = {
+        let state = env.state.borrow();
+        let closure = state.closures[info.data as usize].clone();
+        let args = info
+            .args
+            .iter()
+            .map(|&arg| state.value(arg).unwrap_or(JsValue::Undefined))
+            .chain(iter::repeat(JsValue::Undefined));
+        let scope = closure.params.iter().cloned().zip(args).collect::<Vec<_>>();
+        (closure, scope)
+    };
+
+    for statement in &closure.body {
+        match execute(env, &scope, statement) {
+            Ok(Flow::Normal) => {}
+            Ok(Flow::Return(value)) => return env.state.borrow_mut().push(value),
+            Err(_) => return ptr::null_mut(),
+        }
+    }
+
+    ptr::null_mut()
+}
@@ -0,0 +1,441 @@
+use std::os::raw::c_void;
+use std::rc::Rc;
+
+use crate::sys;
+
+pub type ObjectId = usize;
+
+#[derive(Clone, Debug)]
+pub enum JsValue {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(Rc<[u16]>),
+    Symbol(usize),
+    BigInt(BigIntValue),
+    Object(ObjectId),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BigIntValue {
+    pub negative: bool,
+    pub words: Rc<[u64]>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyKey {
+    String(Rc<[u16]>),
+    Symbol(usize),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Function {
+    pub callback: unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value,
+    pub data: *mut c_void,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Finalizer {
+    pub callback: unsafe extern "C" fn(sys::napi_env, *mut c_void, *mut c_void),
+    pub data: *mut c_void,
+    pub hint: *mut c_void,
+}
+
+#[derive(Clone, Debug)]
+pub enum Property {
+    Data {
+        value: JsValue,
+        writable: bool,
+        enumerable: bool,
+        configurable: bool,
+    },
+    Accessor {
+        getter: Option<Function>,
+        setter: Option<Function>,
+        enumerable: bool,
+        configurable: bool,
+    },
+}
+
+#[derive(Debug)]
+pub struct ArrayBufferData {
+    pub data: *mut u8,
+    pub byte_length: usize,
+    // Owns the memory behind `data` unless the buffer was created as external.
+    pub _storage: Option<Box<[u8]>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ViewData {
+    pub buffer: ObjectId,
+    pub byte_offset: usize,
+    pub byte_length: usize,
+}
+
+#[derive(Clone, Debug)]
+pub enum PromiseData {
+    Pending,
+    Fulfilled(JsValue),
+    Rejected(JsValue),
+}
+
+#[derive(Debug)]
+pub enum Class {
+    Object,
+    Array(Vec<Option<JsValue>>),
+    Function(Function),
+    Error,
+    ArrayBuffer(ArrayBufferData),
+    TypedArray(sys::napi_typedarray_type, ViewData),
+    DataView(ViewData),
+    External(*mut c_void),
+    Date(f64),
+    Promise(PromiseData),
+    Map(Vec<(JsValue, JsValue)>),
+    Set(Vec<(JsValue, JsValue)>),
+    Iterator(Vec<JsValue>, usize),
+}
+
+#[derive(Debug)]
+pub struct ObjectData {
+    pub class: Class,
+    pub prototype: Option<ObjectId>,
+    pub properties: Vec<(PropertyKey, Property)>,
+    pub wrap: Option<(*mut c_void, Option<Finalizer>)>,
+    pub type_tag: Option<(u64, u64)>,
+    pub finalizers: Vec<Finalizer>,
+}
+
+impl ObjectData {
+    pub fn new(class: Class, prototype: Option<ObjectId>) -> ObjectData {
+        ObjectData {
+            class,
+            prototype,
+            properties: Vec::new(),
+            wrap: None,
+            type_tag: None,
+            finalizers: Vec::new(),
+        }
+    }
+
+    pub fn own_property(&self, key: &PropertyKey) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|(own_key, _)| own_key == key)
+            .map(|(_, property)| property)
+    }
+
+    pub fn define(&mut self, key: PropertyKey, property: Property) {
+        match self
+            .properties
+            .iter_mut()
+            .find(|(own_key, _)| *own_key == key)
+        {
+            Some((_, own_property)) => *own_property = property,
+            None => self.properties.push((key, property)),
+        }
+    }
+
+    pub fn define_value(&mut self, key: &str, value: JsValue, enumerable: bool) {
+        self.define(
+            PropertyKey::from_str(key),
+            Property::Data {
+                value,
+                writable: true,
+                enumerable,
+                configurable: true,
+            },
+        );
+    }
+
+    pub fn children(&self) -> Vec<&JsValue> {
+        let mut children = Vec::new();
+
+        for (_, property) in &self.properties {
+            if let Property::Data { value, .. } = property {
+                children.push(value);
+            }
+        }
+
+        match self.class {
+            Class::Array(ref elements) => children.extend(elements.iter().flatten()),
+            Class::Promise(PromiseData::Fulfilled(ref value))
+            | Class::Promise(PromiseData::Rejected(ref value)) => children.push(value),
+            Class::Map(ref entries) | Class::Set(ref entries) => {
+                children.extend(entries.iter().flat_map(|(key, value)| [key, value]))
+            }
+            Class::Iterator(ref values, _) => children.extend(values),
+            _ => {}
+        }
+
+        children
+    }
+
+    pub fn view(&self) -> Option<ViewData> {
+        match self.class {
+            Class::TypedArray(_, view) | Class::DataView(view) => Some(view),
+            _ => None,
+        }
+    }
+}
+
+impl PropertyKey {
+    pub fn from_str(key: &str) -> PropertyKey {
+        PropertyKey::String(key.encode_utf16().collect())
+    }
+
+    pub fn from_index(index: u32) -> PropertyKey {
+        PropertyKey::from_str(&index.to_string())
+    }
+
+    pub fn array_index(&self) -> Option<u32> {
+        let key = match self {
+            PropertyKey::String(key) => String::from_utf16(key).ok()?,
+            PropertyKey::Symbol(_) => return None,
+        };
+
+        let index = key.parse::<u32>().ok()?;
+        if index != u32::MAX && index.to_string() == key {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_str(&self, expected: &str) -> bool {
+        match self {
+            PropertyKey::String(key) => key.iter().copied().eq(expected.encode_utf16()),
+            PropertyKey::Symbol(_) => false,
+        }
+    }
+
+    pub fn to_value(&self) -> JsValue {
+        match self {
+            PropertyKey::String(key) => JsValue::String(key.clone()),
+            PropertyKey::Symbol(symbol) => JsValue::Symbol(*symbol),
+        }
+    }
+}
+
+impl JsValue {
+    pub fn from_str(value: &str) -> JsValue {
+        JsValue::String(value.encode_utf16().collect())
+    }
+
+    pub fn as_object(&self) -> Option<ObjectId> {
+        match self {
+            JsValue::Object(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            JsValue::Undefined | JsValue::Null => false,
+            JsValue::Boolean(value) => *value,
+            JsValue::Number(value) => *value != 0.0 && !value.is_nan(),
+            JsValue::String(value) => !value.is_empty(),
+            JsValue::BigInt(value) => !value.words.is_empty(),
+            JsValue::Symbol(_) | JsValue::Object(_) => true,
+        }
+    }
+
+    pub fn strict_equals(&self, other: &JsValue) -> bool {
+        match (self, other) {
+            (JsValue::Undefined, JsValue::Undefined) | (JsValue::Null, JsValue::Null) => true,
+            (JsValue::Boolean(lhs), JsValue::Boolean(rhs)) => lhs == rhs,
+            (JsValue::Number(lhs), JsValue::Number(rhs)) => lhs == rhs,
+            (JsValue::String(lhs), JsValue::String(rhs)) => lhs == rhs,
+            (JsValue::Symbol(lhs), JsValue::Symbol(rhs)) => lhs == rhs,
+            (JsValue::BigInt(lhs), JsValue::BigInt(rhs)) => lhs == rhs,
+            (JsValue::Object(lhs), JsValue::Object(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl BigIntValue {
+    pub fn new(negative: bool, words: &[u64]) -> BigIntValue {
+        let length = words.len() - words.iter().rev().take_while(|&&word| word == 0).count();
+        let words = &words[..length];
+
+        BigIntValue {
+            negative: negative && !words.is_empty(),
+            words: words.into(),
+        }
+    }
+
+    pub fn low_word(&self) -> u64 {
+        let word = self.words.first().copied().unwrap_or(0);
+        if self.negative {
+            word.wrapping_neg()
+        } else {
+            word
+        }
+    }
+
+    pub fn to_decimal(&self) -> String {
+        let mut words = self.words.to_vec();
+        let mut chunks = Vec::new();
+
+        while !words.is_empty() {
+            let mut remainder = 0u128;
+            for word in words.iter_mut().rev() {
+                let current = (remainder << 64) | u128::from(*word);
+                *word = (current / 10_000_000_000_000_000_000) as u64;
+                remainder = current % 10_000_000_000_000_000_000;
+            }
+
+            chunks.push(remainder as u64);
+
+            while words.last() == Some(&0) {
+                words.pop();
+            }
+        }
+
+        let mut result = String::new();
+        if self.negative {
+            result.push('-');
+        }
+
+        match chunks.pop() {
+            Some(chunk) => result.push_str(&chunk.to_string()),
+            None => result.push('0'),
+        }
+
+        for chunk in chunks.iter().rev() {
+            result.push_str(&format!("{:019}", chunk));
+        }
+
+        result
+    }
+}
+
+pub fn number_to_string(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_owned()
+    } else if value.is_infinite() {
+        if value > 0.0 { "Infinity" } else { "-Infinity" }.to_owned()
+    } else if value == value.trunc() && value.abs() < 1e21 {
+        format!("{}", value as i128)
+    } else {
+        format!("{}", value)
+    }
+}
+
+pub fn string_to_number(value: &[u16]) -> f64 {
+    let value = String::from_utf16_lossy(value);
+    let value = value.trim();
+
+    match value {
+        "" => 0.0,
+        "Infinity" | "+Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        _ => {
+            let radix = match value.get(..2) {
+                Some("0x") | Some("0X") => 16,
+                Some("0o") | Some("0O") => 8,
+                Some("0b") | Some("0B") => 2,
+                _ => return value.parse().unwrap_or(f64::NAN),
+            };
+
+            u64::from_str_radix(&value[2..], radix)
+                .map(|value| value as f64)
+                .unwrap_or(f64::NAN)
+        }
+    }
+}
+
+/// Whether `array_type` is a known typed array type. The other functions
+/// here only take types that have been checked with this.
+pub fn is_element_type(array_type: sys::napi_typedarray_type) -> bool {
+    array_type.0 <= sys::napi_typedarray_type::napi_biguint64_array.0
+}
+
+pub fn element_size(array_type: sys::napi_typedarray_type) -> usize {
+    use crate::consts::napi_typedarray_type::*;
+
+    match array_type {
+        napi_int8_array | napi_uint8_array | napi_uint8_clamped_array => 1,
+        napi_int16_array | napi_uint16_array => 2,
+        napi_int32_array | napi_uint32_array | napi_float32_array => 4,
+        napi_float64_array | napi_bigint64_array | napi_biguint64_array => 8,
+        _ => unreachable!("unchecked typed array type"),
+    }
+}
+
+pub fn element_type_name(array_type: sys::napi_typedarray_type) -> &'static str {
+    use crate::consts::napi_typedarray_type::*;
+
+    match array_type {
+        napi_int8_array => "Int8Array",
+        napi_uint8_array => "Uint8Array",
+        napi_uint8_clamped_array => "Uint8ClampedArray",
+        napi_int16_array => "Int16Array",
+        napi_uint16_array => "Uint16Array",
+        napi_int32_array => "Int32Array",
+        napi_uint32_array => "Uint32Array",
+        napi_float32_array => "Float32Array",
+        napi_float64_array => "Float64Array",
+        napi_bigint64_array => "BigInt64Array",
+        napi_biguint64_array => "BigUint64Array",
+        _ => unreachable!("unchecked typed array type"),
+    }
+}
+
+pub unsafe fn read_element(array_type: sys::napi_typedarray_type, data: *const u8) -> JsValue {
+    use crate::consts::napi_typedarray_type::*;
+
+    match array_type {
+        napi_int8_array => JsValue::Number(f64::from(*(data as *const i8))),
+        napi_uint8_array | napi_uint8_clamped_array => JsValue::Number(f64::from(*data)),
+        napi_int16_array => JsValue::Number(f64::from((data as *const i16).read_unaligned())),
+        napi_uint16_array => JsValue::Number(f64::from((data as *const u16).read_unaligned())),
+        napi_int32_array => JsValue::Number(f64::from((data as *const i32).read_unaligned())),
+        napi_uint32_array => JsValue::Number(f64::from((data as *const u32).read_unaligned())),
+        napi_float32_array => JsValue::Number(f64::from((data as *const f32).read_unaligned())),
+        napi_float64_array => JsValue::Number((data as *const f64).read_unaligned()),
+        napi_bigint64_array => {
+            let value = (data as *const i64).read_unaligned();
+            JsValue::BigInt(BigIntValue::new(value < 0, &[value.unsigned_abs()]))
+        }
+        napi_biguint64_array => JsValue::BigInt(BigIntValue::new(
+            false,
+            &[(data as *const u64).read_unaligned()],
+        )),
+        _ => unreachable!("unchecked typed array type"),
+    }
+}
+
+pub unsafe fn write_element(array_type: sys::napi_typedarray_type, data: *mut u8, value: &JsValue) {
+    use crate::consts::napi_typedarray_type::*;
+
+    let number = match value {
+        JsValue::Number(number) => *number,
+        JsValue::Boolean(value) => f64::from(u8::from(*value)),
+        JsValue::String(value) => string_to_number(value),
+        JsValue::Null => 0.0,
+        _ => f64::NAN,
+    };
+    let integer = if number.is_finite() {
+        number.trunc().rem_euclid(4_294_967_296.0) as u32
+    } else {
+        0
+    };
+
+    match array_type {
+        napi_int8_array | napi_uint8_array => *data = integer as u8,
+        napi_uint8_clamped_array => *data = number.clamp(0.0, 255.0).round() as u8,
+        napi_int16_array | napi_uint16_array => (data as *mut u16).write_unaligned(integer as u16),
+        napi_int32_array | napi_uint32_array => (data as *mut u32).write_unaligned(integer),
+        napi_float32_array => (data as *mut f32).write_unaligned(number as f32),
+        napi_float64_array => (data as *mut f64).write_unaligned(number),
+        napi_bigint64_array | napi_biguint64_array => {
+            if let JsValue::BigInt(value) = value {
+                (data as *mut u64).write_unaligned(value.low_word());
+            }
+        }
+        _ => unreachable!("unchecked typed array type"),
+    }
+}
@@ -0,0 +1,112 @@
+use napi::{Any, Env, Error, Number, Object, PropertyDescriptor, Value, ValueType};
+use napi_mock::Runtime;
+
+#[napi::callback(mock_add)]
+fn add(env: Env, first: f64, second: f64) -> napi::Result<f64> {
+    let _ = env;
+    Ok(first + second)
+}
+
+struct Counter(std::cell::Cell<u32>);
+
+#[napi::callback(mock_counter_new, class = "Counter", constructor)]
+fn counter_new(env: Env, this: Object, start: u32) -> napi::Result<()> {
+    let _ = env;
+    this.wrap_without_size_hint(Counter(std::cell::Cell::new(start)))
+}
+
+#[napi::callback(mock_counter_increment, class = "Counter")]
+fn counter_increment(env: Env, this: Object, by: Option<u32>) -> napi::Result<u32> {
+    let _ = env;
+    let counter = this.unwrap::<Counter>()?;
+    counter.0.set(counter.0.get() + by.unwrap_or(1));
+    Ok(counter.0.get())
+}
+
+#[napi::module_init]
+fn init(_env: Env, exports: Object) -> napi::Result<()> {
+    exports.define_properties(&[PropertyDescriptor::method("add", mock_add)])
+}
+
+#[test]
+fn callbacks() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let args = [
+        Number::from_i32(env, 2).unwrap().as_sys_value(),
+        Number::from_i32(env, 3).unwrap().as_sys_value(),
+    ];
+    let result = runtime.call(mock_add, &args).unwrap();
+    let result = Any::with_value(env, result).as_number().unwrap();
+    assert_eq!(result.to_f64().unwrap(), 5.0);
+
+    let exception = runtime.call(mock_add, &args[..1]).unwrap_err();
+    let exception = Any::with_value(env, exception).as_object().unwrap();
+    let name = exception.get::<std::string::String>("name").unwrap();
+    assert_eq!(name, "TypeError");
+}
+
+#[test]
+fn module_init() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let exports = runtime.init_module(napi_rs_init).unwrap();
+    let exports = Any::with_value(env, exports).as_object().unwrap();
+    let add = exports.get_named_property("add").unwrap();
+    assert_eq!(add.value_type().unwrap(), ValueType::Function);
+}
+
+#[test]
+fn classes() {
+    use napi::AsObject;
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let class = napi::Function::define_class(
+        env,
+        "Counter",
+        mock_counter_new,
+        &[PropertyDescriptor::method(
+            "increment",
+            mock_counter_increment,
+        )],
+    )
+    .unwrap();
+    let start = Number::from_f64(env, 5.0).unwrap();
+    let counter = class.new_instance(&[start.as_napi_any()]).unwrap();
+    assert!(counter
+        .as_napi_any()
+        .instanceof(&class.as_napi_object())
+        .unwrap());
+
+    let increment = counter
+        .get_named_property("increment")
+        .unwrap()
+        .as_function()
+        .unwrap();
+    let count = increment.call(&counter, &[]).unwrap();
+    assert_eq!(count.as_number().unwrap().to_u32().unwrap(), 6);
+    let by = Number::from_f64(env, 10.0).unwrap();
+    let count = increment.call(&counter, &[by.as_napi_any()]).unwrap();
+    assert_eq!(count.as_number().unwrap().to_u32().unwrap(), 16);
+
+    let error = increment.call(&Object::new(env).unwrap(), &[]).unwrap_err();
+    assert!(error.exception.is_some());
+
+    let message = |error: Error| {
+        Any::with_value(env, error.exception.unwrap())
+            .as_object()
+            .unwrap()
+            .get::<std::string::String>("message")
+            .unwrap()
+    };
+    let error = increment
+        .call(&counter, &[by.as_napi_any(), by.as_napi_any()])
+        .unwrap_err();
+    assert_eq!(message(error), "Expected 0 to 1 arguments, but got 2");
+    let error = class.new_instance(&[]).unwrap_err();
+    assert_eq!(message(error), "Expected 1 arguments, but got 0");
+}
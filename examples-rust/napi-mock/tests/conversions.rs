@@ -0,0 +1,230 @@
+use napi::{Array, Env, Object, String, Value, ValueType};
+use napi_mock::Runtime;
+
+#[test]
+fn derived_conversions() {
+    use napi::{FromJs, IntoJs};
+
+    #[derive(Debug, PartialEq, FromJs, IntoJs)]
+    #[napi(rename_all = "camelCase")]
+    struct Entry {
+        item_count: u32,
+        #[napi(rename = "label")]
+        name: std::string::String,
+        #[napi(skip)]
+        cache: Vec<u32>,
+        #[napi(default)]
+        note: std::string::String,
+        property: std::string::String,
+        object: bool,
+        env: f64,
+    }
+
+    #[derive(Debug, PartialEq, FromJs, IntoJs)]
+    enum Color {
+        Red,
+        #[napi(rename = "GREEN")]
+        Green,
+    }
+
+    #[derive(Debug, PartialEq, FromJs, IntoJs)]
+    #[napi(tag = "kind", content = "data")]
+    enum Event {
+        Click { x: u32, object: u32 },
+        Key(std::string::String),
+        Close,
+    }
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let entry = Entry {
+        item_count: 3,
+        name: "first".to_owned(),
+        cache: vec![1, 2],
+        note: "hi".to_owned(),
+        property: "own".to_owned(),
+        object: true,
+        env: 2.5,
+    };
+    let value = entry.into_js(env).unwrap();
+    let object = value.as_object().unwrap();
+    assert_eq!(object.get::<u32>("itemCount").unwrap(), 3);
+    assert_eq!(object.get::<std::string::String>("label").unwrap(), "first");
+    assert!(!object.has_named_property("name").unwrap());
+    assert!(!object.has_named_property("cache").unwrap());
+    assert_eq!(
+        object.get::<std::string::String>("property").unwrap(),
+        "own"
+    );
+    assert!(object.get::<bool>("object").unwrap());
+    assert_eq!(object.get::<f64>("env").unwrap(), 2.5);
+
+    assert!(object.del_named_property("note").unwrap());
+    let entry = Entry::from_js(value).unwrap();
+    assert!(entry.cache.is_empty());
+    assert_eq!(entry.note, "");
+    assert_eq!(entry.property, "own");
+    assert!(entry.object);
+
+    let green = Color::Green.into_js(env).unwrap();
+    assert_eq!(green.as_string().unwrap().to_string().unwrap(), "GREEN");
+    assert_eq!(Color::from_js(green).unwrap(), Color::Green);
+    let blue = String::from_str(env, "Blue").unwrap().as_napi_any();
+    assert!(Color::from_js(blue).is_err());
+
+    let click = Event::Click { x: 1, object: 2 }.into_js(env).unwrap();
+    let object = click.as_object().unwrap();
+    assert_eq!(object.get::<std::string::String>("kind").unwrap(), "Click");
+    assert_eq!(object.get::<u32>("object").unwrap(), 2);
+    assert_eq!(
+        Event::from_js(click).unwrap(),
+        Event::Click { x: 1, object: 2 }
+    );
+
+    let key = Event::Key("a".to_owned()).into_js(env).unwrap();
+    let object = key.as_object().unwrap();
+    assert_eq!(object.get::<std::string::String>("data").unwrap(), "a");
+    assert_eq!(Event::from_js(key).unwrap(), Event::Key("a".to_owned()));
+    assert_eq!(
+        Event::from_js(Event::Close.into_js(env).unwrap()).unwrap(),
+        Event::Close
+    );
+
+    object.set("kind", "Drag").unwrap();
+    let error = Event::from_js(key).unwrap_err();
+    assert_eq!(error.property_path, ["kind"]);
+}
+
+#[test]
+fn serde_values() {
+    use napi::{from_js, from_js_with, to_js, to_js_with, MapRepr, NoneRepr, SerdeOptions};
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Drawing {
+        name: std::string::String,
+        shapes: Vec<Shape>,
+        layers: BTreeMap<u32, std::string::String>,
+        parent: Option<std::string::String>,
+        #[serde(with = "serde_bytes")]
+        thumbnail: Vec<u8>,
+    }
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let drawing = Drawing {
+        name: "sketch".to_owned(),
+        shapes: vec![
+            Shape::Point,
+            Shape::Circle(1.5),
+            Shape::Rect {
+                width: 3,
+                height: 4,
+            },
+        ],
+        layers: vec![(2, "top".to_owned()), (1, "bottom".to_owned())]
+            .into_iter()
+            .collect(),
+        parent: None,
+        thumbnail: vec![0, 255, 7],
+    };
+
+    let value = to_js(env, &drawing).unwrap();
+    let object = value.as_object().unwrap();
+    assert_eq!(object.get::<std::string::String>("name").unwrap(), "sketch");
+    assert_eq!(
+        object
+            .get_named_property("parent")
+            .unwrap()
+            .value_type()
+            .unwrap(),
+        ValueType::Undefined
+    );
+    assert!(object
+        .get_named_property("thumbnail")
+        .unwrap()
+        .is_buffer()
+        .unwrap());
+    let shapes = object
+        .get_named_property("shapes")
+        .unwrap()
+        .as_array()
+        .unwrap();
+    assert_eq!(
+        shapes
+            .get(0)
+            .unwrap()
+            .as_string()
+            .unwrap()
+            .to_string()
+            .unwrap(),
+        "Point"
+    );
+    assert_eq!(from_js::<Drawing>(value).unwrap(), drawing);
+
+    let options = SerdeOptions {
+        none_as: NoneRepr::Null,
+        maps_as: MapRepr::Map,
+        ..SerdeOptions::default()
+    };
+    let drawing = Drawing {
+        parent: Some("root".to_owned()),
+        ..drawing
+    };
+    let value = to_js_with(env, &drawing, options).unwrap();
+    let layers = value
+        .as_object()
+        .unwrap()
+        .get_named_property("layers")
+        .unwrap();
+    assert!(napi::Map::from_sys_checked(env, layers.as_sys_value()).is_ok());
+    assert_eq!(from_js_with::<Drawing>(value, options).unwrap(), drawing);
+    let value = to_js_with(env, &None::<u32>, options).unwrap();
+    assert_eq!(value.value_type().unwrap(), ValueType::Null);
+    assert_eq!(from_js::<Option<u32>>(value).unwrap(), None);
+
+    // Integers become BigInts only above the safe range, whatever their width.
+    let integers = [
+        (-5, ValueType::Number),
+        (i128::from(u64::MAX) + 1, ValueType::BigInt),
+        (i128::MIN, ValueType::BigInt),
+    ];
+    for (value, value_type) in integers {
+        let js = to_js(env, &value).unwrap();
+        assert_eq!(js.value_type().unwrap(), value_type);
+        assert_eq!(from_js::<i128>(js).unwrap(), value);
+    }
+    let small = to_js(env, &7u128).unwrap();
+    assert_eq!(small.value_type().unwrap(), ValueType::Number);
+    assert_eq!(from_js::<u128>(small).unwrap(), 7);
+    let large = to_js(env, &u128::MAX).unwrap();
+    assert_eq!(large.value_type().unwrap(), ValueType::BigInt);
+    assert_eq!(from_js::<u128>(large).unwrap(), u128::MAX);
+
+    let rect = Object::new(env).unwrap();
+    rect.set("width", "wide").unwrap();
+    rect.set("height", 4).unwrap();
+    let shape = Object::new(env).unwrap();
+    shape.set_named_property("Rect", &rect).unwrap();
+    let shapes = Array::new(env).unwrap();
+    shapes.set(0, &shape).unwrap();
+    let object = Object::new(env).unwrap();
+    object.set_named_property("shapes", &shapes).unwrap();
+
+    let error =
+        from_js::<BTreeMap<std::string::String, Vec<Shape>>>(object.as_napi_any()).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("shapes.0.Rect.width: invalid type: string \"wide\""));
+    assert_eq!(error.property_path, ["shapes", "0", "Rect", "width"]);
+}
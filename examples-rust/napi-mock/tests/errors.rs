@@ -0,0 +1,240 @@
+use napi::{Any, Env, Error, ErrorKind, Number, Object, ResultExt, String, Value};
+use napi_mock::Runtime;
+
+#[napi::callback(mock_fail)]
+fn fail(env: Env, message: std::string::String) -> napi::Result<napi::Undefined> {
+    Err(Error::range_error(env, &String::from_str(env, &message)?))
+}
+
+#[napi::callback(mock_parse)]
+fn parse(env: Env, input: std::string::String) -> napi::Result<u32> {
+    let _ = env;
+    input
+        .parse::<u32>()
+        .context(format!("failed to parse {:?}", input))
+}
+
+#[test]
+fn exceptions() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let message = String::from_str(env, "out of range").unwrap();
+    let exception = runtime
+        .call(mock_fail, &[message.as_sys_value()])
+        .unwrap_err();
+    let exception = Any::with_value(env, exception).as_object().unwrap();
+    assert_eq!(
+        exception.get::<std::string::String>("name").unwrap(),
+        "RangeError"
+    );
+    assert_eq!(
+        exception.get::<std::string::String>("message").unwrap(),
+        "out of range"
+    );
+
+    let error = Any::with_value(env, message.as_sys_value())
+        .as_number()
+        .unwrap_err();
+    assert_eq!(error.kind, ErrorKind::ApplicationError);
+}
+
+#[test]
+fn error_chains() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let input = String::from_str(env, "x").unwrap();
+    let exception = runtime
+        .call(mock_parse, &[input.as_sys_value()])
+        .unwrap_err();
+    let exception = Any::with_value(env, exception).as_object().unwrap();
+    assert_eq!(
+        exception.get::<std::string::String>("message").unwrap(),
+        "failed to parse \"x\": invalid digit found in string"
+    );
+
+    let error = Error::wrap(std::fmt::Error).context("outer");
+    assert_eq!(error.to_string(), "outer");
+    assert_eq!(
+        error.full_message(),
+        "outer: an error occurred when formatting an argument"
+    );
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[test]
+fn property_paths() {
+    use napi::{sys, Array};
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let inner = Array::from_iter(env, vec![Any::new(env).unwrap()]).unwrap();
+    inner.set(1, &String::from_str(env, "x").unwrap()).unwrap();
+    let outer = Array::from_iter(env, vec![inner]).unwrap();
+    let object = Object::new(env).unwrap();
+    object.set_named_property("a", &outer).unwrap();
+    let error = object.get::<Vec<Vec<Option<u32>>>>("a").unwrap_err();
+    assert_eq!(error.property_path, ["a", "0", "1"]);
+    let exception = Any::with_value(env, error.exception.unwrap())
+        .as_object()
+        .unwrap();
+    assert!(exception
+        .get::<std::string::String>("message")
+        .unwrap()
+        .starts_with("a.0.1: "));
+
+    // Errors from a bare status keep the path too.
+    let mut number = 0.0;
+    let string = String::from_str(env, "not a number").unwrap();
+    let status =
+        unsafe { sys::napi_get_value_double(env.as_sys_env(), string.as_sys_value(), &mut number) };
+    let error = env
+        .handle_status(status)
+        .unwrap_err()
+        .in_property(env, "timeout")
+        .in_property(env, "options");
+    assert!(error.exception.is_none());
+    assert_eq!(error.property_path, ["options", "timeout"]);
+    assert_eq!(
+        error.to_string(),
+        "options.timeout: number expected: A number was expected"
+    );
+
+    let error = Error::wrap(std::fmt::Error)
+        .context("invalid duration")
+        .in_property(env, "c")
+        .in_property(env, "b")
+        .in_property(env, "a");
+    assert_eq!(error.to_string(), "a.b.c: invalid duration");
+}
+
+#[test]
+fn error_codes_and_classes() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let message = String::from_str(env, "bad config").unwrap();
+    let error = Error::with_code(env, "ERR_INVALID_CONFIG", &message).with_property(
+        env,
+        "path",
+        "/etc/app.json",
+    );
+    let exception = Any::with_value(env, error.exception.unwrap())
+        .as_object()
+        .unwrap();
+    assert_eq!(
+        exception.get::<std::string::String>("code").unwrap(),
+        "ERR_INVALID_CONFIG"
+    );
+    assert_eq!(
+        exception.get::<std::string::String>("path").unwrap(),
+        "/etc/app.json"
+    );
+
+    let error = Error::wrap(std::fmt::Error).with_property(env, "errno", 2);
+    let exception = Any::with_value(env, error.exception.unwrap())
+        .as_object()
+        .unwrap();
+    assert_eq!(exception.get::<i32>("errno").unwrap(), 2);
+
+    let global = unsafe {
+        let mut global = std::ptr::null_mut();
+        napi::sys::napi_get_global(env.as_sys_env(), &mut global);
+        Any::with_value(env, global).as_object().unwrap()
+    };
+    let class = global
+        .get_named_property("RangeError")
+        .unwrap()
+        .as_object()
+        .unwrap();
+    let error = Error::from_class(env, &class, &message);
+    let exception = Any::with_value(env, error.exception.unwrap())
+        .as_object()
+        .unwrap();
+    assert_eq!(
+        exception.get::<std::string::String>("name").unwrap(),
+        "RangeError"
+    );
+
+    let error = Error::from_class(env, &Object::new(env).unwrap(), &message);
+    assert_eq!(error.kind, ErrorKind::FunctionExpected);
+}
+
+#[test]
+fn caught_exceptions() {
+    use napi::sys;
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let mut function = std::ptr::null_mut();
+    let mut result = std::ptr::null_mut();
+    let message = String::from_str(env, "too far").unwrap();
+    let args = [message.as_sys_value()];
+
+    let error = unsafe {
+        sys::napi_create_function(
+            env.as_sys_env(),
+            std::ptr::null(),
+            0,
+            Some(mock_fail),
+            std::ptr::null_mut(),
+            &mut function,
+        );
+        env.handle_status(sys::napi_call_function(
+            env.as_sys_env(),
+            function,
+            function,
+            args.len(),
+            args.as_ptr(),
+            &mut result,
+        ))
+        .unwrap_err()
+    };
+
+    assert_eq!(error.kind, ErrorKind::PendingException);
+    assert_eq!(error.to_string(), "pending exception: RangeError: too far");
+
+    let exception = error.js_exception(env).unwrap();
+    assert_eq!(exception.name().unwrap(), "RangeError");
+    assert_eq!(exception.message().unwrap(), "too far");
+    assert_eq!(exception.code(), None);
+
+    exception.throw().unwrap();
+    let rethrown = env
+        .handle_status(sys::napi_status::napi_pending_exception)
+        .unwrap_err();
+    let rethrown = rethrown.js_exception(env).unwrap();
+    assert_eq!(rethrown.to_string(), "RangeError: too far");
+}
+
+#[test]
+fn newer_statuses() {
+    use napi::sys;
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let number = Number::from_i32(env, 1).unwrap();
+    let (mut value, mut lossless) = (0, false);
+    let error = env
+        .handle_status(unsafe {
+            sys::napi_get_value_bigint_int64(
+                env.as_sys_env(),
+                number.as_sys_value(),
+                &mut value,
+                &mut lossless,
+            )
+        })
+        .unwrap_err();
+    assert_eq!(error.kind, ErrorKind::BigIntExpected);
+    assert_eq!(error.message.unwrap(), "A bigint was expected");
+    assert_eq!(error.engine_error_code, 0);
+
+    assert_eq!(
+        ErrorKind::from_napi_status(sys::napi_status::napi_ok),
+        ErrorKind::Unknown(0)
+    );
+}
@@ -0,0 +1,329 @@
+use napi::{Any, Env, Number, Object, Value};
+use napi_mock::{PromiseState, Runtime};
+
+#[test]
+fn iterators() {
+    use napi::{Array, FromJs};
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let array = Array::from_iter(env, 0..1000u32).unwrap();
+    assert_eq!(array.len().unwrap(), 1000);
+    array.extend(vec![true, false]).unwrap();
+    assert_eq!(array.len().unwrap(), 1002);
+
+    // The mock hands out handles sequentially, so a handle created after the
+    // loop shows whether iterating leaked any.
+    let before = Any::new(env).unwrap().as_sys_value() as usize;
+    let mut sum = 0;
+    array
+        .for_each(|element| {
+            sum += u32::from_js(element).unwrap_or(0);
+            Ok(())
+        })
+        .unwrap();
+    let after = Any::new(env).unwrap().as_sys_value() as usize;
+    assert_eq!(sum, 999 * 1000 / 2);
+    assert_eq!(after, before + 1);
+
+    // Elements from the iterator outlive the call that produced them.
+    let elements = array.iter().unwrap().collect::<napi::Result<Vec<_>>>();
+    let elements = elements.unwrap();
+    assert_eq!(u32::from_js(elements[999]).unwrap(), 999);
+    assert!(bool::from_js(elements[1000]).unwrap());
+    let last = array.iter().unwrap().last().unwrap().unwrap();
+    assert!(!bool::from_js(last).unwrap());
+
+    let mut iter = array.iter().unwrap();
+    assert_eq!(iter.size_hint(), (1002, Some(1002)));
+    let element = iter.nth(1000).unwrap().unwrap();
+    assert!(bool::from_js(element).unwrap());
+
+    let object = Object::new(env).unwrap();
+    object.set("a", 1).unwrap();
+    object.set("b", "two").unwrap();
+    let keys = object
+        .keys()
+        .unwrap()
+        .map(|key| key.and_then(|key| key.to_string()))
+        .collect::<napi::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(keys, ["a", "b"]);
+
+    let entries = object
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            let (key, value) = entry?;
+            Ok((key.to_string()?, value.to_napi_string()?.to_string()?))
+        })
+        .collect::<napi::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        entries,
+        [
+            ("a".to_owned(), "1".to_owned()),
+            ("b".to_owned(), "two".to_owned())
+        ]
+    );
+
+    let large = Object::new(env).unwrap();
+    for index in 0..1000u32 {
+        large.set(&index.to_string(), index).unwrap();
+    }
+
+    let before = Any::new(env).unwrap().as_sys_value() as usize;
+    let mut count = 0;
+    large
+        .for_each_key(|key| {
+            count += key.to_string()?.parse::<u32>().is_ok() as u32;
+            Ok(())
+        })
+        .unwrap();
+    let after = Any::new(env).unwrap().as_sys_value() as usize;
+    assert_eq!(count, 1000);
+    assert_eq!(after, before + 1);
+
+    let before = Any::new(env).unwrap().as_sys_value() as usize;
+    let mut sum = 0;
+    large
+        .for_each_entry(|key, value| {
+            assert_eq!(key.to_string()?, u32::from_js(value)?.to_string());
+            sum += u32::from_js(value)?;
+            Ok(())
+        })
+        .unwrap();
+    let after = Any::new(env).unwrap().as_sys_value() as usize;
+    assert_eq!(sum, 999 * 1000 / 2);
+    assert_eq!(after, before + 1);
+}
+
+#[test]
+fn rust_iterators() {
+    use napi::{AsObject, FromJs, JsIterator};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Tracked(Rc<Cell<bool>>, std::ops::Range<u32>);
+
+    impl Iterator for Tracked {
+        type Item = u32;
+
+        fn next(&mut self) -> Option<u32> {
+            self.1.next()
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let next = |iterator: &JsIterator| -> (Option<u32>, bool) {
+        let result = iterator
+            .as_napi_object()
+            .get_named_property("next")
+            .unwrap()
+            .as_function()
+            .unwrap()
+            .call(iterator, &[])
+            .unwrap()
+            .as_object()
+            .unwrap();
+        (result.get("value").unwrap(), result.get("done").unwrap())
+    };
+
+    let dropped = Rc::new(Cell::new(false));
+    let iterator = JsIterator::from_rust(env, Tracked(dropped.clone(), 1..3)).unwrap();
+    assert_eq!(next(&iterator), (Some(1), false));
+    assert_eq!(next(&iterator), (Some(2), false));
+    assert!(!dropped.get());
+    assert_eq!(next(&iterator), (None, true));
+    assert!(dropped.get());
+    assert_eq!(next(&iterator), (None, true));
+
+    let symbol = env
+        .symbol_constructor()
+        .unwrap()
+        .as_napi_object()
+        .get_named_property("iterator")
+        .unwrap();
+    let dropped = Rc::new(Cell::new(false));
+    let iterator = JsIterator::from_rust(env, Tracked(dropped.clone(), 0..100)).unwrap();
+    let this = iterator
+        .as_napi_object()
+        .get_property(&symbol)
+        .unwrap()
+        .as_function()
+        .unwrap()
+        .call(&iterator, &[])
+        .unwrap();
+    assert!(this.strict_equals(&iterator).unwrap());
+
+    assert_eq!(next(&iterator), (Some(0), false));
+    let result = iterator
+        .as_napi_object()
+        .get_named_property("return")
+        .unwrap()
+        .as_function()
+        .unwrap()
+        .call(
+            &iterator,
+            &[Number::from_f64(env, 7.0).unwrap().as_napi_any()],
+        )
+        .unwrap();
+    assert_eq!(
+        u32::from_js(
+            result
+                .as_object()
+                .unwrap()
+                .get_named_property("value")
+                .unwrap()
+        )
+        .unwrap(),
+        7
+    );
+    assert!(dropped.get());
+    assert_eq!(next(&iterator), (None, true));
+}
+
+#[test]
+fn async_iterables() {
+    use napi::{AsObject, AsyncIterable, FromJs};
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+
+    struct Counter {
+        range: std::ops::Range<u32>,
+        pulls: Arc<AtomicUsize>,
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl futures_core::Stream for Counter {
+        type Item = u32;
+
+        fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Option<u32>> {
+            self.pulls.fetch_add(1, Ordering::SeqCst);
+            Poll::Ready(self.range.next())
+        }
+    }
+
+    impl Drop for Counter {
+        fn drop(&mut self) {
+            self.dropped.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let call = |iterable: &AsyncIterable, method: &str, args: &[Any]| {
+        iterable
+            .as_napi_object()
+            .get_named_property(method)
+            .unwrap()
+            .as_function()
+            .unwrap()
+            .call(iterable, args)
+            .unwrap()
+    };
+    let settled = |promise: Any| -> (Option<u32>, bool) {
+        match runtime.promise_state(promise.as_sys_value()) {
+            Some(PromiseState::Fulfilled(result)) => {
+                let result = Any::with_value(env, result).as_object().unwrap();
+                (result.get("value").unwrap(), result.get("done").unwrap())
+            }
+            state => panic!("unexpected promise state: {:?}", state),
+        }
+    };
+
+    let pulls = Arc::new(AtomicUsize::new(0));
+    let dropped = Arc::new(AtomicBool::new(false));
+    let counter = Counter {
+        range: 1..3,
+        pulls: pulls.clone(),
+        dropped: dropped.clone(),
+    };
+    let iterable = AsyncIterable::from_stream(env, counter).unwrap();
+
+    let symbol = env
+        .symbol_constructor()
+        .unwrap()
+        .as_napi_object()
+        .get_named_property("asyncIterator")
+        .unwrap();
+    let this = iterable
+        .as_napi_object()
+        .get_property(&symbol)
+        .unwrap()
+        .as_function()
+        .unwrap()
+        .call(&iterable, &[])
+        .unwrap();
+    assert!(this.strict_equals(&iterable).unwrap());
+
+    let promises = (0..4)
+        .map(|_| call(&iterable, "next", &[]))
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        runtime.promise_state(promises[0].as_sys_value()),
+        Some(PromiseState::Pending)
+    ));
+    runtime.run_until_idle();
+
+    let results = promises.into_iter().map(settled).collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        [
+            (Some(1), false),
+            (Some(2), false),
+            (None, true),
+            (None, true)
+        ]
+    );
+    assert_eq!(pulls.load(Ordering::SeqCst), 3);
+    assert_eq!(settled(call(&iterable, "next", &[])), (None, true));
+
+    let pulls = Arc::new(AtomicUsize::new(0));
+    let dropped = Arc::new(AtomicBool::new(false));
+    let counter = Counter {
+        range: 0..100,
+        pulls: pulls.clone(),
+        dropped: dropped.clone(),
+    };
+    let iterable = AsyncIterable::from_stream(env, counter).unwrap();
+
+    let promise = call(&iterable, "next", &[]);
+    runtime.run_until_idle();
+    assert_eq!(settled(promise), (Some(0), false));
+    assert_eq!(pulls.load(Ordering::SeqCst), 1);
+
+    let value = Number::from_f64(env, 7.0).unwrap().as_napi_any();
+    let promise = call(&iterable, "return", &[value]);
+    let result = match runtime.promise_state(promise.as_sys_value()) {
+        Some(PromiseState::Fulfilled(result)) => Any::with_value(env, result).as_object().unwrap(),
+        state => panic!("unexpected promise state: {:?}", state),
+    };
+    assert_eq!(
+        u32::from_js(result.get_named_property("value").unwrap()).unwrap(),
+        7
+    );
+
+    for _ in 0..1000 {
+        if dropped.load(Ordering::SeqCst) {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    assert!(dropped.load(Ordering::SeqCst));
+    assert_eq!(pulls.load(Ordering::SeqCst), 1);
+    assert_eq!(settled(call(&iterable, "next", &[])), (None, true));
+    runtime.run_until_idle();
+}
@@ -0,0 +1,238 @@
+use napi::{Env, Object, Value};
+use napi_mock::Runtime;
+
+#[test]
+fn garbage_collection() {
+    use napi::sys;
+    use std::os::raw::c_void;
+
+    unsafe extern "C" fn finalize(_env: sys::napi_env, data: *mut c_void, _hint: *mut c_void) {
+        *(data as *mut bool) = true;
+    }
+
+    let runtime = Runtime::new();
+    let env = runtime.env();
+    let mut finalized = false;
+    let mut reference = std::ptr::null_mut();
+
+    unsafe {
+        let mut scope = std::ptr::null_mut();
+        let mut object = std::ptr::null_mut();
+        sys::napi_open_handle_scope(env, &mut scope);
+        sys::napi_create_object(env, &mut object);
+        sys::napi_wrap(
+            env,
+            object,
+            &mut finalized as *mut bool as *mut c_void,
+            Some(finalize),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        );
+        sys::napi_create_reference(env, object, 0, &mut reference);
+        sys::napi_close_handle_scope(env, scope);
+    }
+
+    runtime.collect_garbage();
+    assert!(finalized);
+
+    let mut value = std::ptr::null_mut();
+    unsafe { sys::napi_get_reference_value(env, reference, &mut value) };
+    assert!(value.is_null());
+}
+
+#[test]
+fn instance_data() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Cache(Rc<Cell<usize>>, u32);
+
+    impl Drop for Cache {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Rc::new(Cell::new(0));
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    assert!(env.get_instance_data::<Cache>().unwrap().is_none());
+
+    env.set_instance_data(Cache(drops.clone(), 1)).unwrap();
+    env.set_instance_data(std::string::String::from("registry"))
+        .unwrap();
+    assert_eq!(env.get_instance_data::<Cache>().unwrap().unwrap().1, 1);
+    assert_eq!(
+        *env.get_instance_data::<std::string::String>()
+            .unwrap()
+            .unwrap(),
+        "registry"
+    );
+
+    env.set_instance_data(Cache(drops.clone(), 2)).unwrap();
+    assert_eq!(drops.get(), 1);
+    assert_eq!(env.get_instance_data::<Cache>().unwrap().unwrap().1, 2);
+
+    let worker = Runtime::new();
+    let worker_env = Env::from(worker.env());
+    assert!(worker_env.get_instance_data::<Cache>().unwrap().is_none());
+
+    drop(runtime);
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn cleanup_hooks() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let runs = Rc::new(RefCell::new(Vec::new()));
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let hook = |name: &'static str| {
+        let runs = runs.clone();
+        move || runs.borrow_mut().push(name)
+    };
+
+    env.add_cleanup_hook(hook("flush logs")).unwrap().keep();
+    let removed = env.add_cleanup_hook(hook("removed")).unwrap();
+    let join_threads = env.add_cleanup_hook(hook("join threads")).unwrap();
+    drop(removed);
+
+    drop(runtime);
+    assert_eq!(*runs.borrow(), ["join threads", "flush logs"]);
+
+    drop(join_threads);
+    assert_eq!(runs.borrow().len(), 2);
+}
+
+#[test]
+fn object_finalizers() {
+    use napi::{sys, Ref};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let finalized = Rc::new(RefCell::new(Vec::new()));
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let mut scope = std::ptr::null_mut();
+    unsafe { sys::napi_open_handle_scope(env.as_sys_env(), &mut scope) };
+
+    let object = Object::new(env).unwrap();
+    let log = finalized.clone();
+    object
+        .add_finalizer(3, move |_env, fd: i32| log.borrow_mut().push(fd))
+        .unwrap();
+    let log = finalized.clone();
+    let weak = object
+        .add_finalizer_with_ref(7, move |_env, slot: i32| log.borrow_mut().push(slot))
+        .unwrap();
+
+    // Dropping the weak reference early must not cancel its finalizer.
+    let dropped = Object::new(env).unwrap();
+    let log = finalized.clone();
+    let early = dropped
+        .add_finalizer_with_ref(9, move |_env, slot: i32| log.borrow_mut().push(slot))
+        .unwrap();
+    drop(early);
+
+    let kept = Object::new(env).unwrap();
+    let strong = Ref::new(&kept, 1).unwrap();
+
+    assert!(weak.value().unwrap().is_some());
+    unsafe { sys::napi_close_handle_scope(env.as_sys_env(), scope) };
+
+    runtime.collect_garbage();
+    finalized.borrow_mut().sort();
+    assert_eq!(*finalized.borrow(), [3, 7, 9]);
+    assert!(weak.value().unwrap().is_none());
+    assert!(strong.value().unwrap().is_some());
+
+    assert_eq!(strong.decrement().unwrap(), 0);
+    assert_eq!(strong.increment().unwrap(), 1);
+}
+
+#[test]
+fn external_memory() {
+    use napi::{sys, Buffer, External, ExternalMemory};
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let mut memory = ExternalMemory::new(env, 100).unwrap();
+    assert_eq!(env.adjust_external_memory(0).unwrap(), 100);
+    memory.resize(40).unwrap();
+    assert_eq!(env.adjust_external_memory(0).unwrap(), 40);
+    drop(memory);
+    assert_eq!(env.adjust_external_memory(0).unwrap(), 0);
+
+    let mut scope = std::ptr::null_mut();
+    unsafe { sys::napi_open_handle_scope(env.as_sys_env(), &mut scope) };
+
+    let external = External::new(env, vec![0u64; 8]).unwrap();
+    assert_eq!(external.get().len(), 8);
+    let any = external.as_napi_any();
+    assert_eq!(
+        External::<Vec<u64>>::from_sys_checked(env, any.as_sys_value())
+            .unwrap()
+            .get()
+            .len(),
+        8
+    );
+    assert!(External::<u64>::from_sys_checked(env, any.as_sys_value()).is_err());
+
+    let wrapper = Object::new(env).unwrap();
+    wrapper.wrap(vec![1u8; 16]).unwrap();
+    assert_eq!(wrapper.unwrap::<Vec<u8>>().unwrap().len(), 16);
+    assert!(wrapper.unwrap::<u8>().is_err());
+
+    let removed = Object::new(env).unwrap();
+    removed.wrap(vec![2u8; 4]).unwrap();
+
+    let buffer = Buffer::from_vec(env, Vec::with_capacity(32)).unwrap();
+    assert!(buffer.is_empty());
+
+    assert_eq!(env.adjust_external_memory(0).unwrap(), 64 + 16 + 4 + 32);
+    assert_eq!(unsafe { removed.remove_wrap::<Vec<u8>>() }.unwrap(), [2; 4]);
+    assert_eq!(env.adjust_external_memory(0).unwrap(), 64 + 16 + 32);
+    assert!(removed.unwrap::<Vec<u8>>().is_err());
+    removed.wrap(5u8).unwrap();
+    assert_eq!(*removed.unwrap::<u8>().unwrap(), 5);
+    let untracked = External::without_size_hint(env, vec![0u8; 64]).unwrap();
+    assert_eq!(untracked.get().len(), 64);
+    assert_eq!(env.adjust_external_memory(0).unwrap(), 64 + 16 + 32);
+
+    // Data attached by other native code is never read as ours.
+    let mut foreign = 0u64;
+    let foreign = &mut foreign as *mut u64 as *mut std::ffi::c_void;
+    let mut value = std::ptr::null_mut();
+    unsafe {
+        sys::napi_create_external(
+            env.as_sys_env(),
+            foreign,
+            None,
+            std::ptr::null_mut(),
+            &mut value,
+        )
+    };
+    assert!(External::<u64>::from_sys_checked(env, value).is_err());
+    let object = Object::new(env).unwrap();
+    unsafe {
+        sys::napi_wrap(
+            env.as_sys_env(),
+            object.as_sys_value(),
+            foreign,
+            None,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    assert!(object.unwrap::<u64>().is_err());
+
+    unsafe { sys::napi_close_handle_scope(env.as_sys_env(), scope) };
+    runtime.collect_garbage();
+    assert_eq!(env.adjust_external_memory(0).unwrap(), 0);
+}
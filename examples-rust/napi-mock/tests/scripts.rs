@@ -0,0 +1,144 @@
+use napi::{Any, Env, ErrorKind, Number, Object, String, Value};
+use napi_mock::Runtime;
+
+#[test]
+fn run_script() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let result = env.run_script("1 + 2 * 3").unwrap();
+    assert_eq!(result.as_number().unwrap().to_f64().unwrap(), 7.0);
+
+    let result = env.run_script("'n = ' + (10 - 4) / 4").unwrap();
+    assert_eq!(result.as_string().unwrap().to_string().unwrap(), "n = 1.5");
+
+    // The completion value is the last expression statement.
+    let result = env.run_script("1;\n'last'").unwrap();
+    assert_eq!(result.as_string().unwrap().to_string().unwrap(), "last");
+
+    let global = env.global().unwrap();
+    global.set("answer", 42).unwrap();
+    let result = env.run_script("globalThis.answer - 2").unwrap();
+    assert_eq!(result.as_number().unwrap().to_u32().unwrap(), 40);
+}
+
+#[test]
+fn script_exceptions() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    // The message carries the stack of the rethrown exception.
+    let error = env
+        .run_script("1 + 1;\nthrow new TypeError('bad ' + 'script')")
+        .unwrap_err();
+    assert_eq!(error.kind, ErrorKind::PendingException);
+    assert_eq!(
+        error.message.as_deref(),
+        Some("TypeError: bad script\n    at <anonymous>:2:7")
+    );
+    let exception = error.js_exception(env).unwrap();
+    assert_eq!(exception.name().as_deref(), Some("TypeError"));
+    assert_eq!(exception.message().as_deref(), Some("bad script"));
+    assert_eq!(
+        exception.stack().as_deref(),
+        Some("TypeError: bad script\n    at <anonymous>:2:7")
+    );
+    assert!(env.run_script("0").is_ok());
+
+    // Thrown values without a stack are reported as they are.
+    let error = env.run_script("throw 'plain'").unwrap_err();
+    assert_eq!(error.message.as_deref(), Some("plain"));
+
+    let error = env.run_script("missing + 1").unwrap_err();
+    assert_eq!(
+        error.message.as_deref(),
+        Some("ReferenceError: missing is not defined")
+    );
+
+    let error = env.run_script("globalThis.missing()").unwrap_err();
+    assert_eq!(
+        error.message.as_deref(),
+        Some("TypeError: globalThis.missing is not a function")
+    );
+
+    let error = env.run_script("1 +").unwrap_err();
+    assert_eq!(
+        error.message.as_deref(),
+        Some("SyntaxError: Unexpected end of input")
+    );
+    let error = env.run_script("return 1").unwrap_err();
+    assert_eq!(
+        error.message.as_deref(),
+        Some("SyntaxError: Illegal return statement")
+    );
+}
+
+#[test]
+fn eval_function() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+    let undefined = Any::new(env).unwrap();
+
+    let add = env.eval_function(&["a", "b"], "return a + b;").unwrap();
+    let add = add.value().unwrap().unwrap();
+    let args = [
+        Number::from_f64(env, 1.0).unwrap().as_napi_any(),
+        Number::from_f64(env, 2.5).unwrap().as_napi_any(),
+    ];
+    let sum = add.call(&undefined, &args).unwrap();
+    assert_eq!(sum.as_number().unwrap().to_f64().unwrap(), 3.5);
+
+    let args = [
+        String::from_str(env, "a").unwrap().as_napi_any(),
+        Number::from_f64(env, 1.0).unwrap().as_napi_any(),
+    ];
+    let joined = add.call(&undefined, &args).unwrap();
+    assert_eq!(joined.as_string().unwrap().to_string().unwrap(), "a1");
+
+    // Missing arguments are `undefined`.
+    let sum = add.call(&undefined, &args[1..]).unwrap();
+    assert!(sum.as_number().unwrap().to_f64().unwrap().is_nan());
+
+    // Methods are called with their object as `this`.
+    let describe = env
+        .eval_function(&["error"], "return error.toString() + '!';")
+        .unwrap();
+    let error = Object::new(env).unwrap();
+    let to_string = env
+        .eval_function(&[], "return 'custom';")
+        .unwrap()
+        .value()
+        .unwrap()
+        .unwrap();
+    error.set_named_property("toString", &to_string).unwrap();
+    let result = describe
+        .value()
+        .unwrap()
+        .unwrap()
+        .call(&undefined, &[error.as_napi_any()])
+        .unwrap();
+    assert_eq!(result.as_string().unwrap().to_string().unwrap(), "custom!");
+
+    let fail = env
+        .eval_function(&["message"], "throw new RangeError(message);")
+        .unwrap();
+    let message = String::from_str(env, "out of range").unwrap();
+    let error = fail
+        .value()
+        .unwrap()
+        .unwrap()
+        .call(&undefined, &[message.as_napi_any()])
+        .unwrap_err();
+    let exception = error.js_exception(env).unwrap();
+    assert_eq!(exception.name().as_deref(), Some("RangeError"));
+    assert_eq!(
+        exception.stack().as_deref(),
+        Some("RangeError: out of range\n    at <anonymous>:2:7")
+    );
+
+    let error = env.eval_function(&["a"], "return a +;").unwrap_err();
+    assert_eq!(
+        error.message.as_deref(),
+        Some("SyntaxError: Unexpected token ';'")
+    );
+}
@@ -0,0 +1,207 @@
+use napi::{Any, Env, Number, Object, String, Value, ValueType};
+use napi_mock::Runtime;
+
+#[test]
+fn node_streams() {
+    use napi::{AsObject, Buffer, FromJs, Function, Readable, Writable};
+    use std::cell::RefCell;
+    use std::io::{self, Cursor, Read, Write};
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+
+    impl Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct Panicking;
+
+    impl Write for Panicking {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            panic!("writer panicked");
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Sink(Arc<Mutex<(Vec<u8>, usize)>>);
+
+    impl Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.lock().unwrap().1 += 1;
+            Ok(())
+        }
+    }
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    // Stands in for `stream.Readable` and `stream.Writable`, keeping the options.
+    let constructor = |name| {
+        Function::from_fn(env, name, |_env, this, args: &[Any]| {
+            this.as_object()?.set_named_property("options", &args[0])?;
+            Ok(this)
+        })
+        .unwrap()
+    };
+    let module = Object::new(env).unwrap();
+    module
+        .set_named_property("Readable", &constructor("Readable"))
+        .unwrap();
+    module
+        .set_named_property("Writable", &constructor("Writable"))
+        .unwrap();
+    let get_builtin_module =
+        Function::from_fn(env, "getBuiltinModule", move |_env, _this, args| {
+            assert_eq!(<std::string::String as FromJs>::from_js(args[0])?, "stream");
+            Ok(module)
+        })
+        .unwrap();
+    let process = Object::new(env).unwrap();
+    process
+        .set_named_property("getBuiltinModule", &get_builtin_module)
+        .unwrap();
+    env.global()
+        .unwrap()
+        .set_named_property("process", &process)
+        .unwrap();
+
+    let log = Rc::new(RefCell::new(Vec::<std::string::String>::new()));
+    let record = |name: &'static str| {
+        let log = log.clone();
+        Function::from_fn(env, name, move |_env, _this, args: &[Any]| {
+            let arg = match args.first() {
+                None => "-".to_owned(),
+                Some(arg) if arg.value_type()? == ValueType::Null => "null".to_owned(),
+                Some(arg) if arg.is_buffer()? => {
+                    let buffer = Buffer::from_sys_checked(arg.env(), arg.as_sys_value())?;
+                    format!("{:?}", buffer.as_ref())
+                }
+                Some(arg) if arg.value_type()? == ValueType::Undefined => "-".to_owned(),
+                Some(arg) => arg.as_object()?.get::<std::string::String>("message")?,
+            };
+            log.borrow_mut().push(format!("{} {}", name, arg));
+            Ok(true)
+        })
+        .unwrap()
+    };
+    let option = |stream: &Object, name| {
+        stream
+            .get_named_property("options")
+            .unwrap()
+            .as_object()
+            .unwrap()
+            .get_named_property(name)
+            .unwrap()
+            .as_function()
+            .unwrap()
+    };
+    let number = |value| Number::from_f64(env, value).unwrap().as_napi_any();
+    let drain = |log: &Rc<RefCell<Vec<std::string::String>>>| {
+        runtime.run_until_idle();
+        std::mem::take(&mut *log.borrow_mut())
+    };
+
+    let readable =
+        Readable::from_reader_with_high_water_mark(env, Cursor::new(vec![1, 2, 3, 4, 5]), 4)
+            .unwrap()
+            .as_napi_object();
+    readable
+        .set_named_property("push", &record("push"))
+        .unwrap();
+    let options = readable
+        .get_named_property("options")
+        .unwrap()
+        .as_object()
+        .unwrap();
+    assert_eq!(options.get::<u32>("highWaterMark").unwrap(), 4);
+
+    let read = option(&readable, "read");
+    read.call(&readable, &[number(2.0)]).unwrap();
+    read.call(&readable, &[number(2.0)]).unwrap();
+    assert_eq!(drain(&log), ["push [1, 2]"]);
+    read.call(&readable, &[number(4.0)]).unwrap();
+    assert_eq!(drain(&log), ["push [3, 4, 5]"]);
+    read.call(&readable, &[number(4.0)]).unwrap();
+    assert_eq!(drain(&log), ["push null"]);
+    read.call(&readable, &[number(4.0)]).unwrap();
+    assert!(drain(&log).is_empty());
+
+    let failing = Readable::from_reader(env, Failing)
+        .unwrap()
+        .as_napi_object();
+    failing
+        .set_named_property("destroy", &record("destroy"))
+        .unwrap();
+    let read = option(&failing, "read");
+    read.call(&failing, &[number(16.0)]).unwrap();
+    assert_eq!(drain(&log), ["destroy disk on fire"]);
+
+    let sink = Sink::default();
+    let writable = Writable::from_writer(env, sink.clone())
+        .unwrap()
+        .as_napi_object();
+    let chunk = Buffer::from_slice(env, b"abc").unwrap().as_napi_any();
+    let encoding = String::from_str(env, "buffer").unwrap().as_napi_any();
+    option(&writable, "write")
+        .call(
+            &writable,
+            &[chunk, encoding, record("written").as_napi_any()],
+        )
+        .unwrap();
+    assert_eq!(drain(&log), ["written -"]);
+    option(&writable, "final")
+        .call(&writable, &[record("finished").as_napi_any()])
+        .unwrap();
+    assert_eq!(drain(&log), ["finished -"]);
+    assert_eq!(*sink.0.lock().unwrap(), (b"abc".to_vec(), 1));
+
+    let null = napi::Null::new(env).unwrap().as_napi_any();
+    option(&writable, "destroy")
+        .call(&writable, &[null, record("destroyed").as_napi_any()])
+        .unwrap();
+    assert_eq!(drain(&log), ["destroyed null"]);
+
+    let failing = Writable::from_writer(env, Failing)
+        .unwrap()
+        .as_napi_object();
+    option(&failing, "write")
+        .call(
+            &failing,
+            &[chunk, encoding, record("written").as_napi_any()],
+        )
+        .unwrap();
+    assert_eq!(drain(&log), ["written disk full"]);
+
+    // The worker thread dies with the writer, so the next write fails.
+    let panicking = Writable::from_writer(env, Panicking)
+        .unwrap()
+        .as_napi_object();
+    let write = option(&panicking, "write");
+    let args = [chunk, encoding, record("written").as_napi_any()];
+    write.call(&panicking, &args).unwrap();
+    assert!(drain(&log).is_empty());
+    write.call(&panicking, &args).unwrap();
+    assert_eq!(drain(&log), ["written Worker thread has stopped"]);
+}
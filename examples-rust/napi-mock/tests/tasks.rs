@@ -0,0 +1,185 @@
+use napi::{Any, Env, Error, Number, String, Value, ValueType};
+use napi_mock::{PromiseState, Runtime};
+
+#[test]
+fn promises() {
+    use napi::sys;
+
+    let runtime = Runtime::new();
+    let env = runtime.env();
+
+    let mut deferred = std::ptr::null_mut();
+    let mut promise = std::ptr::null_mut();
+    let mut value = std::ptr::null_mut();
+    unsafe {
+        sys::napi_create_promise(env, &mut deferred, &mut promise);
+        assert!(matches!(
+            runtime.promise_state(promise),
+            Some(PromiseState::Pending)
+        ));
+
+        sys::napi_create_int32(env, 1, &mut value);
+        sys::napi_resolve_deferred(env, deferred, value);
+    }
+
+    match runtime.promise_state(promise) {
+        Some(PromiseState::Fulfilled(value)) => {
+            let value = Any::with_value(Env::from(env), value).as_number().unwrap();
+            assert_eq!(value.to_i32().unwrap(), 1);
+        }
+        state => panic!("unexpected promise state: {:?}", state),
+    }
+}
+
+#[test]
+fn async_work() {
+    use napi::sys;
+    use std::os::raw::c_void;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static EXECUTED: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe extern "C" fn execute(_env: sys::napi_env, data: *mut c_void) {
+        EXECUTED.fetch_add(1, Ordering::SeqCst);
+        *(data as *mut u32) = 42;
+    }
+
+    unsafe extern "C" fn complete(
+        _env: sys::napi_env,
+        status: sys::napi_status,
+        data: *mut c_void,
+    ) {
+        assert_eq!(status, sys::napi_status::napi_ok);
+        *(data as *mut u32) += 1;
+    }
+
+    let runtime = Runtime::new();
+    let env = runtime.env();
+    let mut data = 0u32;
+
+    unsafe {
+        let mut name = std::ptr::null_mut();
+        let mut work = std::ptr::null_mut();
+        sys::napi_create_string_utf8(env, b"work\0".as_ptr() as *const _, 4, &mut name);
+        sys::napi_create_async_work(
+            env,
+            std::ptr::null_mut(),
+            name,
+            Some(execute),
+            Some(complete),
+            &mut data as *mut u32 as *mut c_void,
+            &mut work,
+        );
+        assert_eq!(
+            sys::napi_queue_async_work(env, work),
+            sys::napi_status::napi_ok
+        );
+
+        runtime.run_until_idle();
+        sys::napi_delete_async_work(env, work);
+    }
+
+    assert_eq!(EXECUTED.load(Ordering::SeqCst), 1);
+    assert_eq!(data, 43);
+}
+
+#[test]
+fn threadsafe_functions() {
+    use napi::sys;
+    use std::os::raw::c_void;
+
+    struct SendPtr(sys::napi_threadsafe_function);
+    unsafe impl Send for SendPtr {}
+
+    unsafe extern "C" fn call_js(
+        _env: sys::napi_env,
+        _js_callback: sys::napi_value,
+        context: *mut c_void,
+        data: *mut c_void,
+    ) {
+        (*(context as *mut Vec<usize>)).push(data as usize);
+    }
+
+    let runtime = Runtime::new();
+    let env = runtime.env();
+    let mut calls: Vec<usize> = Vec::new();
+
+    unsafe {
+        let mut name = std::ptr::null_mut();
+        let mut function = std::ptr::null_mut();
+        sys::napi_create_string_utf8(env, b"tsfn\0".as_ptr() as *const _, 4, &mut name);
+        sys::napi_create_threadsafe_function(
+            env,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            name,
+            0,
+            1,
+            std::ptr::null_mut(),
+            None,
+            &mut calls as *mut Vec<usize> as *mut c_void,
+            Some(call_js),
+            &mut function,
+        );
+
+        let function = SendPtr(function);
+        std::thread::spawn(move || {
+            let function = function;
+            for index in 1..=3 {
+                sys::napi_call_threadsafe_function(
+                    function.0,
+                    index as *mut c_void,
+                    sys::napi_threadsafe_function_call_mode::napi_tsfn_blocking,
+                );
+            }
+            sys::napi_release_threadsafe_function(
+                function.0,
+                sys::napi_threadsafe_function_release_mode::napi_tsfn_release,
+            );
+        });
+
+        runtime.run_until_idle();
+    }
+
+    assert_eq!(calls, vec![1, 2, 3]);
+}
+
+#[test]
+fn async_contexts() {
+    use napi::{sys, AsyncContext, CallbackScope, FromJs, Function};
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let context = AsyncContext::new(env, "TestResource").unwrap();
+    assert_eq!(
+        context.resource().unwrap().value_type().unwrap(),
+        ValueType::Object
+    );
+
+    let add = Function::from_fn(env, "add", |_env, _this, args: &[Any]| {
+        Ok(f64::from_js(args[0])? + f64::from_js(args[1])?)
+    })
+    .unwrap();
+    let args = [
+        Number::from_f64(env, 1.0).unwrap().as_napi_any(),
+        Number::from_f64(env, 2.0).unwrap().as_napi_any(),
+    ];
+    let undefined = Any::new(env).unwrap();
+    {
+        let _outer = CallbackScope::new(&context).unwrap();
+        let _inner = CallbackScope::new(&context).unwrap();
+        let sum = add.make_callback(&context, &undefined, &args).unwrap();
+        assert_eq!(f64::from_js(sum).unwrap(), 3.0);
+    }
+
+    let status = unsafe { sys::napi_close_callback_scope(env.as_sys_env(), 1 as _) };
+    assert_eq!(status, sys::napi_status::napi_callback_scope_mismatch);
+
+    let fail = Function::from_fn(env, "fail", |env, _this, _args| -> napi::Result<()> {
+        Err(Error::range_error(env, &String::from_str(env, "nope")?))
+    })
+    .unwrap();
+    let error = fail.make_callback(&context, &undefined, &[]).unwrap_err();
+    assert_eq!(error.message.as_deref(), Some("RangeError: nope"));
+}
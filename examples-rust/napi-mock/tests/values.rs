@@ -0,0 +1,174 @@
+use napi::{Any, Array, ArrayBuffer, Env, Number, Object, String, UInt8Array, Value, ValueType};
+use napi_mock::Runtime;
+
+#[test]
+fn primitives() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    assert_eq!(env.napi_version().unwrap(), 8);
+
+    let number = Number::from_f64(env, 1.5).unwrap();
+    assert_eq!(number.to_f64().unwrap(), 1.5);
+    assert_eq!(number.value_type().unwrap(), ValueType::Number);
+
+    let string = String::from_str(env, "héllo").unwrap();
+    assert_eq!(string.to_string().unwrap(), "héllo");
+    assert_eq!(string.to_utf16().unwrap().len(), 5);
+
+    let coerced = number.to_napi_string().unwrap();
+    assert_eq!(coerced.to_string().unwrap(), "1.5");
+}
+
+#[test]
+fn objects_and_arrays() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let object = Object::new(env).unwrap();
+    object.set("answer", 42).unwrap();
+    assert_eq!(object.get::<i32>("answer").unwrap(), 42);
+    assert_eq!(object.get_opt::<i32>("missing").unwrap(), None);
+    assert!(object.has_named_property("answer").unwrap());
+    assert!(object.del_named_property("answer").unwrap());
+    assert!(!object.has_named_property("answer").unwrap());
+
+    let array = Array::new(env).unwrap();
+    array.set(2, &Number::from_i32(env, 7).unwrap()).unwrap();
+    assert_eq!(array.len().unwrap(), 3);
+    assert_eq!(
+        array.get(0).unwrap().value_type().unwrap(),
+        ValueType::Undefined
+    );
+    assert_eq!(
+        array.get(2).unwrap().as_number().unwrap().to_i32().unwrap(),
+        7
+    );
+}
+
+#[test]
+fn typed_arrays() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let mut buffer = ArrayBuffer::new(env, 8).unwrap();
+    buffer.as_mut().copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let array = UInt8Array::from_array_buffer(buffer, 0, 8).unwrap();
+    assert_eq!(array.as_ref(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let value = Any::with_value(env, array.as_sys_value());
+    assert!(value.is_typedarray().unwrap());
+}
+
+#[test]
+fn builtins() {
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let global = env.global().unwrap();
+    assert!(global
+        .get_named_property("globalThis")
+        .unwrap()
+        .strict_equals(&global)
+        .unwrap());
+
+    let error = env.error_constructor().unwrap();
+    let message = String::from_str(env, "boom").unwrap().as_napi_any();
+    let instance = error.new_instance(&[message]).unwrap();
+    assert!(instance.as_napi_any().is_error().unwrap());
+
+    let json = Object::new(env).unwrap();
+    global.set_named_property("JSON", &json).unwrap();
+    assert!(env.json().unwrap().strict_equals(&json).unwrap());
+
+    let replaced = Object::new(env).unwrap();
+    global.set_named_property("JSON", &replaced).unwrap();
+    assert!(env.json().unwrap().strict_equals(&json).unwrap());
+
+    let error = env.promise_constructor().unwrap_err();
+    assert_eq!(error.message.as_deref(), Some("Function expected"));
+    assert!(env.map_constructor().is_ok());
+}
+
+#[test]
+fn maps_and_sets() {
+    use napi::{AsObject, FromJs, IntoJs, Map, Set};
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+    let runtime = Runtime::new();
+    let env = Env::from(runtime.env());
+
+    let map = Map::new(env).unwrap();
+    let one = Number::from_f64(env, 1.0).unwrap();
+    let key = Object::new(env).unwrap();
+    map.set(&one, &String::from_str(env, "one").unwrap())
+        .unwrap();
+    map.set(&key, &one).unwrap();
+    assert_eq!(map.size().unwrap(), 2);
+    assert!(map.has(&Number::from_f64(env, 1.0).unwrap()).unwrap());
+    assert!(!map.has(&Object::new(env).unwrap()).unwrap());
+    assert_eq!(
+        map.get(&one)
+            .unwrap()
+            .as_string()
+            .unwrap()
+            .to_string()
+            .unwrap(),
+        "one"
+    );
+    assert!(map.delete(&key).unwrap());
+    assert!(!map.delete(&key).unwrap());
+    assert_eq!(map.entries().unwrap().len(), 1);
+
+    // Methods shadowed on the instance don't hijack the Rust API.
+    let hijacked = String::from_str(env, "hijacked").unwrap();
+    for name in &["get", "set", "has", "entries"] {
+        map.as_napi_object()
+            .set_named_property(name, &hijacked)
+            .unwrap();
+    }
+    map.set(&key, &one).unwrap();
+    assert!(map.has(&key).unwrap());
+    assert_eq!(
+        map.get(&key)
+            .unwrap()
+            .as_number()
+            .unwrap()
+            .to_u32()
+            .unwrap(),
+        1
+    );
+    assert_eq!(map.entries().unwrap().len(), 2);
+
+    let set = Set::new(env).unwrap();
+    set.add(&one).unwrap();
+    set.add(&Number::from_f64(env, 1.0).unwrap()).unwrap();
+    assert_eq!(set.size().unwrap(), 1);
+    set.as_napi_object()
+        .set_named_property("add", &hijacked)
+        .unwrap();
+    set.add(&key).unwrap();
+    assert_eq!(set.size().unwrap(), 2);
+    assert!(Map::from_js(set.as_napi_any()).is_err());
+    assert!(Set::from_js(map.as_napi_any()).is_err());
+    set.clear().unwrap();
+    assert!(set.values().unwrap().is_empty());
+
+    let scores: HashMap<u32, Vec<bool>> = vec![(7, vec![true]), (3, vec![false, true])]
+        .into_iter()
+        .collect();
+    let value = scores.clone().into_js(env).unwrap();
+    assert!(Map::from_js(value).is_ok());
+    assert_eq!(HashMap::<u32, Vec<bool>>::from_js(value).unwrap(), scores);
+    assert_eq!(
+        BTreeMap::<u32, Vec<bool>>::from_js(value).unwrap(),
+        scores.into_iter().collect()
+    );
+
+    let tags: BTreeSet<std::string::String> =
+        vec!["a".to_owned(), "b".to_owned()].into_iter().collect();
+    let value = tags.clone().into_js(env).unwrap();
+    assert_eq!(BTreeSet::from_js(value).unwrap(), tags);
+    assert!(BTreeSet::<u32>::from_js(value).is_err());
+}
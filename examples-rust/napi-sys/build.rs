@@ -0,0 +1,114 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "bindgen")]
+#[path = "build/versions.rs"]
+mod versions;
+
+#[cfg(feature = "bindgen")]
+const UPDATE_ENV_VAR: &str = "NAPI_SYS_UPDATE_BINDINGS";
+
+#[cfg(feature = "bindgen")]
+const HEADERS: &[&str] = &[
+    "include/js_native_api.h",
+    "include/js_native_api_types.h",
+    "include/node_api.h",
+    "include/node_api_types.h",
+];
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/bindings.rs");
+
+    #[cfg(feature = "bindgen")]
+    let bindings = generate_bindings(&manifest_dir);
+
+    #[cfg(not(feature = "bindgen"))]
+    let bindings = fs::read_to_string(manifest_dir.join("src/bindings.rs")).unwrap();
+
+    #[cfg(feature = "dynamic-loading")]
+    let bindings = dynamic_bindings(&bindings, &out_dir);
+
+    write_bindings(&bindings, &out_dir);
+}
+
+#[cfg(feature = "bindgen")]
+fn generate_bindings(manifest_dir: &Path) -> String {
+    use std::collections::BTreeMap;
+
+    println!("cargo:rerun-if-changed=build/versions.rs");
+    println!("cargo:rerun-if-env-changed={}", UPDATE_ENV_VAR);
+
+    let mut versions = BTreeMap::new();
+    for header in HEADERS {
+        println!("cargo:rerun-if-changed={}", header);
+        let source = fs::read_to_string(manifest_dir.join(header)).unwrap();
+        versions::function_versions(&source, &mut versions);
+    }
+
+    // Generate everything up to the newest version with a `napiN` feature
+    // and let the features decide what is visible.
+    let bindings = bindgen::Builder::default()
+        .header(manifest_dir.join("include/node_api.h").to_str().unwrap())
+        .clang_arg("-DNAPI_VERSION=8")
+        .allowlist_function("napi_.*|node_api_.*")
+        .allowlist_type("napi_.*|node_api_.*")
+        .bitfield_enum("napi_property_attributes|napi_key_filter")
+        // The engine may return values that are missing from the headers,
+        // e.g. statuses added by a newer Node.js, which would be UB for a
+        // Rust enum.
+        .default_enum_style(bindgen::EnumVariation::NewType {
+            is_bitfield: false,
+            is_global: false,
+        })
+        .rust_target(bindgen::RustTarget::stable(77, 0).unwrap())
+        .generate()
+        .expect("failed to generate N-API bindings")
+        .to_string();
+
+    let bindings = versions::gate_functions(&bindings, &versions);
+
+    if env::var_os(UPDATE_ENV_VAR).is_some() {
+        fs::write(manifest_dir.join("src/bindings.rs"), &bindings).unwrap();
+    }
+
+    bindings
+}
+
+/// Replaces the `extern "C"` blocks with wrappers that call through function
+/// pointers resolved at runtime, and records every symbol name so that they
+/// can all be resolved when the host is loaded.
+#[cfg(feature = "dynamic-loading")]
+fn dynamic_bindings(bindings: &str, out_dir: &Path) -> String {
+    let symbols: Vec<String> = bindings
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("pub fn "))
+        .map(|line| format!("{:?}", &line[..line.find('(').unwrap()]))
+        .collect();
+
+    fs::write(
+        out_dir.join("symbols.rs"),
+        format!("[{}]\n", symbols.join(", ")),
+    )
+    .unwrap();
+
+    // Let the tests stand in for the host by exporting N-API functions
+    // from the test binary itself.
+    if env::var("CARGO_CFG_TARGET_FAMILY").as_deref() == Ok("unix") {
+        println!("cargo:rustc-link-arg-tests=-rdynamic");
+    }
+
+    bindings.replace("extern \"C\" {", "dynamic_functions! {")
+}
+
+#[cfg(any(feature = "bindgen", feature = "dynamic-loading"))]
+fn write_bindings(bindings: &str, out_dir: &Path) {
+    fs::write(out_dir.join("bindings.rs"), bindings).unwrap();
+}
+
+#[cfg(not(any(feature = "bindgen", feature = "dynamic-loading")))]
+fn write_bindings(_: &str, _: &Path) {}
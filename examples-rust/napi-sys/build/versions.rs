@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+/// Maps every stable `NAPI_EXTERN` function declared in `header` to the
+/// `NAPI_VERSION` it first appeared in. Functions only available under
+/// `NAPI_EXPERIMENTAL` are skipped.
+pub fn function_versions(header: &str, versions: &mut BTreeMap<String, u32>) {
+    let source = header.replace("\\\n", " ");
+
+    // Each entry is `Some(version)` for `#if NAPI_VERSION >= N`, `None` for
+    // blocks that are irrelevant to versioning, and `Some(u32::MAX)` for
+    // experimental blocks.
+    let mut conditions: Vec<Option<u32>> = Vec::new();
+    let mut declaration = String::new();
+
+    for line in source.lines() {
+        let line = line.split("//").next().unwrap().trim();
+
+        if let Some(directive) = line.strip_prefix('#') {
+            let directive = directive.trim_start();
+            if let Some(condition) = directive.strip_prefix("if") {
+                conditions.push(parse_condition(condition));
+            } else if directive.starts_with("else") || directive.starts_with("elif") {
+                if let Some(condition) = conditions.last_mut() {
+                    if *condition == Some(u32::MAX) {
+                        *condition = None;
+                    }
+                }
+            } else if directive.starts_with("endif") {
+                conditions.pop();
+            }
+            continue;
+        }
+
+        if declaration.is_empty() && !line.starts_with("NAPI_EXTERN ") {
+            continue;
+        }
+        declaration.push_str(line);
+        declaration.push(' ');
+
+        if line.ends_with(';') {
+            let version = conditions.iter().flatten().copied().max().unwrap_or(1);
+            if version != u32::MAX {
+                versions.insert(function_name(&declaration), version);
+            }
+            declaration.clear();
+        }
+    }
+}
+
+fn parse_condition(condition: &str) -> Option<u32> {
+    let condition = condition.trim();
+    if condition.contains("NAPI_EXPERIMENTAL") && !condition.starts_with('!') {
+        if condition.starts_with("def ") || condition.starts_with("def(") {
+            return Some(u32::MAX);
+        }
+        return None;
+    }
+
+    condition
+        .split("NAPI_VERSION >=")
+        .nth(1)
+        .and_then(|version| version.trim().parse().ok())
+}
+
+fn function_name(declaration: &str) -> String {
+    let name = &declaration[..declaration.find('(').unwrap()];
+    name.rsplit(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .next()
+        .unwrap()
+        .to_string()
+}
+
+/// Puts every `extern "C"` block declaring a function newer than N-API 1
+/// behind the matching `napiN` feature.
+pub fn gate_functions(bindings: &str, versions: &BTreeMap<String, u32>) -> String {
+    let mut output = String::with_capacity(bindings.len());
+    let mut lines = bindings.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if line == "extern \"C\" {" {
+            let version = lines
+                .peek()
+                .and_then(|next| next.trim().strip_prefix("pub fn "))
+                .and_then(|next| versions.get(&function_name(next)));
+
+            if let Some(&version) = version.filter(|&&version| version > 1) {
+                output.push_str(&format!("#[cfg(feature = \"napi{}\")]\n", version));
+            }
+        }
+
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    output
+}
@@ -1,4 +1,4 @@
-/* automatically generated by rust-bindgen */
+/* automatically generated by rust-bindgen 0.72.1 */
 
 pub type __uint16_t = ::std::os::raw::c_ushort;
 pub type __int32_t = ::std::os::raw::c_int;
@@ -11,6 +11,8 @@ pub struct napi_env__ {
     _unused: [u8; 0],
 }
 pub type napi_env = *mut napi_env__;
+pub type node_api_nogc_env = napi_env;
+pub type node_api_basic_env = node_api_nogc_env;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct napi_value__ {
@@ -47,69 +49,200 @@ pub struct napi_deferred__ {
     _unused: [u8; 0],
 }
 pub type napi_deferred = *mut napi_deferred__;
-#[repr(u32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum napi_property_attributes {
-    napi_default = 0,
-    napi_writable = 1,
-    napi_enumerable = 2,
-    napi_configurable = 4,
-    napi_static = 1024,
-}
-#[repr(u32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum napi_valuetype {
-    napi_undefined = 0,
-    napi_null = 1,
-    napi_boolean = 2,
-    napi_number = 3,
-    napi_string = 4,
-    napi_symbol = 5,
-    napi_object = 6,
-    napi_function = 7,
-    napi_external = 8,
-    napi_bigint = 9,
-}
-#[repr(u32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum napi_typedarray_type {
-    napi_int8_array = 0,
-    napi_uint8_array = 1,
-    napi_uint8_clamped_array = 2,
-    napi_int16_array = 3,
-    napi_uint16_array = 4,
-    napi_int32_array = 5,
-    napi_uint32_array = 6,
-    napi_float32_array = 7,
-    napi_float64_array = 8,
-    napi_bigint64_array = 9,
-    napi_biguint64_array = 10,
-}
-#[repr(u32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum napi_status {
-    napi_ok = 0,
-    napi_invalid_arg = 1,
-    napi_object_expected = 2,
-    napi_string_expected = 3,
-    napi_name_expected = 4,
-    napi_function_expected = 5,
-    napi_number_expected = 6,
-    napi_boolean_expected = 7,
-    napi_array_expected = 8,
-    napi_generic_failure = 9,
-    napi_pending_exception = 10,
-    napi_cancelled = 11,
-    napi_escape_called_twice = 12,
-    napi_handle_scope_mismatch = 13,
-    napi_callback_scope_mismatch = 14,
-    napi_queue_full = 15,
-    napi_closing = 16,
-    napi_bigint_expected = 17,
-    napi_date_expected = 18,
-    napi_arraybuffer_expected = 19,
-    napi_detachable_arraybuffer_expected = 20,
+impl napi_property_attributes {
+    pub const napi_default: napi_property_attributes = napi_property_attributes(0);
 }
+impl napi_property_attributes {
+    pub const napi_writable: napi_property_attributes = napi_property_attributes(1);
+}
+impl napi_property_attributes {
+    pub const napi_enumerable: napi_property_attributes = napi_property_attributes(2);
+}
+impl napi_property_attributes {
+    pub const napi_configurable: napi_property_attributes = napi_property_attributes(4);
+}
+impl napi_property_attributes {
+    pub const napi_static: napi_property_attributes = napi_property_attributes(1024);
+}
+impl napi_property_attributes {
+    pub const napi_default_method: napi_property_attributes = napi_property_attributes(5);
+}
+impl napi_property_attributes {
+    pub const napi_default_jsproperty: napi_property_attributes = napi_property_attributes(7);
+}
+impl ::std::ops::BitOr<napi_property_attributes> for napi_property_attributes {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        napi_property_attributes(self.0 | other.0)
+    }
+}
+impl ::std::ops::BitOrAssign for napi_property_attributes {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: napi_property_attributes) {
+        self.0 |= rhs.0;
+    }
+}
+impl ::std::ops::BitAnd<napi_property_attributes> for napi_property_attributes {
+    type Output = Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        napi_property_attributes(self.0 & other.0)
+    }
+}
+impl ::std::ops::BitAndAssign for napi_property_attributes {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: napi_property_attributes) {
+        self.0 &= rhs.0;
+    }
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct napi_property_attributes(pub u32);
+impl napi_valuetype {
+    pub const napi_undefined: napi_valuetype = napi_valuetype(0);
+}
+impl napi_valuetype {
+    pub const napi_null: napi_valuetype = napi_valuetype(1);
+}
+impl napi_valuetype {
+    pub const napi_boolean: napi_valuetype = napi_valuetype(2);
+}
+impl napi_valuetype {
+    pub const napi_number: napi_valuetype = napi_valuetype(3);
+}
+impl napi_valuetype {
+    pub const napi_string: napi_valuetype = napi_valuetype(4);
+}
+impl napi_valuetype {
+    pub const napi_symbol: napi_valuetype = napi_valuetype(5);
+}
+impl napi_valuetype {
+    pub const napi_object: napi_valuetype = napi_valuetype(6);
+}
+impl napi_valuetype {
+    pub const napi_function: napi_valuetype = napi_valuetype(7);
+}
+impl napi_valuetype {
+    pub const napi_external: napi_valuetype = napi_valuetype(8);
+}
+impl napi_valuetype {
+    pub const napi_bigint: napi_valuetype = napi_valuetype(9);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct napi_valuetype(pub u32);
+impl napi_typedarray_type {
+    pub const napi_int8_array: napi_typedarray_type = napi_typedarray_type(0);
+}
+impl napi_typedarray_type {
+    pub const napi_uint8_array: napi_typedarray_type = napi_typedarray_type(1);
+}
+impl napi_typedarray_type {
+    pub const napi_uint8_clamped_array: napi_typedarray_type = napi_typedarray_type(2);
+}
+impl napi_typedarray_type {
+    pub const napi_int16_array: napi_typedarray_type = napi_typedarray_type(3);
+}
+impl napi_typedarray_type {
+    pub const napi_uint16_array: napi_typedarray_type = napi_typedarray_type(4);
+}
+impl napi_typedarray_type {
+    pub const napi_int32_array: napi_typedarray_type = napi_typedarray_type(5);
+}
+impl napi_typedarray_type {
+    pub const napi_uint32_array: napi_typedarray_type = napi_typedarray_type(6);
+}
+impl napi_typedarray_type {
+    pub const napi_float32_array: napi_typedarray_type = napi_typedarray_type(7);
+}
+impl napi_typedarray_type {
+    pub const napi_float64_array: napi_typedarray_type = napi_typedarray_type(8);
+}
+impl napi_typedarray_type {
+    pub const napi_bigint64_array: napi_typedarray_type = napi_typedarray_type(9);
+}
+impl napi_typedarray_type {
+    pub const napi_biguint64_array: napi_typedarray_type = napi_typedarray_type(10);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct napi_typedarray_type(pub u32);
+impl napi_status {
+    pub const napi_ok: napi_status = napi_status(0);
+}
+impl napi_status {
+    pub const napi_invalid_arg: napi_status = napi_status(1);
+}
+impl napi_status {
+    pub const napi_object_expected: napi_status = napi_status(2);
+}
+impl napi_status {
+    pub const napi_string_expected: napi_status = napi_status(3);
+}
+impl napi_status {
+    pub const napi_name_expected: napi_status = napi_status(4);
+}
+impl napi_status {
+    pub const napi_function_expected: napi_status = napi_status(5);
+}
+impl napi_status {
+    pub const napi_number_expected: napi_status = napi_status(6);
+}
+impl napi_status {
+    pub const napi_boolean_expected: napi_status = napi_status(7);
+}
+impl napi_status {
+    pub const napi_array_expected: napi_status = napi_status(8);
+}
+impl napi_status {
+    pub const napi_generic_failure: napi_status = napi_status(9);
+}
+impl napi_status {
+    pub const napi_pending_exception: napi_status = napi_status(10);
+}
+impl napi_status {
+    pub const napi_cancelled: napi_status = napi_status(11);
+}
+impl napi_status {
+    pub const napi_escape_called_twice: napi_status = napi_status(12);
+}
+impl napi_status {
+    pub const napi_handle_scope_mismatch: napi_status = napi_status(13);
+}
+impl napi_status {
+    pub const napi_callback_scope_mismatch: napi_status = napi_status(14);
+}
+impl napi_status {
+    pub const napi_queue_full: napi_status = napi_status(15);
+}
+impl napi_status {
+    pub const napi_closing: napi_status = napi_status(16);
+}
+impl napi_status {
+    pub const napi_bigint_expected: napi_status = napi_status(17);
+}
+impl napi_status {
+    pub const napi_date_expected: napi_status = napi_status(18);
+}
+impl napi_status {
+    pub const napi_arraybuffer_expected: napi_status = napi_status(19);
+}
+impl napi_status {
+    pub const napi_detachable_arraybuffer_expected: napi_status = napi_status(20);
+}
+impl napi_status {
+    pub const napi_would_deadlock: napi_status = napi_status(21);
+}
+impl napi_status {
+    pub const napi_no_external_buffers_allowed: napi_status = napi_status(22);
+}
+impl napi_status {
+    pub const napi_cannot_run_js: napi_status = napi_status(23);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct napi_status(pub u32);
 pub type napi_callback = ::std::option::Option<
     unsafe extern "C" fn(env: napi_env, info: napi_callback_info) -> napi_value,
 >;
@@ -120,6 +253,8 @@ pub type napi_finalize = ::std::option::Option<
         finalize_hint: *mut ::std::os::raw::c_void,
     ),
 >;
+pub type node_api_nogc_finalize = napi_finalize;
+pub type node_api_basic_finalize = node_api_nogc_finalize;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct napi_property_descriptor {
@@ -132,103 +267,19 @@ pub struct napi_property_descriptor {
     pub attributes: napi_property_attributes,
     pub data: *mut ::std::os::raw::c_void,
 }
-#[test]
-fn bindgen_test_layout_napi_property_descriptor() {
-    assert_eq!(
-        ::std::mem::size_of::<napi_property_descriptor>(),
-        64usize,
-        concat!("Size of: ", stringify!(napi_property_descriptor))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<napi_property_descriptor>(),
-        8usize,
-        concat!("Alignment of ", stringify!(napi_property_descriptor))
-    );
-    assert_eq!(
-        unsafe {
-            &(*(::std::ptr::null::<napi_property_descriptor>())).utf8name as *const _ as usize
-        },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_property_descriptor),
-            "::",
-            stringify!(utf8name)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_property_descriptor>())).name as *const _ as usize },
-        8usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_property_descriptor),
-            "::",
-            stringify!(name)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_property_descriptor>())).method as *const _ as usize },
-        16usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_property_descriptor),
-            "::",
-            stringify!(method)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_property_descriptor>())).getter as *const _ as usize },
-        24usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_property_descriptor),
-            "::",
-            stringify!(getter)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_property_descriptor>())).setter as *const _ as usize },
-        32usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_property_descriptor),
-            "::",
-            stringify!(setter)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_property_descriptor>())).value as *const _ as usize },
-        40usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_property_descriptor),
-            "::",
-            stringify!(value)
-        )
-    );
-    assert_eq!(
-        unsafe {
-            &(*(::std::ptr::null::<napi_property_descriptor>())).attributes as *const _ as usize
-        },
-        48usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_property_descriptor),
-            "::",
-            stringify!(attributes)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_property_descriptor>())).data as *const _ as usize },
-        56usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_property_descriptor),
-            "::",
-            stringify!(data)
-        )
-    );
-}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of napi_property_descriptor"][::std::mem::size_of::<napi_property_descriptor>() - 64usize];
+    ["Alignment of napi_property_descriptor"][::std::mem::align_of::<napi_property_descriptor>() - 8usize];
+    ["Offset of field: napi_property_descriptor::utf8name"][::std::mem::offset_of!(napi_property_descriptor, utf8name) - 0usize];
+    ["Offset of field: napi_property_descriptor::name"][::std::mem::offset_of!(napi_property_descriptor, name) - 8usize];
+    ["Offset of field: napi_property_descriptor::method"][::std::mem::offset_of!(napi_property_descriptor, method) - 16usize];
+    ["Offset of field: napi_property_descriptor::getter"][::std::mem::offset_of!(napi_property_descriptor, getter) - 24usize];
+    ["Offset of field: napi_property_descriptor::setter"][::std::mem::offset_of!(napi_property_descriptor, setter) - 32usize];
+    ["Offset of field: napi_property_descriptor::value"][::std::mem::offset_of!(napi_property_descriptor, value) - 40usize];
+    ["Offset of field: napi_property_descriptor::attributes"][::std::mem::offset_of!(napi_property_descriptor, attributes) - 48usize];
+    ["Offset of field: napi_property_descriptor::data"][::std::mem::offset_of!(napi_property_descriptor, data) - 56usize];
+};
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct napi_extended_error_info {
@@ -237,72 +288,96 @@ pub struct napi_extended_error_info {
     pub engine_error_code: u32,
     pub error_code: napi_status,
 }
-#[test]
-fn bindgen_test_layout_napi_extended_error_info() {
-    assert_eq!(
-        ::std::mem::size_of::<napi_extended_error_info>(),
-        24usize,
-        concat!("Size of: ", stringify!(napi_extended_error_info))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<napi_extended_error_info>(),
-        8usize,
-        concat!("Alignment of ", stringify!(napi_extended_error_info))
-    );
-    assert_eq!(
-        unsafe {
-            &(*(::std::ptr::null::<napi_extended_error_info>())).error_message as *const _ as usize
-        },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_extended_error_info),
-            "::",
-            stringify!(error_message)
-        )
-    );
-    assert_eq!(
-        unsafe {
-            &(*(::std::ptr::null::<napi_extended_error_info>())).engine_reserved as *const _
-                as usize
-        },
-        8usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_extended_error_info),
-            "::",
-            stringify!(engine_reserved)
-        )
-    );
-    assert_eq!(
-        unsafe {
-            &(*(::std::ptr::null::<napi_extended_error_info>())).engine_error_code as *const _
-                as usize
-        },
-        16usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_extended_error_info),
-            "::",
-            stringify!(engine_error_code)
-        )
-    );
-    assert_eq!(
-        unsafe {
-            &(*(::std::ptr::null::<napi_extended_error_info>())).error_code as *const _ as usize
-        },
-        20usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_extended_error_info),
-            "::",
-            stringify!(error_code)
-        )
-    );
-}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of napi_extended_error_info"][::std::mem::size_of::<napi_extended_error_info>() - 24usize];
+    ["Alignment of napi_extended_error_info"][::std::mem::align_of::<napi_extended_error_info>() - 8usize];
+    ["Offset of field: napi_extended_error_info::error_message"][::std::mem::offset_of!(napi_extended_error_info, error_message) - 0usize];
+    ["Offset of field: napi_extended_error_info::engine_reserved"][::std::mem::offset_of!(napi_extended_error_info, engine_reserved) - 8usize];
+    ["Offset of field: napi_extended_error_info::engine_error_code"][::std::mem::offset_of!(napi_extended_error_info, engine_error_code) - 16usize];
+    ["Offset of field: napi_extended_error_info::error_code"][::std::mem::offset_of!(napi_extended_error_info, error_code) - 20usize];
+};
+impl napi_key_collection_mode {
+    pub const napi_key_include_prototypes: napi_key_collection_mode = napi_key_collection_mode(0);
+}
+impl napi_key_collection_mode {
+    pub const napi_key_own_only: napi_key_collection_mode = napi_key_collection_mode(1);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct napi_key_collection_mode(pub u32);
+impl napi_key_filter {
+    pub const napi_key_all_properties: napi_key_filter = napi_key_filter(0);
+}
+impl napi_key_filter {
+    pub const napi_key_writable: napi_key_filter = napi_key_filter(1);
+}
+impl napi_key_filter {
+    pub const napi_key_enumerable: napi_key_filter = napi_key_filter(2);
+}
+impl napi_key_filter {
+    pub const napi_key_configurable: napi_key_filter = napi_key_filter(4);
+}
+impl napi_key_filter {
+    pub const napi_key_skip_strings: napi_key_filter = napi_key_filter(8);
+}
+impl napi_key_filter {
+    pub const napi_key_skip_symbols: napi_key_filter = napi_key_filter(16);
+}
+impl ::std::ops::BitOr<napi_key_filter> for napi_key_filter {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        napi_key_filter(self.0 | other.0)
+    }
+}
+impl ::std::ops::BitOrAssign for napi_key_filter {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: napi_key_filter) {
+        self.0 |= rhs.0;
+    }
+}
+impl ::std::ops::BitAnd<napi_key_filter> for napi_key_filter {
+    type Output = Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        napi_key_filter(self.0 & other.0)
+    }
+}
+impl ::std::ops::BitAndAssign for napi_key_filter {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: napi_key_filter) {
+        self.0 &= rhs.0;
+    }
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct napi_key_filter(pub u32);
+impl napi_key_conversion {
+    pub const napi_key_keep_numbers: napi_key_conversion = napi_key_conversion(0);
+}
+impl napi_key_conversion {
+    pub const napi_key_numbers_to_strings: napi_key_conversion = napi_key_conversion(1);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct napi_key_conversion(pub u32);
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct napi_type_tag {
+    pub lower: u64,
+    pub upper: u64,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of napi_type_tag"][::std::mem::size_of::<napi_type_tag>() - 16usize];
+    ["Alignment of napi_type_tag"][::std::mem::align_of::<napi_type_tag>() - 8usize];
+    ["Offset of field: napi_type_tag::lower"][::std::mem::offset_of!(napi_type_tag, lower) - 0usize];
+    ["Offset of field: napi_type_tag::upper"][::std::mem::offset_of!(napi_type_tag, upper) - 8usize];
+};
 extern "C" {
     pub fn napi_get_last_error_info(
-        env: napi_env,
+        env: node_api_basic_env,
         result: *mut *const napi_extended_error_info,
     ) -> napi_status;
 }
@@ -681,7 +756,7 @@ extern "C" {
         env: napi_env,
         js_object: napi_value,
         native_object: *mut ::std::os::raw::c_void,
-        finalize_cb: napi_finalize,
+        finalize_cb: node_api_basic_finalize,
         finalize_hint: *mut ::std::os::raw::c_void,
         result: *mut napi_ref,
     ) -> napi_status;
@@ -704,7 +779,7 @@ extern "C" {
     pub fn napi_create_external(
         env: napi_env,
         data: *mut ::std::os::raw::c_void,
-        finalize_cb: napi_finalize,
+        finalize_cb: node_api_basic_finalize,
         finalize_hint: *mut ::std::os::raw::c_void,
         result: *mut napi_value,
     ) -> napi_status;
@@ -816,7 +891,7 @@ extern "C" {
         env: napi_env,
         external_data: *mut ::std::os::raw::c_void,
         byte_length: usize,
-        finalize_cb: napi_finalize,
+        finalize_cb: node_api_basic_finalize,
         finalize_hint: *mut ::std::os::raw::c_void,
         result: *mut napi_value,
     ) -> napi_status;
@@ -876,7 +951,7 @@ extern "C" {
     ) -> napi_status;
 }
 extern "C" {
-    pub fn napi_get_version(env: napi_env, result: *mut u32) -> napi_status;
+    pub fn napi_get_version(env: node_api_basic_env, result: *mut u32) -> napi_status;
 }
 extern "C" {
     pub fn napi_create_promise(
@@ -915,30 +990,152 @@ extern "C" {
 }
 extern "C" {
     pub fn napi_adjust_external_memory(
-        env: napi_env,
+        env: node_api_basic_env,
         change_in_bytes: i64,
         adjusted_value: *mut i64,
     ) -> napi_status;
 }
+#[cfg(feature = "napi5")]
 extern "C" {
     pub fn napi_create_date(env: napi_env, time: f64, result: *mut napi_value) -> napi_status;
 }
+#[cfg(feature = "napi5")]
 extern "C" {
     pub fn napi_is_date(env: napi_env, value: napi_value, is_date: *mut bool) -> napi_status;
 }
+#[cfg(feature = "napi5")]
 extern "C" {
     pub fn napi_get_date_value(env: napi_env, value: napi_value, result: *mut f64) -> napi_status;
 }
+#[cfg(feature = "napi5")]
 extern "C" {
     pub fn napi_add_finalizer(
         env: napi_env,
         js_object: napi_value,
         native_object: *mut ::std::os::raw::c_void,
-        finalize_cb: napi_finalize,
+        finalize_cb: node_api_basic_finalize,
         finalize_hint: *mut ::std::os::raw::c_void,
         result: *mut napi_ref,
     ) -> napi_status;
 }
+#[cfg(feature = "napi6")]
+extern "C" {
+    pub fn napi_create_bigint_int64(
+        env: napi_env,
+        value: i64,
+        result: *mut napi_value,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi6")]
+extern "C" {
+    pub fn napi_create_bigint_uint64(
+        env: napi_env,
+        value: u64,
+        result: *mut napi_value,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi6")]
+extern "C" {
+    pub fn napi_create_bigint_words(
+        env: napi_env,
+        sign_bit: ::std::os::raw::c_int,
+        word_count: usize,
+        words: *const u64,
+        result: *mut napi_value,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi6")]
+extern "C" {
+    pub fn napi_get_value_bigint_int64(
+        env: napi_env,
+        value: napi_value,
+        result: *mut i64,
+        lossless: *mut bool,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi6")]
+extern "C" {
+    pub fn napi_get_value_bigint_uint64(
+        env: napi_env,
+        value: napi_value,
+        result: *mut u64,
+        lossless: *mut bool,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi6")]
+extern "C" {
+    pub fn napi_get_value_bigint_words(
+        env: napi_env,
+        value: napi_value,
+        sign_bit: *mut ::std::os::raw::c_int,
+        word_count: *mut usize,
+        words: *mut u64,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi6")]
+extern "C" {
+    pub fn napi_get_all_property_names(
+        env: napi_env,
+        object: napi_value,
+        key_mode: napi_key_collection_mode,
+        key_filter: napi_key_filter,
+        key_conversion: napi_key_conversion,
+        result: *mut napi_value,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi6")]
+extern "C" {
+    pub fn napi_set_instance_data(
+        env: node_api_basic_env,
+        data: *mut ::std::os::raw::c_void,
+        finalize_cb: napi_finalize,
+        finalize_hint: *mut ::std::os::raw::c_void,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi6")]
+extern "C" {
+    pub fn napi_get_instance_data(
+        env: node_api_basic_env,
+        data: *mut *mut ::std::os::raw::c_void,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi7")]
+extern "C" {
+    pub fn napi_detach_arraybuffer(env: napi_env, arraybuffer: napi_value) -> napi_status;
+}
+#[cfg(feature = "napi7")]
+extern "C" {
+    pub fn napi_is_detached_arraybuffer(
+        env: napi_env,
+        value: napi_value,
+        result: *mut bool,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi8")]
+extern "C" {
+    pub fn napi_type_tag_object(
+        env: napi_env,
+        value: napi_value,
+        type_tag: *const napi_type_tag,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi8")]
+extern "C" {
+    pub fn napi_check_object_type_tag(
+        env: napi_env,
+        value: napi_value,
+        type_tag: *const napi_type_tag,
+        result: *mut bool,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi8")]
+extern "C" {
+    pub fn napi_object_freeze(env: napi_env, object: napi_value) -> napi_status;
+}
+#[cfg(feature = "napi8")]
+extern "C" {
+    pub fn napi_object_seal(env: napi_env, object: napi_value) -> napi_status;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct napi_callback_scope__ {
@@ -957,24 +1154,32 @@ pub struct napi_async_work__ {
     _unused: [u8; 0],
 }
 pub type napi_async_work = *mut napi_async_work__;
+pub type napi_cleanup_hook =
+    ::std::option::Option<unsafe extern "C" fn(arg: *mut ::std::os::raw::c_void)>;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct napi_threadsafe_function__ {
     _unused: [u8; 0],
 }
 pub type napi_threadsafe_function = *mut napi_threadsafe_function__;
-#[repr(u32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum napi_threadsafe_function_release_mode {
-    napi_tsfn_release = 0,
-    napi_tsfn_abort = 1,
-}
-#[repr(u32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum napi_threadsafe_function_call_mode {
-    napi_tsfn_nonblocking = 0,
-    napi_tsfn_blocking = 1,
+impl napi_threadsafe_function_release_mode {
+    pub const napi_tsfn_release: napi_threadsafe_function_release_mode = napi_threadsafe_function_release_mode(0);
+}
+impl napi_threadsafe_function_release_mode {
+    pub const napi_tsfn_abort: napi_threadsafe_function_release_mode = napi_threadsafe_function_release_mode(1);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct napi_threadsafe_function_release_mode(pub u32);
+impl napi_threadsafe_function_call_mode {
+    pub const napi_tsfn_nonblocking: napi_threadsafe_function_call_mode = napi_threadsafe_function_call_mode(0);
 }
+impl napi_threadsafe_function_call_mode {
+    pub const napi_tsfn_blocking: napi_threadsafe_function_call_mode = napi_threadsafe_function_call_mode(1);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct napi_threadsafe_function_call_mode(pub u32);
 pub type napi_async_execute_callback =
     ::std::option::Option<unsafe extern "C" fn(env: napi_env, data: *mut ::std::os::raw::c_void)>;
 pub type napi_async_complete_callback = ::std::option::Option<
@@ -996,59 +1201,24 @@ pub struct napi_node_version {
     pub patch: u32,
     pub release: *const ::std::os::raw::c_char,
 }
-#[test]
-fn bindgen_test_layout_napi_node_version() {
-    assert_eq!(
-        ::std::mem::size_of::<napi_node_version>(),
-        24usize,
-        concat!("Size of: ", stringify!(napi_node_version))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<napi_node_version>(),
-        8usize,
-        concat!("Alignment of ", stringify!(napi_node_version))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_node_version>())).major as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_node_version),
-            "::",
-            stringify!(major)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_node_version>())).minor as *const _ as usize },
-        4usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_node_version),
-            "::",
-            stringify!(minor)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_node_version>())).patch as *const _ as usize },
-        8usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_node_version),
-            "::",
-            stringify!(patch)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_node_version>())).release as *const _ as usize },
-        16usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_node_version),
-            "::",
-            stringify!(release)
-        )
-    );
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of napi_node_version"][::std::mem::size_of::<napi_node_version>() - 24usize];
+    ["Alignment of napi_node_version"][::std::mem::align_of::<napi_node_version>() - 8usize];
+    ["Offset of field: napi_node_version::major"][::std::mem::offset_of!(napi_node_version, major) - 0usize];
+    ["Offset of field: napi_node_version::minor"][::std::mem::offset_of!(napi_node_version, minor) - 4usize];
+    ["Offset of field: napi_node_version::patch"][::std::mem::offset_of!(napi_node_version, patch) - 8usize];
+    ["Offset of field: napi_node_version::release"][::std::mem::offset_of!(napi_node_version, release) - 16usize];
+};
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct napi_async_cleanup_hook_handle__ {
+    _unused: [u8; 0],
 }
+pub type napi_async_cleanup_hook_handle = *mut napi_async_cleanup_hook_handle__;
+pub type napi_async_cleanup_hook = ::std::option::Option<
+    unsafe extern "C" fn(handle: napi_async_cleanup_hook_handle, data: *mut ::std::os::raw::c_void),
+>;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct uv_loop_s {
@@ -1067,89 +1237,18 @@ pub struct napi_module {
     pub nm_priv: *mut ::std::os::raw::c_void,
     pub reserved: [*mut ::std::os::raw::c_void; 4usize],
 }
-#[test]
-fn bindgen_test_layout_napi_module() {
-    assert_eq!(
-        ::std::mem::size_of::<napi_module>(),
-        72usize,
-        concat!("Size of: ", stringify!(napi_module))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<napi_module>(),
-        8usize,
-        concat!("Alignment of ", stringify!(napi_module))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_module>())).nm_version as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_module),
-            "::",
-            stringify!(nm_version)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_module>())).nm_flags as *const _ as usize },
-        4usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_module),
-            "::",
-            stringify!(nm_flags)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_module>())).nm_filename as *const _ as usize },
-        8usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_module),
-            "::",
-            stringify!(nm_filename)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_module>())).nm_register_func as *const _ as usize },
-        16usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_module),
-            "::",
-            stringify!(nm_register_func)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_module>())).nm_modname as *const _ as usize },
-        24usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_module),
-            "::",
-            stringify!(nm_modname)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_module>())).nm_priv as *const _ as usize },
-        32usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_module),
-            "::",
-            stringify!(nm_priv)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<napi_module>())).reserved as *const _ as usize },
-        40usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(napi_module),
-            "::",
-            stringify!(reserved)
-        )
-    );
-}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of napi_module"][::std::mem::size_of::<napi_module>() - 72usize];
+    ["Alignment of napi_module"][::std::mem::align_of::<napi_module>() - 8usize];
+    ["Offset of field: napi_module::nm_version"][::std::mem::offset_of!(napi_module, nm_version) - 0usize];
+    ["Offset of field: napi_module::nm_flags"][::std::mem::offset_of!(napi_module, nm_flags) - 4usize];
+    ["Offset of field: napi_module::nm_filename"][::std::mem::offset_of!(napi_module, nm_filename) - 8usize];
+    ["Offset of field: napi_module::nm_register_func"][::std::mem::offset_of!(napi_module, nm_register_func) - 16usize];
+    ["Offset of field: napi_module::nm_modname"][::std::mem::offset_of!(napi_module, nm_modname) - 24usize];
+    ["Offset of field: napi_module::nm_priv"][::std::mem::offset_of!(napi_module, nm_priv) - 32usize];
+    ["Offset of field: napi_module::reserved"][::std::mem::offset_of!(napi_module, reserved) - 40usize];
+};
 extern "C" {
     pub fn napi_module_register(mod_: *mut napi_module);
 }
@@ -1196,7 +1295,7 @@ extern "C" {
         env: napi_env,
         length: usize,
         data: *mut ::std::os::raw::c_void,
-        finalize_cb: napi_finalize,
+        finalize_cb: node_api_basic_finalize,
         finalize_hint: *mut ::std::os::raw::c_void,
         result: *mut napi_value,
     ) -> napi_status;
@@ -1236,37 +1335,42 @@ extern "C" {
     pub fn napi_delete_async_work(env: napi_env, work: napi_async_work) -> napi_status;
 }
 extern "C" {
-    pub fn napi_queue_async_work(env: napi_env, work: napi_async_work) -> napi_status;
+    pub fn napi_queue_async_work(env: node_api_basic_env, work: napi_async_work) -> napi_status;
 }
 extern "C" {
-    pub fn napi_cancel_async_work(env: napi_env, work: napi_async_work) -> napi_status;
+    pub fn napi_cancel_async_work(env: node_api_basic_env, work: napi_async_work) -> napi_status;
 }
 extern "C" {
     pub fn napi_get_node_version(
-        env: napi_env,
+        env: node_api_basic_env,
         version: *mut *const napi_node_version,
     ) -> napi_status;
 }
+#[cfg(feature = "napi2")]
 extern "C" {
-    pub fn napi_get_uv_event_loop(env: napi_env, loop_: *mut *mut uv_loop_s) -> napi_status;
+    pub fn napi_get_uv_event_loop(env: node_api_basic_env, loop_: *mut *mut uv_loop_s) -> napi_status;
 }
+#[cfg(feature = "napi3")]
 extern "C" {
     pub fn napi_fatal_exception(env: napi_env, err: napi_value) -> napi_status;
 }
+#[cfg(feature = "napi3")]
 extern "C" {
     pub fn napi_add_env_cleanup_hook(
-        env: napi_env,
-        fun: ::std::option::Option<unsafe extern "C" fn(arg: *mut ::std::os::raw::c_void)>,
+        env: node_api_basic_env,
+        fun: napi_cleanup_hook,
         arg: *mut ::std::os::raw::c_void,
     ) -> napi_status;
 }
+#[cfg(feature = "napi3")]
 extern "C" {
     pub fn napi_remove_env_cleanup_hook(
-        env: napi_env,
-        fun: ::std::option::Option<unsafe extern "C" fn(arg: *mut ::std::os::raw::c_void)>,
+        env: node_api_basic_env,
+        fun: napi_cleanup_hook,
         arg: *mut ::std::os::raw::c_void,
     ) -> napi_status;
 }
+#[cfg(feature = "napi3")]
 extern "C" {
     pub fn napi_open_callback_scope(
         env: napi_env,
@@ -1275,9 +1379,11 @@ extern "C" {
         result: *mut napi_callback_scope,
     ) -> napi_status;
 }
+#[cfg(feature = "napi3")]
 extern "C" {
     pub fn napi_close_callback_scope(env: napi_env, scope: napi_callback_scope) -> napi_status;
 }
+#[cfg(feature = "napi4")]
 extern "C" {
     pub fn napi_create_threadsafe_function(
         env: napi_env,
@@ -1293,12 +1399,14 @@ extern "C" {
         result: *mut napi_threadsafe_function,
     ) -> napi_status;
 }
+#[cfg(feature = "napi4")]
 extern "C" {
     pub fn napi_get_threadsafe_function_context(
         func: napi_threadsafe_function,
         result: *mut *mut ::std::os::raw::c_void,
     ) -> napi_status;
 }
+#[cfg(feature = "napi4")]
 extern "C" {
     pub fn napi_call_threadsafe_function(
         func: napi_threadsafe_function,
@@ -1306,24 +1414,43 @@ extern "C" {
         is_blocking: napi_threadsafe_function_call_mode,
     ) -> napi_status;
 }
+#[cfg(feature = "napi4")]
 extern "C" {
     pub fn napi_acquire_threadsafe_function(func: napi_threadsafe_function) -> napi_status;
 }
+#[cfg(feature = "napi4")]
 extern "C" {
     pub fn napi_release_threadsafe_function(
         func: napi_threadsafe_function,
         mode: napi_threadsafe_function_release_mode,
     ) -> napi_status;
 }
+#[cfg(feature = "napi4")]
 extern "C" {
     pub fn napi_unref_threadsafe_function(
-        env: napi_env,
+        env: node_api_basic_env,
         func: napi_threadsafe_function,
     ) -> napi_status;
 }
+#[cfg(feature = "napi4")]
 extern "C" {
     pub fn napi_ref_threadsafe_function(
-        env: napi_env,
+        env: node_api_basic_env,
         func: napi_threadsafe_function,
     ) -> napi_status;
 }
+#[cfg(feature = "napi8")]
+extern "C" {
+    pub fn napi_add_async_cleanup_hook(
+        env: node_api_basic_env,
+        hook: napi_async_cleanup_hook,
+        arg: *mut ::std::os::raw::c_void,
+        remove_handle: *mut napi_async_cleanup_hook_handle,
+    ) -> napi_status;
+}
+#[cfg(feature = "napi8")]
+extern "C" {
+    pub fn napi_remove_async_cleanup_hook(
+        remove_handle: napi_async_cleanup_hook_handle,
+    ) -> napi_status;
+}
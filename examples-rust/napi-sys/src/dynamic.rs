@@ -0,0 +1,192 @@
+//! Runtime resolution of N-API functions, enabled by the `dynamic-loading`
+//! feature.
+//!
+//! Instead of relying on the host process exporting every `napi_*` symbol at
+//! link time, each function is looked up in a [`Host`] when it is loaded.
+//! If nothing was loaded explicitly, the current process is used on first
+//! call. Calling a function the host doesn't export aborts the process with
+//! an error naming it, so check [`Host::missing_symbols`] up front when the
+//! host is not known to support every enabled N-API version.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::OnceLock;
+
+use libloading::Library;
+
+const SYMBOLS: &[&str] = &include!(concat!(env!("OUT_DIR"), "/symbols.rs"));
+
+static HOST: OnceLock<Host> = OnceLock::new();
+
+macro_rules! dynamic_functions {
+    ($(pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        $(
+            #[allow(clippy::missing_safety_doc)]
+            pub unsafe extern "C" fn $name($($arg: $ty),*) $(-> $ret)? {
+                static SYMBOL: $crate::dynamic::Symbol =
+                    $crate::dynamic::Symbol::new(stringify!($name));
+
+                let function = ::std::mem::transmute::<
+                    *mut ::std::os::raw::c_void,
+                    unsafe extern "C" fn($($ty),*) $(-> $ret)?,
+                >(SYMBOL.address());
+                function($($arg),*)
+            }
+        )*
+    };
+}
+
+/// The library N-API functions are resolved from.
+pub struct Host {
+    _library: Library,
+    source: String,
+    symbols: HashMap<&'static str, usize>,
+    missing: Vec<&'static str>,
+}
+
+impl Host {
+    fn new(library: Library, source: String) -> Host {
+        let mut symbols = HashMap::new();
+        let mut missing = Vec::new();
+
+        for &name in SYMBOLS {
+            match unsafe { library.get::<*mut c_void>(name.as_bytes()) } {
+                Ok(symbol) => {
+                    symbols.insert(name, *symbol as usize);
+                }
+                Err(_) => missing.push(name),
+            }
+        }
+
+        Host {
+            _library: library,
+            source,
+            symbols,
+            missing,
+        }
+    }
+
+    /// Names of the N-API functions the host doesn't export.
+    pub fn missing_symbols(&self) -> &[&'static str] {
+        &self.missing
+    }
+
+    pub fn has_symbol(&self, name: &str) -> bool {
+        self.symbols.contains_key(name)
+    }
+}
+
+impl fmt::Debug for Host {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Host")
+            .field("source", &self.source)
+            .field("missing", &self.missing)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    AlreadyLoaded,
+    Library(libloading::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::AlreadyLoaded => write!(formatter, "N-API host is already loaded"),
+            LoadError::Library(error) => write!(formatter, "failed to load N-API host: {}", error),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::AlreadyLoaded => None,
+            LoadError::Library(error) => Some(error),
+        }
+    }
+}
+
+fn install(library: Library, source: String) -> Result<&'static Host, LoadError> {
+    HOST.set(Host::new(library, source))
+        .map_err(|_| LoadError::AlreadyLoaded)?;
+    Ok(HOST.get().unwrap())
+}
+
+fn this() -> Result<Library, libloading::Error> {
+    #[cfg(unix)]
+    let library = libloading::os::unix::Library::this();
+
+    #[cfg(windows)]
+    let library = libloading::os::windows::Library::this()?;
+
+    Ok(library.into())
+}
+
+/// Resolves N-API functions from the current process.
+pub fn load_host() -> Result<&'static Host, LoadError> {
+    let library = this().map_err(LoadError::Library)?;
+    install(library, String::from("the current process"))
+}
+
+/// Resolves N-API functions from the shared library at `path`.
+pub fn load<P: AsRef<OsStr>>(path: P) -> Result<&'static Host, LoadError> {
+    let path = path.as_ref();
+    let library = unsafe { Library::new(path) }.map_err(LoadError::Library)?;
+    install(library, path.to_string_lossy().into_owned())
+}
+
+/// The loaded host, if any.
+pub fn host() -> Option<&'static Host> {
+    HOST.get()
+}
+
+#[doc(hidden)]
+pub struct Symbol {
+    name: &'static str,
+    address: AtomicPtr<c_void>,
+}
+
+impl Symbol {
+    pub const fn new(name: &'static str) -> Symbol {
+        Symbol {
+            name,
+            address: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    pub fn address(&self) -> *mut c_void {
+        let address = self.address.load(Ordering::Relaxed);
+        if !address.is_null() {
+            return address;
+        }
+
+        let host = match HOST.get() {
+            Some(host) => host,
+            None => match load_host() {
+                Ok(host) => host,
+                Err(LoadError::AlreadyLoaded) => HOST.get().unwrap(),
+                Err(error) => panic!("cannot call `{}`: {}", self.name, error),
+            },
+        };
+
+        let address = match host.symbols.get(self.name) {
+            Some(&address) => address as *mut c_void,
+            None => panic!(
+                "N-API function `{}` is not exported by {}",
+                self.name, host.source
+            ),
+        };
+
+        self.address.store(address, Ordering::Relaxed);
+        address
+    }
+}
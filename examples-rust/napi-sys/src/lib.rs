@@ -2,4 +2,12 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
+#[cfg(feature = "dynamic-loading")]
+#[macro_use]
+pub mod dynamic;
+
+#[cfg(any(feature = "bindgen", feature = "dynamic-loading"))]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+#[cfg(not(any(feature = "bindgen", feature = "dynamic-loading")))]
 include!("bindings.rs");
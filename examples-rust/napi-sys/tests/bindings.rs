@@ -0,0 +1,73 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+#[path = "../build/versions.rs"]
+mod versions;
+
+/// The newest version with a `napiN` feature, which the bindings are
+/// generated for.
+const NEWEST_VERSION: u32 = 8;
+
+const BINDINGS: &str = include_str!("../src/bindings.rs");
+
+const HEADERS: &[&str] = &[
+    include_str!("../include/js_native_api.h"),
+    include_str!("../include/js_native_api_types.h"),
+    include_str!("../include/node_api.h"),
+    include_str!("../include/node_api_types.h"),
+];
+
+fn header_versions() -> BTreeMap<String, u32> {
+    let mut versions = BTreeMap::new();
+    for header in HEADERS {
+        versions::function_versions(header, &mut versions);
+    }
+    versions
+}
+
+#[test]
+fn bindings_declare_every_header_function() {
+    let declared: BTreeSet<_> = BINDINGS
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("pub fn "))
+        .map(|line| line[..line.find('(').unwrap()].to_string())
+        .collect();
+    let expected: BTreeSet<_> = header_versions()
+        .into_iter()
+        .filter(|(_, version)| *version <= NEWEST_VERSION)
+        .map(|(name, _)| name)
+        .collect();
+
+    assert_eq!(declared, expected);
+}
+
+#[test]
+fn bindings_gate_functions_by_napi_version() {
+    let ungated: String = BINDINGS
+        .lines()
+        .filter(|line| !line.starts_with("#[cfg(feature = \"napi"))
+        .map(|line| format!("{}\n", line))
+        .collect();
+
+    assert_eq!(
+        versions::gate_functions(&ungated, &header_versions()),
+        BINDINGS
+    );
+}
+
+#[cfg(feature = "bindgen")]
+#[test]
+fn bindings_are_up_to_date() {
+    let normalize = |bindings: &str| -> String {
+        bindings
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("/*"))
+            .flat_map(|line| line.split_whitespace())
+            .collect()
+    };
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+    assert!(
+        normalize(generated) == normalize(BINDINGS),
+        "src/bindings.rs is out of date, rebuild with NAPI_SYS_UPDATE_BINDINGS set to regenerate it"
+    );
+}
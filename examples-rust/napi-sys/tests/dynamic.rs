@@ -0,0 +1,28 @@
+#![cfg(feature = "dynamic-loading")]
+
+use std::ptr;
+
+use napi_sys::dynamic::{self, LoadError};
+use napi_sys::*;
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_version(_env: napi_env, result: *mut u32) -> napi_status {
+    *result = 42;
+    napi_status::napi_ok
+}
+
+#[test]
+fn resolves_functions_from_host() {
+    let host = dynamic::load_host().unwrap();
+    assert!(host.has_symbol("napi_get_version"));
+    assert!(host.missing_symbols().contains(&"napi_create_object"));
+    assert!(matches!(
+        dynamic::load_host(),
+        Err(LoadError::AlreadyLoaded)
+    ));
+
+    let mut version = 0;
+    let status = unsafe { napi_sys::napi_get_version(ptr::null_mut(), &mut version) };
+    assert_eq!(status, napi_status::napi_ok);
+    assert_eq!(version, 42);
+}
@@ -1,5 +1,7 @@
 #include <node_api.h>
 
+#ifdef FUNCTIONS_MAP
+
 #define V(name, func)                                                         \
   extern napi_value func(napi_env env, napi_callback_info info);
 
@@ -39,3 +41,13 @@ napi_value init_bindings(napi_env env, napi_value exports) {
 }
 
 NAPI_MODULE(NODE_GYP_MODULE_NAME, init_bindings)
+
+#else
+
+// Without FUNCTIONS_MAP the exports are defined on the Rust side by a function
+// annotated with #[napi::module_init].
+extern napi_value napi_rs_init(napi_env env, napi_value exports);
+
+NAPI_MODULE(NODE_GYP_MODULE_NAME, napi_rs_init)
+
+#endif
@@ -0,0 +1,111 @@
+#[cfg(feature = "napi3")]
+use std::marker::PhantomData;
+use std::ptr;
+
+use crate::env::Env;
+use crate::reference::Ref;
+use crate::result::Result;
+use crate::sys;
+use crate::value::{Object, String, Value};
+
+/// An `async_hooks` resource for calling into JavaScript later from native
+/// code, e.g. from a completion callback, with the async context the
+/// resource was created in, so that `AsyncLocalStorage` and domains keep
+/// working. The resource is destroyed when this is dropped.
+#[derive(Debug)]
+pub struct AsyncContext {
+    context: sys::napi_async_context,
+    resource: Ref<Object>,
+    env: Env,
+}
+
+impl AsyncContext {
+    /// Creates a context for an empty resource object. `resource_name` is
+    /// the type reported to `async_hooks`.
+    pub fn new(env: Env, resource_name: &str) -> Result<AsyncContext> {
+        AsyncContext::with_resource(&Object::new(env)?, resource_name)
+    }
+
+    pub fn with_resource(resource: &Object, resource_name: &str) -> Result<AsyncContext> {
+        let env = resource.env();
+        let resource_name = String::from_str(env, resource_name)?;
+        let reference = Ref::new(resource, 1)?;
+        let mut context = ptr::null_mut();
+
+        env.handle_status(unsafe {
+            sys::napi_async_init(
+                env.as_sys_env(),
+                resource.as_sys_value(),
+                resource_name.as_sys_value(),
+                &mut context,
+            )
+        })?;
+
+        Ok(AsyncContext {
+            context,
+            resource: reference,
+            env,
+        })
+    }
+
+    pub fn as_sys_context(&self) -> sys::napi_async_context {
+        self.context
+    }
+
+    pub fn resource(&self) -> Result<Object> {
+        Ok(self.resource.value()?.unwrap())
+    }
+}
+
+impl Drop for AsyncContext {
+    fn drop(&mut self) {
+        unsafe {
+            sys::napi_async_destroy(self.env.as_sys_env(), self.context);
+        }
+    }
+}
+
+/// Runs a batch of calls into JavaScript in an async context, as if they
+/// were made by one `Function::make_callback`: the microtask queue and
+/// `process.nextTick` callbacks run when the scope is closed on drop rather
+/// than after every call. Scopes must be closed in the reverse order of
+/// opening them.
+#[cfg(feature = "napi3")]
+#[derive(Debug)]
+pub struct CallbackScope<'a> {
+    scope: sys::napi_callback_scope,
+    env: Env,
+    marker: PhantomData<&'a AsyncContext>,
+}
+
+#[cfg(feature = "napi3")]
+impl<'a> CallbackScope<'a> {
+    pub fn new(context: &'a AsyncContext) -> Result<CallbackScope<'a>> {
+        let env = context.env;
+        let mut scope = ptr::null_mut();
+
+        env.handle_status(unsafe {
+            sys::napi_open_callback_scope(
+                env.as_sys_env(),
+                context.resource()?.as_sys_value(),
+                context.context,
+                &mut scope,
+            )
+        })?;
+
+        Ok(CallbackScope {
+            scope,
+            env,
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "napi3")]
+impl<'a> Drop for CallbackScope<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::napi_close_callback_scope(self.env.as_sys_env(), self.scope);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::cell::Cell;
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+use std::rc::Rc;
+
+use crate::env::Env;
+use crate::result::Result;
+use crate::sys;
+
+struct CleanupHook {
+    hook: Cell<Option<Box<dyn FnOnce()>>>,
+}
+
+/// Keeps a hook registered with `Env::add_cleanup_hook`. Dropping the handle
+/// before the environment shuts down removes the hook.
+#[must_use = "dropping the handle removes the cleanup hook"]
+pub struct CleanupHandle {
+    env: Env,
+    hook: Rc<CleanupHook>,
+}
+
+unsafe extern "C" fn run_cleanup_hook(arg: *mut c_void) {
+    let hook = Rc::from_raw(arg as *const CleanupHook);
+    if let Some(hook) = hook.hook.take() {
+        hook();
+    }
+}
+
+impl Env {
+    /// Registers `hook` to run when the environment shuts down, e.g. when
+    /// Node exits or a worker thread terminates. Hooks run in reverse order
+    /// of registration.
+    pub fn add_cleanup_hook<F>(self, hook: F) -> Result<CleanupHandle>
+    where
+        F: FnOnce() + 'static,
+    {
+        let hook = Rc::new(CleanupHook {
+            hook: Cell::new(Some(Box::new(hook))),
+        });
+
+        let arg = Rc::into_raw(hook.clone()) as *mut c_void;
+        let status = unsafe {
+            sys::napi_add_env_cleanup_hook(self.as_sys_env(), Some(run_cleanup_hook), arg)
+        };
+
+        if let Err(error) = self.handle_status(status) {
+            drop(unsafe { Rc::from_raw(arg as *const CleanupHook) });
+            return Err(error);
+        }
+
+        Ok(CleanupHandle { env: self, hook })
+    }
+}
+
+impl CleanupHandle {
+    /// Leaves the hook registered until the environment shuts down.
+    pub fn keep(self) {
+        let hook = unsafe { ptr::read(&self.hook) };
+        mem::forget(self);
+        drop(hook);
+    }
+}
+
+impl Drop for CleanupHandle {
+    fn drop(&mut self) {
+        let hook = match self.hook.hook.take() {
+            Some(hook) => hook,
+            None => return,
+        };
+
+        let arg = Rc::as_ptr(&self.hook) as *mut c_void;
+        unsafe {
+            sys::napi_remove_env_cleanup_hook(self.env.as_sys_env(), Some(run_cleanup_hook), arg);
+            drop(Rc::from_raw(arg as *const CleanupHook));
+        }
+        drop(hook);
+    }
+}
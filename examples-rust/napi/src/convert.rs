@@ -0,0 +1,233 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+
+use crate::env::Env;
+use crate::result::Result;
+use crate::value::{Any, Array, Boolean, Map, Number, Set, String, Undefined, Value, ValueType};
+
+pub trait FromJs: Sized {
+    fn from_js(value: Any) -> Result<Self>;
+}
+
+pub trait IntoJs {
+    fn into_js(self, env: Env) -> Result<Any>;
+}
+
+impl<T: Value> FromJs for T {
+    fn from_js(value: Any) -> Result<T> {
+        T::from_sys_checked(value.env(), value.as_sys_value())
+    }
+}
+
+impl<T: Value> IntoJs for T {
+    fn into_js(self, _env: Env) -> Result<Any> {
+        Ok(self.as_napi_any())
+    }
+}
+
+macro_rules! number_conversions {
+    ($ty:ty, $from_fn:ident, $to_fn:ident) => {
+        impl FromJs for $ty {
+            fn from_js(value: Any) -> Result<$ty> {
+                value.as_number()?.$to_fn()
+            }
+        }
+
+        impl IntoJs for $ty {
+            fn into_js(self, env: Env) -> Result<Any> {
+                Number::$from_fn(env, self).map(|number| number.as_napi_any())
+            }
+        }
+    };
+}
+
+number_conversions!(i32, from_i32, to_i32);
+number_conversions!(u32, from_u32, to_u32);
+number_conversions!(i64, from_i64, to_i64);
+number_conversions!(f64, from_f64, to_f64);
+
+impl FromJs for bool {
+    fn from_js(value: Any) -> Result<bool> {
+        value.as_boolean()?.to_bool()
+    }
+}
+
+impl IntoJs for bool {
+    fn into_js(self, env: Env) -> Result<Any> {
+        let value = if self {
+            Boolean::truth(env)?
+        } else {
+            Boolean::lie(env)?
+        };
+
+        Ok(value.as_napi_any())
+    }
+}
+
+impl FromJs for std::string::String {
+    fn from_js(value: Any) -> Result<std::string::String> {
+        value.as_string()?.to_string()
+    }
+}
+
+impl IntoJs for std::string::String {
+    fn into_js(self, env: Env) -> Result<Any> {
+        self.as_str().into_js(env)
+    }
+}
+
+impl IntoJs for &str {
+    fn into_js(self, env: Env) -> Result<Any> {
+        String::from_str(env, self).map(|string| string.as_napi_any())
+    }
+}
+
+impl IntoJs for () {
+    fn into_js(self, env: Env) -> Result<Any> {
+        Undefined::new(env).map(|undefined| undefined.as_napi_any())
+    }
+}
+
+impl<T: FromJs> FromJs for Option<T> {
+    fn from_js(value: Any) -> Result<Option<T>> {
+        if value.value_type()? == ValueType::Undefined {
+            Ok(None)
+        } else {
+            T::from_js(value).map(Some)
+        }
+    }
+}
+
+impl<T: IntoJs> IntoJs for Option<T> {
+    fn into_js(self, env: Env) -> Result<Any> {
+        match self {
+            Some(value) => value.into_js(env),
+            None => ().into_js(env),
+        }
+    }
+}
+
+impl<T: FromJs> FromJs for Vec<T> {
+    fn from_js(value: Any) -> Result<Vec<T>> {
+        let env = value.env();
+        let array = value.as_array()?;
+
+        (0..array.len()?)
+            .map(|index| {
+                array
+                    .get(index)
+                    .and_then(T::from_js)
+                    .map_err(|error| error.in_property(env, &index.to_string()))
+            })
+            .collect()
+    }
+}
+
+impl<T: IntoJs> IntoJs for Vec<T> {
+    fn into_js(self, env: Env) -> Result<Any> {
+        let array = Array::with_len(env, self.len())?;
+
+        for (index, element) in self.into_iter().enumerate() {
+            array.set(index as u32, &element.into_js(env)?)?;
+        }
+
+        Ok(array.as_napi_any())
+    }
+}
+
+fn map_from_js<K: FromJs, V: FromJs, C: FromIterator<(K, V)>>(value: Any) -> Result<C> {
+    Map::from_js(value)?
+        .entries()?
+        .into_iter()
+        .map(|(key, value)| Ok((K::from_js(key)?, V::from_js(value)?)))
+        .collect()
+}
+
+fn map_into_js<K: IntoJs, V: IntoJs>(
+    env: Env,
+    entries: impl Iterator<Item = (K, V)>,
+) -> Result<Any> {
+    let map = Map::new(env)?;
+
+    for (key, value) in entries {
+        map.set(&key.into_js(env)?, &value.into_js(env)?)?;
+    }
+
+    Ok(map.as_napi_any())
+}
+
+fn set_from_js<T: FromJs, C: FromIterator<T>>(value: Any) -> Result<C> {
+    Set::from_js(value)?
+        .values()?
+        .into_iter()
+        .map(T::from_js)
+        .collect()
+}
+
+fn set_into_js<T: IntoJs>(env: Env, values: impl Iterator<Item = T>) -> Result<Any> {
+    let set = Set::new(env)?;
+
+    for value in values {
+        set.add(&value.into_js(env)?)?;
+    }
+
+    Ok(set.as_napi_any())
+}
+
+impl<K, V, S> FromJs for HashMap<K, V, S>
+where
+    K: FromJs + Eq + Hash,
+    V: FromJs,
+    S: BuildHasher + Default,
+{
+    fn from_js(value: Any) -> Result<HashMap<K, V, S>> {
+        map_from_js(value)
+    }
+}
+
+impl<K: IntoJs, V: IntoJs, S> IntoJs for HashMap<K, V, S> {
+    fn into_js(self, env: Env) -> Result<Any> {
+        map_into_js(env, self.into_iter())
+    }
+}
+
+impl<K: FromJs + Ord, V: FromJs> FromJs for BTreeMap<K, V> {
+    fn from_js(value: Any) -> Result<BTreeMap<K, V>> {
+        map_from_js(value)
+    }
+}
+
+impl<K: IntoJs, V: IntoJs> IntoJs for BTreeMap<K, V> {
+    fn into_js(self, env: Env) -> Result<Any> {
+        map_into_js(env, self.into_iter())
+    }
+}
+
+impl<T, S> FromJs for HashSet<T, S>
+where
+    T: FromJs + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_js(value: Any) -> Result<HashSet<T, S>> {
+        set_from_js(value)
+    }
+}
+
+impl<T: IntoJs, S> IntoJs for HashSet<T, S> {
+    fn into_js(self, env: Env) -> Result<Any> {
+        set_into_js(env, self.into_iter())
+    }
+}
+
+impl<T: FromJs + Ord> FromJs for BTreeSet<T> {
+    fn from_js(value: Any) -> Result<BTreeSet<T>> {
+        set_from_js(value)
+    }
+}
+
+impl<T: IntoJs> IntoJs for BTreeSet<T> {
+    fn into_js(self, env: Env) -> Result<Any> {
+        set_into_js(env, self.into_iter())
+    }
+}
@@ -1,6 +1,7 @@
 use std::ffi::CStr;
 use std::ptr;
 
+use crate::exception::JsException;
 use crate::result::{Error, ErrorKind, Result};
 use crate::sys;
 
@@ -20,28 +21,45 @@ impl Env {
         self.env
     }
 
+    pub fn napi_version(self) -> Result<u32> {
+        let mut version = 0;
+        self.handle_status(unsafe { sys::napi_get_version(self.env, &mut version) })?;
+        Ok(version)
+    }
+
     pub fn handle_status(self, status: sys::napi_status) -> Result<()> {
         if status == sys::napi_status::napi_ok {
             return Ok(());
         }
 
-        let error_message = unsafe {
+        let (error_message, engine_error_code) = unsafe {
             let mut extended_error_info = ptr::null();
             sys::napi_get_last_error_info(self.env, &mut extended_error_info);
 
             let raw_error_message = (*extended_error_info).error_message;
-            if raw_error_message.is_null() {
+            let error_message = if raw_error_message.is_null() {
                 None
             } else {
                 let c_string = CStr::from_ptr(raw_error_message);
                 Some(c_string.to_string_lossy().into_owned())
-            }
+            };
+
+            (error_message, (*extended_error_info).engine_error_code)
+        };
+
+        let exception = self.get_pending_exception_for_status(status);
+        let message = match exception {
+            Some(exception) => Some(JsException::new(self, exception).to_string()),
+            None => error_message,
         };
 
         Err(Error {
             kind: ErrorKind::from_napi_status(status),
-            message: error_message,
-            exception: self.get_pending_exception_for_status(status),
+            message,
+            exception,
+            property_path: Vec::new(),
+            source: None,
+            engine_error_code,
         })
     }
 
@@ -0,0 +1,137 @@
+use std::ffi::CString;
+use std::fmt;
+use std::ptr;
+
+use crate::env::Env;
+use crate::result::{Error, ErrorKind, Result};
+use crate::sys;
+use crate::value::{Any, Value, ValueType};
+
+#[derive(Clone, Copy, Debug)]
+pub struct JsException {
+    value: sys::napi_value,
+    env: Env,
+}
+
+impl JsException {
+    pub fn new(env: Env, value: sys::napi_value) -> JsException {
+        JsException { value, env }
+    }
+
+    pub fn value(&self) -> Any {
+        Any::with_value(self.env, self.value)
+    }
+
+    pub fn name(&self) -> Option<String> {
+        self.string_property("name")
+    }
+
+    pub fn message(&self) -> Option<String> {
+        self.string_property("message")
+    }
+
+    pub fn code(&self) -> Option<String> {
+        self.string_property("code")
+    }
+
+    pub fn stack(&self) -> Option<String> {
+        self.string_property("stack")
+    }
+
+    pub fn throw(&self) -> Result<()> {
+        self.env
+            .handle_status(unsafe { sys::napi_throw(self.env.as_sys_env(), self.value) })
+    }
+
+    pub fn into_error(self) -> Error {
+        Error {
+            kind: ErrorKind::ApplicationError,
+            message: Some(self.to_string()),
+            exception: Some(self.value),
+            property_path: Vec::new(),
+            source: None,
+            engine_error_code: 0,
+        }
+    }
+
+    fn string_property(&self, name: &str) -> Option<String> {
+        match self.value().value_type().ok()? {
+            ValueType::Object | ValueType::Function => {}
+            _ => return None,
+        }
+
+        // Getters on the exception may throw again; read the property without going
+        // through `Env::handle_status` so that doesn't recurse into another `JsException`.
+        let name = CString::new(name).ok()?;
+        let mut result = ptr::null_mut();
+        let status = unsafe {
+            sys::napi_get_named_property(
+                self.env.as_sys_env(),
+                self.value,
+                name.as_ptr(),
+                &mut result,
+            )
+        };
+
+        if status != sys::napi_status::napi_ok {
+            unsafe {
+                sys::napi_get_and_clear_last_exception(self.env.as_sys_env(), &mut result);
+            }
+            return None;
+        }
+
+        primitive_to_string(Any::with_value(self.env, result))
+    }
+}
+
+fn primitive_to_string(value: Any) -> Option<String> {
+    match value.value_type().ok()? {
+        ValueType::String | ValueType::Number | ValueType::Boolean | ValueType::BigInt => {
+            value.to_napi_string().ok()?.to_string().ok()
+        }
+        _ => None,
+    }
+}
+
+impl fmt::Display for JsException {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.name();
+        let message = self.message();
+
+        match (&name, &message) {
+            (None, None) => match primitive_to_string(self.value()) {
+                Some(value) => formatter.write_str(&value)?,
+                None => formatter.write_str("unknown exception")?,
+            },
+            (name, message) => {
+                formatter.write_str(name.as_deref().unwrap_or("Error"))?;
+                if let Some(code) = self.code() {
+                    write!(formatter, " [{}]", code)?;
+                }
+                if let Some(message) = message {
+                    write!(formatter, ": {}", message)?;
+                }
+            }
+        }
+
+        if formatter.alternate() {
+            if let Some(stack) = self.stack() {
+                for line in stack
+                    .lines()
+                    .skip_while(|line| !line.trim_start().starts_with("at "))
+                {
+                    write!(formatter, "\n{}", line)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error {
+    pub fn js_exception(&self, env: Env) -> Option<JsException> {
+        self.exception
+            .map(|exception| JsException::new(env, exception))
+    }
+}
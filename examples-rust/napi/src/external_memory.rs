@@ -0,0 +1,132 @@
+use std::mem;
+
+use crate::env::Env;
+use crate::result::Result;
+use crate::sys;
+
+/// Number of bytes of native memory a value keeps alive, reported to the
+/// JavaScript engine so that it can take them into account when scheduling
+/// garbage collection.
+pub trait SizeHint {
+    fn size_hint(&self) -> usize;
+}
+
+impl<T> SizeHint for Vec<T> {
+    fn size_hint(&self) -> usize {
+        self.capacity() * mem::size_of::<T>()
+    }
+}
+
+impl<T> SizeHint for Box<[T]> {
+    fn size_hint(&self) -> usize {
+        self.len() * mem::size_of::<T>()
+    }
+}
+
+impl SizeHint for std::string::String {
+    fn size_hint(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: SizeHint> SizeHint for Box<T> {
+    fn size_hint(&self) -> usize {
+        mem::size_of::<T>() + (**self).size_hint()
+    }
+}
+
+impl<T: SizeHint> SizeHint for Option<T> {
+    fn size_hint(&self) -> usize {
+        self.as_ref().map_or(0, SizeHint::size_hint)
+    }
+}
+
+macro_rules! impl_size_hint_zero {
+    ($($ty:ty),*) => {
+        $(
+            impl SizeHint for $ty {
+                fn size_hint(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_size_hint_zero!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+/// Reports `bytes` of native memory to the engine for as long as it is
+/// alive.
+#[derive(Debug)]
+pub struct ExternalMemory {
+    env: Env,
+    bytes: usize,
+}
+
+impl ExternalMemory {
+    pub fn new(env: Env, bytes: usize) -> Result<ExternalMemory> {
+        if bytes != 0 {
+            env.adjust_external_memory(bytes as i64)?;
+        }
+        Ok(ExternalMemory { env, bytes })
+    }
+
+    pub fn for_value<T: SizeHint>(env: Env, value: &T) -> Result<ExternalMemory> {
+        ExternalMemory::new(env, value.size_hint())
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    pub fn resize(&mut self, bytes: usize) -> Result<()> {
+        self.env
+            .adjust_external_memory(bytes as i64 - self.bytes as i64)?;
+        self.bytes = bytes;
+        Ok(())
+    }
+}
+
+impl Drop for ExternalMemory {
+    fn drop(&mut self) {
+        if self.bytes != 0 {
+            let _ = self.env.adjust_external_memory(-(self.bytes as i64));
+        }
+    }
+}
+
+impl Env {
+    /// Adjusts the amount of native memory kept alive by JavaScript objects
+    /// and returns the new total.
+    pub fn adjust_external_memory(self, change_in_bytes: i64) -> Result<i64> {
+        let mut adjusted_value = 0;
+
+        self.handle_status(unsafe {
+            sys::napi_adjust_external_memory(
+                self.as_sys_env(),
+                change_in_bytes,
+                &mut adjusted_value,
+            )
+        })?;
+
+        Ok(adjusted_value)
+    }
+}
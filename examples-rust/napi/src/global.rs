@@ -0,0 +1,141 @@
+#[cfg(feature = "napi6")]
+use std::cell::RefCell;
+#[cfg(feature = "napi6")]
+use std::collections::HashMap;
+use std::ptr;
+#[cfg(feature = "napi6")]
+use std::rc::Rc;
+
+use crate::env::Env;
+#[cfg(feature = "napi6")]
+use crate::reference::Ref;
+use crate::result::Result;
+use crate::sys;
+use crate::value::{Any, Function, Object, String, Value, ValueType};
+
+/// Built-ins already looked up in this environment, kept in its instance
+/// data.
+#[cfg(feature = "napi6")]
+#[derive(Default)]
+struct Builtins(RefCell<HashMap<&'static str, Ref<Any>>>);
+
+macro_rules! builtins {
+    ($($(#[$attr:meta])* $vis:vis $method:ident: $ty:ident = $name:literal;)*) => {
+        impl Env {
+            $(
+                $(#[$attr])*
+                $vis fn $method(self) -> Result<$ty> {
+                    self.builtin($name)
+                }
+            )*
+        }
+    };
+}
+
+builtins! {
+    pub json: Object = "JSON";
+    pub reflect: Object = "Reflect";
+    pub object_constructor: Function = "Object";
+    pub map_constructor: Function = "Map";
+    pub set_constructor: Function = "Set";
+    pub promise_constructor: Function = "Promise";
+    pub symbol_constructor: Function = "Symbol";
+    pub error_constructor: Function = "Error";
+
+    pub(crate) map_get: Function = "Map.prototype.get";
+    pub(crate) map_set: Function = "Map.prototype.set";
+    pub(crate) map_has: Function = "Map.prototype.has";
+    pub(crate) map_delete: Function = "Map.prototype.delete";
+    pub(crate) map_clear: Function = "Map.prototype.clear";
+    pub(crate) map_size: Function = "Map.prototype.size";
+    pub(crate) map_entries: Function = "Map.prototype.entries";
+    pub(crate) set_add: Function = "Set.prototype.add";
+    pub(crate) set_has: Function = "Set.prototype.has";
+    pub(crate) set_delete: Function = "Set.prototype.delete";
+    pub(crate) set_clear: Function = "Set.prototype.clear";
+    pub(crate) set_size: Function = "Set.prototype.size";
+    pub(crate) set_values: Function = "Set.prototype.values";
+}
+
+impl Env {
+    pub fn global(self) -> Result<Object> {
+        let mut value = ptr::null_mut();
+        self.handle_status(unsafe { sys::napi_get_global(self.as_sys_env(), &mut value) })?;
+        Object::from_sys_checked(self, value)
+    }
+
+    /// Looks up the global `name`, or the property at a dotted path like
+    /// `Map.prototype.get`, the first time it is requested in this
+    /// environment, so later calls are unaffected by user code reassigning
+    /// it. Without N-API 6 instance data the lookup is repeated every time.
+    fn builtin<T: Value>(self, name: &'static str) -> Result<T> {
+        #[cfg(feature = "napi6")]
+        if let Some(value) = self.cached_builtin(name)? {
+            return T::from_sys_checked(self, value.as_sys_value());
+        }
+
+        let value = self.lookup_builtin(name)?;
+        let builtin = T::from_sys_checked(self, value.as_sys_value())?;
+
+        #[cfg(feature = "napi6")]
+        self.builtins()?
+            .0
+            .borrow_mut()
+            .insert(name, Ref::new(&value, 1)?);
+
+        Ok(builtin)
+    }
+
+    /// The last property of a dotted path is read from its descriptor, so
+    /// that accessors like `Map.prototype.size` yield their getter.
+    fn lookup_builtin(self, path: &str) -> Result<Any> {
+        let mut names = path.split('.');
+        let mut value = self.global()?.get_named_property(names.next().unwrap())?;
+        let mut names = names.peekable();
+
+        while let Some(name) = names.next() {
+            let object = value.as_object()?;
+            if names.peek().is_some() {
+                value = object.get_named_property(name)?;
+                continue;
+            }
+
+            let reflect = self.reflect()?;
+            let descriptor = reflect
+                .get_named_property("getOwnPropertyDescriptor")?
+                .as_function()?
+                .call(
+                    &reflect,
+                    &[
+                        object.as_napi_any(),
+                        String::from_str(self, name)?.as_napi_any(),
+                    ],
+                )?
+                .as_object()?;
+            value = match descriptor.get_named_property("get")? {
+                getter if getter.value_type()? == ValueType::Function => getter,
+                _ => descriptor.get_named_property("value")?,
+            };
+        }
+
+        Ok(value)
+    }
+
+    #[cfg(feature = "napi6")]
+    fn cached_builtin(self, name: &'static str) -> Result<Option<Any>> {
+        match self.builtins()?.0.borrow().get(name) {
+            Some(reference) => reference.value(),
+            None => Ok(None),
+        }
+    }
+
+    #[cfg(feature = "napi6")]
+    fn builtins(self) -> Result<Rc<Builtins>> {
+        if let Some(builtins) = self.get_instance_data::<Builtins>()? {
+            return Ok(builtins);
+        }
+
+        self.set_instance_data(Builtins::default())?;
+        Ok(self.get_instance_data::<Builtins>()?.unwrap())
+    }
+}
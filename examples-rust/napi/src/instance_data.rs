@@ -0,0 +1,83 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::os::raw::c_void;
+use std::ptr;
+use std::rc::Rc;
+
+use crate::env::Env;
+use crate::result::Result;
+use crate::sys;
+
+/// Values stored with `Env::set_instance_data`, keyed by their type. This is
+/// what the environment's N-API instance data points to.
+type InstanceData = RefCell<HashMap<TypeId, Rc<dyn Any>>>;
+
+unsafe extern "C" fn finalize_instance_data(
+    _env: sys::napi_env,
+    data: *mut c_void,
+    _hint: *mut c_void,
+) {
+    drop(Box::from_raw(data as *mut InstanceData));
+}
+
+impl Env {
+    /// Stores `data` for this environment, replacing the previous value of
+    /// the same type. Each worker thread has its own environment, so this is
+    /// where per-addon state such as cached constructors belongs. Values are
+    /// dropped when the environment shuts down.
+    ///
+    /// The crate owns the environment's N-API instance data, so addons must
+    /// not call `napi_set_instance_data` themselves.
+    pub fn set_instance_data<T: 'static>(self, data: T) -> Result<()> {
+        let instance_data = match self.instance_data()? {
+            Some(instance_data) => instance_data,
+            None => self.create_instance_data()?,
+        };
+
+        // Drop the replaced value only after releasing the borrow, its
+        // destructor may use the instance data too.
+        let previous = instance_data
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Rc::new(data));
+        drop(previous);
+
+        Ok(())
+    }
+
+    /// Returns the value of type `T` stored with `set_instance_data`.
+    pub fn get_instance_data<T: 'static>(self) -> Result<Option<Rc<T>>> {
+        let instance_data = match self.instance_data()? {
+            Some(instance_data) => instance_data,
+            None => return Ok(None),
+        };
+
+        let data = instance_data.borrow().get(&TypeId::of::<T>()).cloned();
+        Ok(data.map(|data| data.downcast().unwrap()))
+    }
+
+    fn instance_data<'a>(self) -> Result<Option<&'a InstanceData>> {
+        let mut data = ptr::null_mut();
+        self.handle_status(unsafe { sys::napi_get_instance_data(self.as_sys_env(), &mut data) })?;
+        Ok(unsafe { (data as *const InstanceData).as_ref() })
+    }
+
+    fn create_instance_data<'a>(self) -> Result<&'a InstanceData> {
+        let instance_data = Box::into_raw(Box::<InstanceData>::default());
+        let status = unsafe {
+            sys::napi_set_instance_data(
+                self.as_sys_env(),
+                instance_data as *mut c_void,
+                Some(finalize_instance_data),
+                ptr::null_mut(),
+            )
+        };
+
+        if let Err(error) = self.handle_status(status) {
+            drop(unsafe { Box::from_raw(instance_data) });
+            return Err(error);
+        }
+
+        Ok(unsafe { &*instance_data })
+    }
+}
@@ -1,13 +1,51 @@
+mod async_context;
+#[cfg(feature = "napi3")]
+mod cleanup;
+mod convert;
 mod env;
+mod exception;
+mod external_memory;
+mod global;
+#[cfg(feature = "napi6")]
+mod instance_data;
+mod native;
+mod promise;
+mod property;
+mod reference;
 mod result;
+mod scope;
+mod script;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "napi4")]
+mod threadsafe;
 mod value;
 
+#[cfg(feature = "serde")]
+pub use crate::serde::{
+    from_js, from_js_with, to_js, to_js_with, BytesRepr, Deserializer, MapRepr, NoneRepr,
+    SerdeError, SerdeOptions, Serializer,
+};
+pub use async_context::AsyncContext;
+#[cfg(feature = "napi3")]
+pub use async_context::CallbackScope;
+#[cfg(feature = "napi3")]
+pub use cleanup::CleanupHandle;
+pub use convert::{FromJs, IntoJs};
 pub use env::Env;
+pub use exception::JsException;
+pub use external_memory::{ExternalMemory, SizeHint};
+pub use promise::Deferred;
+pub use property::{Callback, PropertyDescriptor};
+pub use reference::Ref;
+#[cfg(feature = "napi5")]
+pub use reference::WeakRef;
 pub use result::*;
+pub use scope::HandleScope;
 pub use value::*;
 
 pub mod sys {
     pub use napi_sys::*;
 }
 
-pub use napi_codegen::callback;
+pub use napi_codegen::{callback, module_init, FromJs, IntoJs};
@@ -0,0 +1,102 @@
+use std::any::TypeId;
+use std::os::raw::c_void;
+use std::ptr;
+
+use crate::external_memory::ExternalMemory;
+use crate::result::Result;
+use crate::sys;
+#[cfg(feature = "napi8")]
+use crate::value::Value;
+
+/// Native data handed over to the engine, tagged with its type so that it
+/// can be checked when it comes back, together with the memory it accounts
+/// for.
+///
+/// Objects wrapping a `Native` and externals holding one are type-tagged
+/// with `NATIVE_TAG`, since data attached by other native code can't be told
+/// apart from it otherwise.
+#[repr(C)]
+pub(crate) struct Native<T> {
+    type_id: TypeId,
+    _memory: Option<ExternalMemory>,
+    data: T,
+}
+
+impl<T: 'static> Native<T> {
+    pub(crate) fn into_raw(data: T, memory: Option<ExternalMemory>) -> *mut c_void {
+        let native = Native {
+            type_id: TypeId::of::<T>(),
+            _memory: memory,
+            data,
+        };
+        Box::into_raw(Box::new(native)) as *mut c_void
+    }
+
+    /// `pointer` must come from `into_raw`, possibly for a different `T`,
+    /// e.g. be attached to a value with `NATIVE_TAG`.
+    pub(crate) unsafe fn get<'a>(pointer: *mut c_void) -> Option<&'a T> {
+        if pointer.is_null() || *(pointer as *const TypeId) != TypeId::of::<T>() {
+            return None;
+        }
+        Some(&(*(pointer as *const Native<T>)).data)
+    }
+
+    /// `pointer` must come from `into_raw` for this `T`.
+    pub(crate) unsafe fn from_raw(pointer: *mut c_void) -> T {
+        Box::from_raw(pointer as *mut Native<T>).data
+    }
+
+    pub(crate) unsafe extern "C" fn finalize(
+        _env: sys::napi_env,
+        data: *mut c_void,
+        _hint: *mut c_void,
+    ) {
+        drop(Box::from_raw(data as *mut Native<T>));
+    }
+
+    /// Finalizer for external buffers, which get the native box as their hint.
+    pub(crate) unsafe extern "C" fn finalize_hint(
+        env: sys::napi_env,
+        _data: *mut c_void,
+        hint: *mut c_void,
+    ) {
+        Native::<T>::finalize(env, hint, ptr::null_mut());
+    }
+}
+
+#[cfg(feature = "napi8")]
+const NATIVE_TAG: sys::napi_type_tag = sys::napi_type_tag {
+    lower: 0x9c3e_51d4_07a2_4f6b,
+    upper: 0xb815_e2c9_6d40_a173,
+};
+
+/// Tags `value` as carrying a `Native`, unless it already is. Fails if the
+/// value has a type tag of other native code.
+#[cfg(feature = "napi8")]
+pub(crate) fn tag_native<V: Value>(value: &V) -> Result<()> {
+    if has_native_tag(value)? {
+        return Ok(());
+    }
+
+    let env = value.env();
+    env.handle_status(unsafe {
+        sys::napi_type_tag_object(env.as_sys_env(), value.as_sys_value(), &NATIVE_TAG)
+    })
+}
+
+#[cfg(feature = "napi8")]
+pub(crate) fn has_native_tag<V: Value>(value: &V) -> Result<bool> {
+    let env = value.env();
+    let mut result = false;
+
+    env.handle_status(unsafe {
+        sys::napi_check_object_type_tag(
+            env.as_sys_env(),
+            value.as_sys_value(),
+            &NATIVE_TAG,
+            &mut result,
+        )
+    })?;
+
+    Ok(result)
+}
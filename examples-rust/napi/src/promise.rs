@@ -0,0 +1,49 @@
+use std::ptr;
+
+use crate::env::Env;
+use crate::result::{Error, Result};
+use crate::sys;
+use crate::value::{Object, Value};
+
+/// The resolving functions of a promise created with `Deferred::new`. A
+/// deferred dropped without being settled leaves its promise pending forever.
+#[must_use = "the promise stays pending unless the deferred is settled"]
+#[derive(Debug)]
+pub struct Deferred {
+    deferred: sys::napi_deferred,
+    env: Env,
+}
+
+impl Deferred {
+    pub fn new(env: Env) -> Result<(Deferred, Object)> {
+        let mut deferred = ptr::null_mut();
+        let mut promise = ptr::null_mut();
+
+        env.handle_status(unsafe {
+            sys::napi_create_promise(env.as_sys_env(), &mut deferred, &mut promise)
+        })?;
+
+        Ok((
+            Deferred { deferred, env },
+            Object::from_sys_checked(env, promise)?,
+        ))
+    }
+
+    pub fn resolve<T: Value>(self, value: &T) -> Result<()> {
+        self.env.handle_status(unsafe {
+            sys::napi_resolve_deferred(self.env.as_sys_env(), self.deferred, value.as_sys_value())
+        })
+    }
+
+    pub fn reject<T: Value>(self, value: &T) -> Result<()> {
+        self.env.handle_status(unsafe {
+            sys::napi_reject_deferred(self.env.as_sys_env(), self.deferred, value.as_sys_value())
+        })
+    }
+
+    /// Rejects the promise with the exception `error` would be thrown as.
+    pub fn reject_with_error(self, error: Error) -> Result<()> {
+        let exception = error.into_exception(self.env)?;
+        self.reject(&exception)
+    }
+}
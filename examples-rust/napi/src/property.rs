@@ -0,0 +1,112 @@
+use std::ptr;
+
+use crate::env::Env;
+use crate::result::Result;
+use crate::sys;
+use crate::value::{String, Undefined, Value};
+
+pub type Callback =
+    unsafe extern "C" fn(env: sys::napi_env, cb_info: sys::napi_callback_info) -> sys::napi_value;
+
+#[derive(Clone, Debug)]
+pub struct PropertyDescriptor {
+    name: std::string::String,
+    method: sys::napi_callback,
+    getter: sys::napi_callback,
+    setter: sys::napi_callback,
+    value: sys::napi_value,
+    attributes: sys::napi_property_attributes,
+}
+
+impl PropertyDescriptor {
+    fn new(name: &str) -> PropertyDescriptor {
+        PropertyDescriptor {
+            name: name.to_owned(),
+            method: None,
+            getter: None,
+            setter: None,
+            value: ptr::null_mut(),
+            attributes: sys::napi_property_attributes::napi_default,
+        }
+    }
+
+    pub fn value<T>(name: &str, value: &T) -> PropertyDescriptor
+    where
+        T: Value,
+    {
+        PropertyDescriptor {
+            value: value.as_sys_value(),
+            ..PropertyDescriptor::new(name)
+        }
+    }
+
+    pub fn method(name: &str, method: Callback) -> PropertyDescriptor {
+        PropertyDescriptor {
+            method: Some(method),
+            ..PropertyDescriptor::new(name)
+        }
+    }
+
+    pub fn accessor(name: &str) -> PropertyDescriptor {
+        PropertyDescriptor::new(name)
+    }
+
+    pub fn getter(mut self, getter: Callback) -> PropertyDescriptor {
+        self.getter = Some(getter);
+        self
+    }
+
+    pub fn setter(mut self, setter: Callback) -> PropertyDescriptor {
+        self.setter = Some(setter);
+        self
+    }
+
+    pub fn writable(mut self) -> PropertyDescriptor {
+        self.attributes |= sys::napi_property_attributes::napi_writable;
+        self
+    }
+
+    pub fn enumerable(mut self) -> PropertyDescriptor {
+        self.attributes |= sys::napi_property_attributes::napi_enumerable;
+        self
+    }
+
+    pub fn configurable(mut self) -> PropertyDescriptor {
+        self.attributes |= sys::napi_property_attributes::napi_configurable;
+        self
+    }
+
+    pub fn static_member(mut self) -> PropertyDescriptor {
+        self.attributes |= sys::napi_property_attributes::napi_static;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn to_sys_descriptor(&self, env: Env) -> Result<sys::napi_property_descriptor> {
+        let name = String::from_str(env, &self.name)?;
+
+        let value = if self.value.is_null()
+            && self.method.is_none()
+            && self.getter.is_none()
+            && self.setter.is_none()
+        {
+            Undefined::new(env)?.as_sys_value()
+        } else {
+            self.value
+        };
+
+        Ok(sys::napi_property_descriptor {
+            utf8name: ptr::null(),
+            name: name.as_sys_value(),
+            method: self.method,
+            getter: self.getter,
+            setter: self.setter,
+            value,
+            attributes: self.attributes,
+            data: ptr::null_mut(),
+        })
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(feature = "napi5")]
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::ptr;
+#[cfg(feature = "napi5")]
+use std::rc::Rc;
+
+use crate::env::Env;
+use crate::result::Result;
+use crate::sys;
+use crate::value::Value;
+
+/// A reference to a JavaScript value that outlives the current handle scope.
+///
+/// With a reference count of zero the reference is weak and `value` returns
+/// `None` once the value has been garbage-collected. A `Ref` must not
+/// outlive the environment it was created in.
+#[derive(Debug)]
+pub struct Ref<T: Value> {
+    reference: sys::napi_ref,
+    env: Env,
+    marker: PhantomData<T>,
+}
+
+impl<T: Value> Ref<T> {
+    pub fn new(value: &T, initial_refcount: u32) -> Result<Ref<T>> {
+        let env = value.env();
+        let mut reference = ptr::null_mut();
+
+        env.handle_status(unsafe {
+            sys::napi_create_reference(
+                env.as_sys_env(),
+                value.as_sys_value(),
+                initial_refcount,
+                &mut reference,
+            )
+        })?;
+
+        Ok(Ref::from_sys(env, reference))
+    }
+
+    pub(crate) fn from_sys(env: Env, reference: sys::napi_ref) -> Ref<T> {
+        Ref {
+            reference,
+            env,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn as_sys_ref(&self) -> sys::napi_ref {
+        self.reference
+    }
+
+    pub fn env(&self) -> Env {
+        self.env
+    }
+
+    pub fn value(&self) -> Result<Option<T>> {
+        reference_value(self.env, self.reference)
+    }
+
+    /// Increments the reference count and returns the new count.
+    pub fn increment(&self) -> Result<u32> {
+        let mut count = 0;
+
+        self.env.handle_status(unsafe {
+            sys::napi_reference_ref(self.env.as_sys_env(), self.reference, &mut count)
+        })?;
+
+        Ok(count)
+    }
+
+    /// Decrements the reference count and returns the new count.
+    pub fn decrement(&self) -> Result<u32> {
+        let mut count = 0;
+
+        self.env.handle_status(unsafe {
+            sys::napi_reference_unref(self.env.as_sys_env(), self.reference, &mut count)
+        })?;
+
+        Ok(count)
+    }
+}
+
+impl<T: Value> Drop for Ref<T> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::napi_delete_reference(self.env.as_sys_env(), self.reference);
+        }
+    }
+}
+
+/// A weak reference to an object with a finalizer, returned by
+/// `Object::add_finalizer_with_ref`.
+///
+/// The reference belongs to the finalizer, which deletes it once the object
+/// has been garbage-collected, so dropping a `WeakRef` never keeps the
+/// finalizer from running.
+#[cfg(feature = "napi5")]
+#[derive(Clone, Debug)]
+pub struct WeakRef<T: Value> {
+    reference: Rc<Cell<sys::napi_ref>>,
+    env: Env,
+    marker: PhantomData<T>,
+}
+
+#[cfg(feature = "napi5")]
+impl<T: Value> WeakRef<T> {
+    /// `reference` is cleared by the finalizer when it deletes the reference.
+    pub(crate) fn from_shared(env: Env, reference: Rc<Cell<sys::napi_ref>>) -> WeakRef<T> {
+        WeakRef {
+            reference,
+            env,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn env(&self) -> Env {
+        self.env
+    }
+
+    /// Returns the object, or `None` once it has been garbage-collected.
+    pub fn value(&self) -> Result<Option<T>> {
+        let reference = self.reference.get();
+        if reference.is_null() {
+            return Ok(None);
+        }
+
+        reference_value(self.env, reference)
+    }
+}
+
+fn reference_value<T: Value>(env: Env, reference: sys::napi_ref) -> Result<Option<T>> {
+    let mut value = ptr::null_mut();
+
+    env.handle_status(unsafe {
+        sys::napi_get_reference_value(env.as_sys_env(), reference, &mut value)
+    })?;
+
+    if value.is_null() {
+        Ok(None)
+    } else {
+        T::from_sys_checked(env, value).map(Some)
+    }
+}
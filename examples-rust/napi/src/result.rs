@@ -1,13 +1,17 @@
+use std::any::Any as StdAny;
 use std::error::Error as StdError;
 use std::fmt;
 use std::fmt::Display;
 use std::ptr;
+use std::rc::Rc;
 
+use crate::convert::IntoJs;
 use crate::env::Env;
+use crate::sys;
 use crate::sys::{
     napi_create_error, napi_create_range_error, napi_create_type_error, napi_status, napi_value,
 };
-use crate::value::{String, Value};
+use crate::value::{Any, Object, String, Value};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ErrorKind {
@@ -23,7 +27,19 @@ pub enum ErrorKind {
     PendingException,
     Cancelled,
     EscapeCalledTwice,
+    HandleScopeMismatch,
+    CallbackScopeMismatch,
+    QueueFull,
+    Closing,
+    BigIntExpected,
+    DateExpected,
+    ArrayBufferExpected,
+    DetachableArrayBufferExpected,
+    WouldDeadlock,
+    NoExternalBuffersAllowed,
+    CannotRunJs,
     ApplicationError,
+    Unknown(u32),
 }
 
 #[derive(Clone, Debug)]
@@ -31,6 +47,9 @@ pub struct Error {
     pub kind: ErrorKind,
     pub message: Option<std::string::String>,
     pub exception: Option<napi_value>,
+    pub property_path: Vec<std::string::String>,
+    pub source: Option<Rc<dyn StdError>>,
+    pub engine_error_code: u32,
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -50,86 +69,389 @@ impl ErrorKind {
             napi_status::napi_pending_exception => ErrorKind::PendingException,
             napi_status::napi_cancelled => ErrorKind::Cancelled,
             napi_status::napi_escape_called_twice => ErrorKind::EscapeCalledTwice,
-            _ => {
-                // Both situations should never happen, so just panic.
-                panic!(
-                    "Either the JavaScript VM returned an unknown status code, \
-                     or NapiErrorKind::from_napi_status was called with \
-                     napi_status::napi_ok"
-                );
+            napi_status::napi_handle_scope_mismatch => ErrorKind::HandleScopeMismatch,
+            napi_status::napi_callback_scope_mismatch => ErrorKind::CallbackScopeMismatch,
+            napi_status::napi_queue_full => ErrorKind::QueueFull,
+            napi_status::napi_closing => ErrorKind::Closing,
+            napi_status::napi_bigint_expected => ErrorKind::BigIntExpected,
+            napi_status::napi_date_expected => ErrorKind::DateExpected,
+            napi_status::napi_arraybuffer_expected => ErrorKind::ArrayBufferExpected,
+            napi_status::napi_detachable_arraybuffer_expected => {
+                ErrorKind::DetachableArrayBufferExpected
             }
+            napi_status::napi_would_deadlock => ErrorKind::WouldDeadlock,
+            napi_status::napi_no_external_buffers_allowed => ErrorKind::NoExternalBuffersAllowed,
+            napi_status::napi_cannot_run_js => ErrorKind::CannotRunJs,
+            status => ErrorKind::Unknown(status.0),
         }
     }
 }
 
+impl Display for ErrorKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            ErrorKind::InvalidArg => "invalid argument",
+            ErrorKind::ObjectExpected => "object expected",
+            ErrorKind::StringExpected => "string expected",
+            ErrorKind::NameExpected => "name expected",
+            ErrorKind::FunctionExpected => "function expected",
+            ErrorKind::NumberExpected => "number expected",
+            ErrorKind::BooleanExpected => "boolean expected",
+            ErrorKind::ArrayExpected => "array expected",
+            ErrorKind::GenericFailure => "generic failure",
+            ErrorKind::PendingException => "pending exception",
+            ErrorKind::Cancelled => "cancelled",
+            ErrorKind::EscapeCalledTwice => "escape called twice",
+            ErrorKind::HandleScopeMismatch => "handle scope mismatch",
+            ErrorKind::CallbackScopeMismatch => "callback scope mismatch",
+            ErrorKind::QueueFull => "queue full",
+            ErrorKind::Closing => "closing",
+            ErrorKind::BigIntExpected => "bigint expected",
+            ErrorKind::DateExpected => "date expected",
+            ErrorKind::ArrayBufferExpected => "arraybuffer expected",
+            ErrorKind::DetachableArrayBufferExpected => "detachable arraybuffer expected",
+            ErrorKind::WouldDeadlock => "would deadlock",
+            ErrorKind::NoExternalBuffersAllowed => "external buffers not allowed",
+            ErrorKind::CannotRunJs => "cannot run JavaScript",
+            ErrorKind::ApplicationError => "application error",
+            ErrorKind::Unknown(status) => return write!(formatter, "unknown status {}", status),
+        };
+
+        formatter.write_str(description)
+    }
+}
+
 impl StdError for Error {
-    fn description(&self) -> &str {
-        match self.kind {
-            ErrorKind::InvalidArg => "NapiError: invalid argument",
-            ErrorKind::ObjectExpected => "NapiError: object expected",
-            ErrorKind::StringExpected => "NapiError: string expected",
-            ErrorKind::NameExpected => "NapiError: name expected",
-            ErrorKind::FunctionExpected => "NapiError: function expected",
-            ErrorKind::NumberExpected => "NapiError: number expected",
-            ErrorKind::BooleanExpected => "NapiError: boolean argument",
-            ErrorKind::ArrayExpected => "NapiError: array expected",
-            ErrorKind::GenericFailure => "NapiError: generic failure",
-            ErrorKind::PendingException => "NapiError: pending exception",
-            ErrorKind::Cancelled => "NapiError: cancelled",
-            ErrorKind::EscapeCalledTwice => "NapiError: escape called twice",
-            ErrorKind::ApplicationError => "NapiError: application error",
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match (&self.message, &self.source) {
+            // An error without its own message is a transparent wrapper around its source.
+            (None, Some(source)) => source.source(),
+            (_, source) => source.as_deref(),
         }
     }
 }
 
 impl Display for Error {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        write!(formatter, "{}", self)
-            .and_then(|result| {
-                if let Some(ref message) = self.message {
-                    write!(formatter, " ({})", message)
-                } else {
-                    Ok(result)
-                }
-            })
-            .and_then(|result| {
-                if self.exception.is_some() {
-                    write!(formatter, ", JavaScript exception attached")
-                } else {
-                    Ok(result)
-                }
-            })
+        if !self.property_path.is_empty() {
+            write!(formatter, "{}: ", self.property_path.join("."))?;
+        }
+
+        match (&self.message, &self.source, self.kind) {
+            (None, Some(source), _) => write!(formatter, "{}", source),
+            (None, None, kind) => write!(formatter, "{}", kind),
+            (Some(message), _, ErrorKind::ApplicationError) => write!(formatter, "{}", message),
+            (Some(message), _, kind) => write!(formatter, "{}: {}", kind, message),
+        }
     }
 }
 
 macro_rules! error_constructor {
-    ($name:ident => $napi_fn_name:ident) => {
+    ($name:ident, $with_code_name:ident => $napi_fn_name:ident) => {
         pub fn $name(env: Env, message: &String) -> Error {
-            let mut exception = ptr::null_mut();
-            let status = unsafe {
-                $napi_fn_name(
-                    env.as_sys_env(),
-                    ptr::null_mut(),
-                    message.as_sys_value(),
-                    &mut exception,
-                )
+            Error::create(env, $napi_fn_name, None, message)
+        }
+
+        pub fn $with_code_name(env: Env, code: &str, message: &String) -> Error {
+            Error::create(env, $napi_fn_name, Some(code), message)
+        }
+    };
+}
+
+type CreateErrorFn = unsafe extern "C" fn(
+    sys::napi_env,
+    sys::napi_value,
+    sys::napi_value,
+    *mut sys::napi_value,
+) -> napi_status;
+
+impl Error {
+    error_constructor!(error, with_code => napi_create_error);
+    error_constructor!(type_error, type_error_with_code => napi_create_type_error);
+    error_constructor!(range_error, range_error_with_code => napi_create_range_error);
+
+    fn create(env: Env, create: CreateErrorFn, code: Option<&str>, message: &String) -> Error {
+        let code = match code.map(|code| String::from_str(env, code)).transpose() {
+            Ok(code) => code,
+            Err(error) => return error,
+        };
+
+        let mut exception = ptr::null_mut();
+        let status = unsafe {
+            create(
+                env.as_sys_env(),
+                code.map_or(ptr::null_mut(), |code| code.as_sys_value()),
+                message.as_sys_value(),
+                &mut exception,
+            )
+        };
+
+        if let Err(error) = env.handle_status(status) {
+            return error;
+        }
+
+        Error {
+            kind: ErrorKind::ApplicationError,
+            message: message.to_string().ok(),
+            exception: Some(exception),
+            property_path: Vec::new(),
+            source: None,
+            engine_error_code: 0,
+        }
+    }
+
+    pub fn from_class(env: Env, class: &Object, message: &String) -> Error {
+        let mut exception = ptr::null_mut();
+        let args = [message.as_sys_value()];
+        let status = unsafe {
+            sys::napi_new_instance(
+                env.as_sys_env(),
+                class.as_sys_value(),
+                args.len(),
+                args.as_ptr(),
+                &mut exception,
+            )
+        };
+
+        if let Err(error) = env.handle_status(status) {
+            return error;
+        }
+
+        let mut is_error = false;
+        let status = unsafe { sys::napi_is_error(env.as_sys_env(), exception, &mut is_error) };
+        if let Err(error) = env.handle_status(status) {
+            return error;
+        }
+
+        if !is_error {
+            return match String::from_str(env, "Error subclass expected") {
+                Ok(message) => Error::type_error(env, &message),
+                Err(error) => error,
             };
+        }
+
+        Error {
+            kind: ErrorKind::ApplicationError,
+            message: message.to_string().ok(),
+            exception: Some(exception),
+            property_path: Vec::new(),
+            source: None,
+            engine_error_code: 0,
+        }
+    }
+
+    pub fn with_property<T: IntoJs>(self, env: Env, key: &str, value: T) -> Error {
+        let error = match self.exception {
+            Some(_) => self,
+            None => {
+                let message = match String::from_str(env, &self.full_message()) {
+                    Ok(message) => message,
+                    Err(error) => return error,
+                };
 
-            if let Err(error) = env.handle_status(status) {
-                return error;
+                Error {
+                    exception: Error::error(env, &message).exception,
+                    ..self
+                }
             }
+        };
+
+        let result = Any::with_value(env, error.exception.unwrap())
+            .as_object()
+            .and_then(|exception| exception.set(key, value));
+
+        match result {
+            Ok(()) => error,
+            Err(set_error) => set_error,
+        }
+    }
 
-            Error {
-                kind: ErrorKind::ApplicationError,
-                message: None,
-                exception: Some(exception),
+    pub fn wrap<E: StdError + 'static>(error: E) -> Error {
+        let mut error = Some(error);
+        if let Some(error) = (&mut error as &mut dyn StdAny).downcast_mut::<Option<Error>>() {
+            return error.take().unwrap();
+        }
+
+        Error {
+            kind: ErrorKind::ApplicationError,
+            message: None,
+            exception: None,
+            property_path: Vec::new(),
+            source: error.map(|error| Rc::new(error) as Rc<dyn StdError>),
+            engine_error_code: 0,
+        }
+    }
+
+    pub fn context<C: Display>(self, context: C) -> Error {
+        Error {
+            kind: ErrorKind::ApplicationError,
+            message: Some(context.to_string()),
+            exception: None,
+            property_path: Vec::new(),
+            source: Some(Rc::new(self)),
+            engine_error_code: 0,
+        }
+    }
+
+    /// Throws the error into JavaScript. This is how errors returned from
+    /// `#[napi::callback]` functions are thrown, so the macro calls it too.
+    #[doc(hidden)]
+    pub fn throw(self, env: Env) {
+        if let Some(exception) = self.exception {
+            unsafe {
+                sys::napi_throw(env.as_sys_env(), exception);
+            }
+        } else {
+            let message = self.full_message().replace('\0', "\\0");
+            let c_string = std::ffi::CString::new(message).unwrap();
+
+            unsafe {
+                sys::napi_throw_error(env.as_sys_env(), ptr::null(), c_string.as_ptr());
             }
         }
-    };
+    }
+
+    /// Returns the exception the error is thrown as, creating an `Error`
+    /// from the full message if it doesn't carry one.
+    pub(crate) fn into_exception(self, env: Env) -> Result<Any> {
+        if let Some(exception) = self.exception {
+            return Ok(Any::with_value(env, exception));
+        }
+
+        let message = String::from_str(env, &self.full_message())?;
+        let error = Error::error(env, &message);
+        match error.exception {
+            Some(exception) => Ok(Any::with_value(env, exception)),
+            None => Err(error),
+        }
+    }
+
+    pub fn full_message(&self) -> std::string::String {
+        let mut message = self.to_string();
+        let mut source = self.source();
+
+        while let Some(error) = source {
+            message.push_str(": ");
+            message.push_str(&error.to_string());
+            source = error.source();
+        }
+
+        message
+    }
+
+    /// Prefixes the property path with `key`. An error thrown from a
+    /// JavaScript exception of its own is rethrown as one whose message
+    /// starts with the full path.
+    pub fn in_property(self, env: Env, key: &str) -> Error {
+        let mut property_path = self.property_path;
+        property_path.insert(0, key.to_owned());
+
+        let (exception, reason) = match (self.exception, self.message) {
+            (Some(exception), Some(reason)) => (exception, reason),
+            (exception, message) => {
+                return Error {
+                    exception,
+                    message,
+                    property_path,
+                    ..self
+                }
+            }
+        };
+
+        let full_message = format!("{}: {}", property_path.join("."), reason);
+        let full_message = match String::from_str(env, &full_message) {
+            Ok(full_message) => full_message,
+            Err(error) => return error,
+        };
+
+        let name = Any::with_value(env, exception)
+            .as_object()
+            .and_then(|exception| exception.get_named_property("name"))
+            .and_then(|name| name.as_string())
+            .and_then(|name| name.to_string());
+
+        let error = match name.as_ref().map(|name| name.as_str()) {
+            Ok("TypeError") => Error::type_error(env, &full_message),
+            Ok("RangeError") => Error::range_error(env, &full_message),
+            _ => Error::error(env, &full_message),
+        };
+
+        Error {
+            message: Some(reason),
+            property_path,
+            ..error
+        }
+    }
 }
 
-impl Error {
-    error_constructor!(error => napi_create_error);
-    error_constructor!(type_error => napi_create_type_error);
-    error_constructor!(range_error => napi_create_range_error);
+pub trait ResultExt<T> {
+    fn context<C: Display>(self, context: C) -> Result<T>;
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, f: F) -> Result<T>;
+}
+
+impl<T, E: StdError + 'static> ResultExt<T> for std::result::Result<T, E> {
+    fn context<C: Display>(self, context: C) -> Result<T> {
+        self.map_err(|error| Error::wrap(error).context(context))
+    }
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, f: F) -> Result<T> {
+        self.map_err(|error| Error::wrap(error).context(f()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_error(kind: ErrorKind) -> Error {
+        Error {
+            kind,
+            message: None,
+            exception: None,
+            property_path: Vec::new(),
+            source: None,
+            engine_error_code: 0,
+        }
+    }
+
+    #[test]
+    fn maps_statuses() {
+        assert_eq!(
+            ErrorKind::from_napi_status(napi_status::napi_queue_full),
+            ErrorKind::QueueFull
+        );
+        assert_eq!(
+            ErrorKind::from_napi_status(napi_status(1000)),
+            ErrorKind::Unknown(1000)
+        );
+        assert_eq!(ErrorKind::Unknown(1000).to_string(), "unknown status 1000");
+    }
+
+    #[test]
+    fn displays_path_and_kind() {
+        let mut error = status_error(ErrorKind::NumberExpected);
+        assert_eq!(error.to_string(), "number expected");
+
+        error.message = Some("A number was expected".to_owned());
+        error.property_path = vec!["options".to_owned(), "timeout".to_owned()];
+        assert_eq!(
+            error.to_string(),
+            "options.timeout: number expected: A number was expected"
+        );
+    }
+
+    #[test]
+    fn chains_context() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+        let error = Error::wrap(io).context("failed to read config");
+        assert_eq!(error.kind, ErrorKind::ApplicationError);
+        assert_eq!(error.to_string(), "failed to read config");
+        assert_eq!(error.full_message(), "failed to read config: no such file");
+
+        // Wrapping an `Error` returns it as it is.
+        let wrapped = Error::wrap(status_error(ErrorKind::Closing));
+        assert_eq!(wrapped.kind, ErrorKind::Closing);
+        assert!(wrapped.source.is_none());
+    }
 }
@@ -0,0 +1,30 @@
+use std::ptr;
+
+use crate::env::Env;
+use crate::result::Result;
+use crate::sys;
+
+/// Releases the handles created while it is open when dropped. Values
+/// created inside the scope must not be used after it is closed, and scopes
+/// must be closed in the reverse order of opening them.
+#[derive(Debug)]
+pub struct HandleScope {
+    scope: sys::napi_handle_scope,
+    env: Env,
+}
+
+impl HandleScope {
+    pub fn new(env: Env) -> Result<HandleScope> {
+        let mut scope = ptr::null_mut();
+        env.handle_status(unsafe { sys::napi_open_handle_scope(env.as_sys_env(), &mut scope) })?;
+        Ok(HandleScope { scope, env })
+    }
+}
+
+impl Drop for HandleScope {
+    fn drop(&mut self) {
+        unsafe {
+            sys::napi_close_handle_scope(self.env.as_sys_env(), self.scope);
+        }
+    }
+}
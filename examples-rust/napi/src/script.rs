@@ -0,0 +1,37 @@
+use std::ptr;
+
+use crate::env::Env;
+use crate::reference::Ref;
+use crate::result::Result;
+use crate::sys;
+use crate::value::{Any, Function, String, Value};
+
+impl Env {
+    /// Runs `source` as a script in the global scope and returns its
+    /// completion value. If the script throws, the error carries the
+    /// exception and its stack trace.
+    pub fn run_script(self, source: &str) -> Result<Any> {
+        let script = String::from_str(self, source)?;
+        let mut result = ptr::null_mut();
+
+        let status =
+            unsafe { sys::napi_run_script(self.as_sys_env(), script.as_sys_value(), &mut result) };
+
+        self.handle_status(status).map_err(|mut error| {
+            if let Some(exception) = error.js_exception(self) {
+                error.message = Some(format!("{:#}", exception));
+            }
+            error
+        })?;
+
+        Ok(Any::with_value(self, result))
+    }
+
+    /// Compiles `body` as the body of a function taking `args` and keeps it
+    /// alive so that it can be called again without recompiling it.
+    pub fn eval_function(self, args: &[&str], body: &str) -> Result<Ref<Function>> {
+        let source = format!("(function ({}) {{\n{}\n}})", args.join(", "), body);
+        let function = self.run_script(&source)?.as_function()?;
+        Ref::new(&function, 1)
+    }
+}
@@ -0,0 +1,507 @@
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::env::Env;
+use crate::result::Result;
+use crate::value::{Any, Array, AsObject, Function, Map, Object, Value, ValueType};
+
+use super::{SerdeError, SerdeOptions, SerdeResult};
+
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Deserializer {
+    value: Any,
+    options: SerdeOptions,
+    map_constructor: Function,
+}
+
+impl Deserializer {
+    pub fn new(value: Any, options: SerdeOptions) -> Result<Deserializer> {
+        Ok(Deserializer {
+            value,
+            options,
+            map_constructor: value.env().map_constructor()?,
+        })
+    }
+
+    fn env(&self) -> Env {
+        self.value.env()
+    }
+
+    fn nested(&self, value: Any) -> Deserializer {
+        Deserializer {
+            value,
+            options: self.options,
+            map_constructor: self.map_constructor,
+        }
+    }
+
+    fn is_nullish(&self) -> SerdeResult<bool> {
+        Ok(matches!(
+            self.value.value_type()?,
+            ValueType::Undefined | ValueType::Null
+        ))
+    }
+
+    fn deserialize_number<'de, V>(&self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let number = self.value.as_number()?.to_f64()?;
+
+        if number.fract() == 0.0 && number.abs() <= MAX_SAFE_INTEGER {
+            if number < 0.0 {
+                visitor.visit_i64(number as i64)
+            } else {
+                visitor.visit_u64(number as u64)
+            }
+        } else {
+            visitor.visit_f64(number)
+        }
+    }
+
+    fn deserialize_bigint<'de, V>(&self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let bigint = self.value.as_bigint()?;
+
+        if let (value, true) = bigint.to_u64()? {
+            return visitor.visit_u64(value);
+        }
+
+        if let (value, true) = bigint.to_i64()? {
+            return visitor.visit_i64(value);
+        }
+
+        let (negative, words) = bigint.to_words()?;
+        if words.len() > 2 {
+            return Err(de::Error::custom("BigInt does not fit into 128 bits"));
+        }
+
+        let magnitude = words
+            .iter()
+            .rev()
+            .fold(0u128, |result, &word| (result << 64) | u128::from(word));
+
+        if !negative {
+            visitor.visit_u128(magnitude)
+        } else if magnitude <= i128::MAX as u128 + 1 {
+            visitor.visit_i128((magnitude as i128).wrapping_neg())
+        } else {
+            Err(de::Error::custom("BigInt does not fit into 128 bits"))
+        }
+    }
+
+    fn deserialize_object<'de, V>(&self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let env = self.env();
+
+        if self.value.is_buffer()? {
+            let buffer = crate::value::Buffer::from_sys_checked(env, self.value.as_sys_value())?;
+            return visitor.visit_byte_buf(buffer.as_ref().to_vec());
+        }
+
+        if self.value.is_array()? {
+            let array = self.value.as_array()?;
+            return visitor.visit_seq(SeqAccess::new(*self, array)?);
+        }
+
+        let object = self.value.as_object()?;
+
+        if self
+            .value
+            .instanceof(&self.map_constructor.as_napi_object())?
+        {
+            let entries = Map::from_sys_checked(env, self.value.as_sys_value())?.entries()?;
+            return visitor.visit_map(MapAccess::entries(*self, entries));
+        }
+
+        visitor.visit_map(MapAccess::properties(*self, object)?)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value.value_type()? {
+            ValueType::Undefined | ValueType::Null => visitor.visit_unit(),
+            ValueType::Boolean => visitor.visit_bool(self.value.as_boolean()?.to_bool()?),
+            ValueType::Number => self.deserialize_number(visitor),
+            ValueType::String => visitor.visit_string(self.value.as_string()?.to_string()?),
+            ValueType::BigInt => self.deserialize_bigint(visitor),
+            ValueType::Object => self.deserialize_object(visitor),
+            value_type => Err(de::Error::custom(format!(
+                "{:?} values cannot be deserialized",
+                value_type
+            ))),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.is_nullish()? {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.is_nullish()? {
+            visitor.visit_unit()
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value.value_type()? {
+            ValueType::String => {
+                let variant = self.value.as_string()?.to_string()?;
+                visitor.visit_enum(variant.into_deserializer())
+            }
+            ValueType::Object => {
+                let object = self.value.as_object()?;
+                let keys = object.property_names()?;
+
+                if keys.len()? != 1 {
+                    return Err(de::Error::custom(
+                        "object with a single key expected for an enum variant",
+                    ));
+                }
+
+                let variant = keys.get(0)?.as_string()?.to_string()?;
+                let value = object.get_named_property(&variant)?;
+
+                visitor.visit_enum(EnumAccess {
+                    deserializer: self,
+                    variant,
+                    value,
+                })
+            }
+            _ => Err(de::Error::custom("string or object expected for an enum")),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct SeqAccess {
+    deserializer: Deserializer,
+    array: Array,
+    index: u32,
+    len: u32,
+}
+
+impl SeqAccess {
+    fn new(deserializer: Deserializer, array: Array) -> SerdeResult<SeqAccess> {
+        Ok(SeqAccess {
+            deserializer,
+            array,
+            index: 0,
+            len: array.len()?,
+        })
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = SerdeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> SerdeResult<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+
+        let env = self.deserializer.env();
+        let index = self.index;
+        self.index += 1;
+
+        let element = self.deserializer.nested(self.array.get(index)?);
+        seed.deserialize(element)
+            .map(Some)
+            .map_err(|error| error.in_property(env, &index.to_string()))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some((self.len - self.index) as usize)
+    }
+}
+
+enum MapSource {
+    Properties { object: Object, keys: Array },
+    Entries(Vec<(Any, Any)>),
+}
+
+struct MapAccess {
+    deserializer: Deserializer,
+    source: MapSource,
+    index: u32,
+    len: u32,
+    current: Option<(std::string::String, Any)>,
+}
+
+impl MapAccess {
+    fn properties(deserializer: Deserializer, object: Object) -> SerdeResult<MapAccess> {
+        let keys = object.property_names()?;
+
+        Ok(MapAccess {
+            deserializer,
+            source: MapSource::Properties { object, keys },
+            index: 0,
+            len: keys.len()?,
+            current: None,
+        })
+    }
+
+    fn entries(deserializer: Deserializer, entries: Vec<(Any, Any)>) -> MapAccess {
+        MapAccess {
+            deserializer,
+            len: entries.len() as u32,
+            source: MapSource::Entries(entries),
+            index: 0,
+            current: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = SerdeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> SerdeResult<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+
+        let (key, value) = match &self.source {
+            MapSource::Properties { object, keys } => {
+                let key = keys.get(self.index)?;
+                (key, object.get_property(&key)?)
+            }
+            MapSource::Entries(entries) => entries[self.index as usize],
+        };
+        self.index += 1;
+
+        let key_name = key.to_napi_string()?.to_string()?;
+        self.current = Some((key_name.clone(), value));
+
+        let result = match self.source {
+            MapSource::Properties { .. } => seed.deserialize(KeyDeserializer { key: key_name }),
+            MapSource::Entries(_) => seed.deserialize(self.deserializer.nested(key)),
+        };
+
+        result.map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> SerdeResult<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let env = self.deserializer.env();
+        let (key, value) = match self.current.take() {
+            Some(current) => current,
+            None => return Err(de::Error::custom("map value requested before its key")),
+        };
+
+        seed.deserialize(self.deserializer.nested(value))
+            .map_err(|error| error.in_property(env, &key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some((self.len - self.index) as usize)
+    }
+}
+
+struct EnumAccess {
+    deserializer: Deserializer,
+    variant: std::string::String,
+    value: Any,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = SerdeError;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V>(self, seed: V) -> SerdeResult<(V::Value, VariantAccess)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(KeyDeserializer {
+            key: self.variant.clone(),
+        })?;
+
+        Ok((
+            variant,
+            VariantAccess {
+                deserializer: self.deserializer.nested(self.value),
+                variant: self.variant,
+            },
+        ))
+    }
+}
+
+struct VariantAccess {
+    deserializer: Deserializer,
+    variant: std::string::String,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> SerdeResult<()> {
+        de::Deserialize::deserialize(self.deserializer)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> SerdeResult<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let env = self.deserializer.env();
+        seed.deserialize(self.deserializer)
+            .map_err(|error| error.in_property(env, &self.variant))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let env = self.deserializer.env();
+        de::Deserializer::deserialize_seq(self.deserializer, visitor)
+            .map_err(|error| error.in_property(env, &self.variant))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let env = self.deserializer.env();
+        de::Deserializer::deserialize_map(self.deserializer, visitor)
+            .map_err(|error| error.in_property(env, &self.variant))
+    }
+}
+
+struct KeyDeserializer {
+    key: std::string::String,
+}
+
+macro_rules! deserialize_parsed_key {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> SerdeResult<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                match self.key.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => visitor.visit_string(self.key),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.key)
+    }
+
+    deserialize_parsed_key! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self.key.into_deserializer())
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf option unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+
+use crate::env::Env;
+use crate::result::{Error, Result};
+use crate::value::{Any, String, Value};
+
+mod de;
+mod ser;
+
+pub use self::de::Deserializer;
+pub use self::ser::Serializer;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NoneRepr {
+    Undefined,
+    Null,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BytesRepr {
+    Buffer,
+    Array,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MapRepr {
+    Object,
+    Map,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SerdeOptions {
+    pub none_as: NoneRepr,
+    pub bytes_as: BytesRepr,
+    pub maps_as: MapRepr,
+}
+
+impl Default for SerdeOptions {
+    fn default() -> SerdeOptions {
+        SerdeOptions {
+            none_as: NoneRepr::Undefined,
+            bytes_as: BytesRepr::Buffer,
+            maps_as: MapRepr::Object,
+        }
+    }
+}
+
+pub fn to_js<T>(env: Env, value: &T) -> Result<Any>
+where
+    T: serde::Serialize + ?Sized,
+{
+    to_js_with(env, value, SerdeOptions::default())
+}
+
+pub fn to_js_with<T>(env: Env, value: &T, options: SerdeOptions) -> Result<Any>
+where
+    T: serde::Serialize + ?Sized,
+{
+    value
+        .serialize(Serializer::new(env, options))
+        .map_err(|error| error.into_napi_error(env))
+}
+
+pub fn from_js<T>(value: Any) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    from_js_with(value, SerdeOptions::default())
+}
+
+pub fn from_js_with<T>(value: Any, options: SerdeOptions) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let deserializer = Deserializer::new(value, options)?;
+    T::deserialize(deserializer).map_err(|error| error.into_napi_error(value.env()))
+}
+
+pub type SerdeResult<T> = std::result::Result<T, SerdeError>;
+
+#[derive(Debug)]
+pub enum SerdeError {
+    Napi(Error),
+    Message {
+        message: std::string::String,
+        property_path: Vec<std::string::String>,
+    },
+}
+
+impl SerdeError {
+    fn in_property(self, env: Env, key: &str) -> SerdeError {
+        match self {
+            SerdeError::Napi(error) => SerdeError::Napi(error.in_property(env, key)),
+            SerdeError::Message {
+                message,
+                mut property_path,
+            } => {
+                property_path.insert(0, key.to_owned());
+                SerdeError::Message {
+                    message,
+                    property_path,
+                }
+            }
+        }
+    }
+
+    fn into_napi_error(self, env: Env) -> Error {
+        match self {
+            SerdeError::Napi(error) => error,
+            SerdeError::Message {
+                message,
+                property_path,
+            } => {
+                let full_message = if property_path.is_empty() {
+                    message.clone()
+                } else {
+                    format!("{}: {}", property_path.join("."), message)
+                };
+
+                let error = match String::from_str(env, &full_message) {
+                    Ok(full_message) => Error::type_error(env, &full_message),
+                    Err(error) => return error,
+                };
+
+                Error {
+                    message: Some(message),
+                    property_path,
+                    ..error
+                }
+            }
+        }
+    }
+}
+
+impl From<Error> for SerdeError {
+    fn from(error: Error) -> SerdeError {
+        SerdeError::Napi(error)
+    }
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SerdeError::Napi(error) => write!(formatter, "{}", error),
+            SerdeError::Message { message, .. } => write!(formatter, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl serde::ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(message: T) -> SerdeError {
+        SerdeError::Message {
+            message: message.to_string(),
+            property_path: Vec::new(),
+        }
+    }
+}
+
+impl serde::de::Error for SerdeError {
+    fn custom<T: fmt::Display>(message: T) -> SerdeError {
+        SerdeError::Message {
+            message: message.to_string(),
+            property_path: Vec::new(),
+        }
+    }
+}
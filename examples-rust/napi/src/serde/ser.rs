@@ -0,0 +1,458 @@
+use serde::ser::{self, Serialize};
+
+use crate::env::Env;
+use crate::value::{
+    call_method, Any, Array, BigInt, Boolean, Buffer, Null, Number, Object, String, Undefined,
+    Value,
+};
+
+use super::{BytesRepr, MapRepr, NoneRepr, SerdeError, SerdeOptions, SerdeResult};
+
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Serializer {
+    env: Env,
+    options: SerdeOptions,
+}
+
+impl Serializer {
+    pub fn new(env: Env, options: SerdeOptions) -> Serializer {
+        Serializer { env, options }
+    }
+
+    fn serialize_nested<T>(&self, value: &T) -> SerdeResult<Any>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(*self)
+    }
+
+    fn wrap_variant(&self, variant: &str, value: Any) -> SerdeResult<Any> {
+        let object = Object::new(self.env)?;
+        object.set_named_property(variant, &value)?;
+        Ok(object.as_napi_any())
+    }
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Any;
+    type Error = SerdeError;
+
+    type SerializeSeq = ArraySerializer;
+    type SerializeTuple = ArraySerializer;
+    type SerializeTupleStruct = ArraySerializer;
+    type SerializeTupleVariant = VariantSerializer<ArraySerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = ObjectSerializer;
+    type SerializeStructVariant = VariantSerializer<ObjectSerializer>;
+
+    fn serialize_bool(self, value: bool) -> SerdeResult<Any> {
+        let value = if value {
+            Boolean::truth(self.env)?
+        } else {
+            Boolean::lie(self.env)?
+        };
+
+        Ok(value.as_napi_any())
+    }
+
+    fn serialize_i8(self, value: i8) -> SerdeResult<Any> {
+        self.serialize_i32(i32::from(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> SerdeResult<Any> {
+        self.serialize_i32(i32::from(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> SerdeResult<Any> {
+        Ok(Number::from_i32(self.env, value)?.as_napi_any())
+    }
+
+    fn serialize_i64(self, value: i64) -> SerdeResult<Any> {
+        if value.unsigned_abs() <= MAX_SAFE_INTEGER {
+            Ok(Number::from_i64(self.env, value)?.as_napi_any())
+        } else {
+            Ok(BigInt::from_i64(self.env, value)?.as_napi_any())
+        }
+    }
+
+    fn serialize_i128(self, value: i128) -> SerdeResult<Any> {
+        if value.unsigned_abs() <= u128::from(MAX_SAFE_INTEGER) {
+            self.serialize_i64(value as i64)
+        } else {
+            Ok(BigInt::from_i128(self.env, value)?.as_napi_any())
+        }
+    }
+
+    fn serialize_u8(self, value: u8) -> SerdeResult<Any> {
+        self.serialize_u32(u32::from(value))
+    }
+
+    fn serialize_u16(self, value: u16) -> SerdeResult<Any> {
+        self.serialize_u32(u32::from(value))
+    }
+
+    fn serialize_u32(self, value: u32) -> SerdeResult<Any> {
+        Ok(Number::from_u32(self.env, value)?.as_napi_any())
+    }
+
+    fn serialize_u64(self, value: u64) -> SerdeResult<Any> {
+        if value <= MAX_SAFE_INTEGER {
+            Ok(Number::from_i64(self.env, value as i64)?.as_napi_any())
+        } else {
+            Ok(BigInt::from_u64(self.env, value)?.as_napi_any())
+        }
+    }
+
+    fn serialize_u128(self, value: u128) -> SerdeResult<Any> {
+        if value <= u128::from(MAX_SAFE_INTEGER) {
+            self.serialize_u64(value as u64)
+        } else {
+            Ok(BigInt::from_u128(self.env, value)?.as_napi_any())
+        }
+    }
+
+    fn serialize_f32(self, value: f32) -> SerdeResult<Any> {
+        self.serialize_f64(f64::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> SerdeResult<Any> {
+        Ok(Number::from_f64(self.env, value)?.as_napi_any())
+    }
+
+    fn serialize_char(self, value: char) -> SerdeResult<Any> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, value: &str) -> SerdeResult<Any> {
+        Ok(String::from_str(self.env, value)?.as_napi_any())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> SerdeResult<Any> {
+        match self.options.bytes_as {
+            BytesRepr::Buffer => Ok(Buffer::from_slice(self.env, value)?.as_napi_any()),
+            BytesRepr::Array => {
+                let array = Array::with_len(self.env, value.len())?;
+                for (index, byte) in value.iter().enumerate() {
+                    array.set(index as u32, &Number::from_u32(self.env, u32::from(*byte))?)?;
+                }
+                Ok(array.as_napi_any())
+            }
+        }
+    }
+
+    fn serialize_none(self) -> SerdeResult<Any> {
+        match self.options.none_as {
+            NoneRepr::Undefined => Ok(Undefined::new(self.env)?.as_napi_any()),
+            NoneRepr::Null => Ok(Null::new(self.env)?.as_napi_any()),
+        }
+    }
+
+    fn serialize_some<T>(self, value: &T) -> SerdeResult<Any>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> SerdeResult<Any> {
+        Ok(Null::new(self.env)?.as_napi_any())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> SerdeResult<Any> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> SerdeResult<Any> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> SerdeResult<Any>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> SerdeResult<Any>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = self.serialize_nested(value)?;
+        self.wrap_variant(variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> SerdeResult<ArraySerializer> {
+        let array = match len {
+            Some(len) => Array::with_len(self.env, len)?,
+            None => Array::new(self.env)?,
+        };
+
+        Ok(ArraySerializer {
+            serializer: self,
+            array,
+            index: 0,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> SerdeResult<ArraySerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> SerdeResult<ArraySerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> SerdeResult<VariantSerializer<ArraySerializer>> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> SerdeResult<MapSerializer> {
+        let target = match self.options.maps_as {
+            MapRepr::Object => Object::new(self.env)?,
+            MapRepr::Map => self.env.map_constructor()?.new_instance(&[])?,
+        };
+
+        Ok(MapSerializer {
+            serializer: self,
+            target,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> SerdeResult<ObjectSerializer> {
+        Ok(ObjectSerializer {
+            serializer: self,
+            object: Object::new(self.env)?,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> SerdeResult<VariantSerializer<ObjectSerializer>> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_struct(name, len)?,
+        })
+    }
+}
+
+pub struct ArraySerializer {
+    serializer: Serializer,
+    array: Array,
+    index: u32,
+}
+
+impl ArraySerializer {
+    fn push<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let env = self.serializer.env;
+        let index = self.index;
+
+        let value = self
+            .serializer
+            .serialize_nested(value)
+            .map_err(|error| error.in_property(env, &index.to_string()))?;
+
+        self.array.set(index, &value)?;
+        self.index += 1;
+
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for ArraySerializer {
+    type Ok = Any;
+    type Error = SerdeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> SerdeResult<Any> {
+        Ok(self.array.as_napi_any())
+    }
+}
+
+impl ser::SerializeTuple for ArraySerializer {
+    type Ok = Any;
+    type Error = SerdeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> SerdeResult<Any> {
+        Ok(self.array.as_napi_any())
+    }
+}
+
+impl ser::SerializeTupleStruct for ArraySerializer {
+    type Ok = Any;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> SerdeResult<Any> {
+        Ok(self.array.as_napi_any())
+    }
+}
+
+pub struct ObjectSerializer {
+    serializer: Serializer,
+    object: Object,
+}
+
+impl ser::SerializeStruct for ObjectSerializer {
+    type Ok = Any;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> SerdeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let env = self.serializer.env;
+
+        let value = self
+            .serializer
+            .serialize_nested(value)
+            .map_err(|error| error.in_property(env, key))?;
+
+        Ok(self.object.set_named_property(key, &value)?)
+    }
+
+    fn end(self) -> SerdeResult<Any> {
+        Ok(self.object.as_napi_any())
+    }
+}
+
+pub struct MapSerializer {
+    serializer: Serializer,
+    target: Object,
+    key: Option<Any>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Any;
+    type Error = SerdeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> SerdeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(self.serializer.serialize_nested(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let env = self.serializer.env;
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Err(ser::Error::custom("map value serialized before its key")),
+        };
+
+        let value = self.serializer.serialize_nested(value).map_err(|error| {
+            match key.to_napi_string().and_then(|key| key.to_string()) {
+                Ok(key) => error.in_property(env, &key),
+                Err(_) => error,
+            }
+        })?;
+
+        match self.serializer.options.maps_as {
+            MapRepr::Object => self.target.set_property(&key, &value)?,
+            MapRepr::Map => {
+                call_method(&self.target, "set", &[key, value])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn end(self) -> SerdeResult<Any> {
+        Ok(self.target.as_napi_any())
+    }
+}
+
+pub struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<ArraySerializer> {
+    type Ok = Any;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> SerdeResult<Any> {
+        let serializer = self.inner.serializer;
+        serializer.wrap_variant(self.variant, self.inner.array.as_napi_any())
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<ObjectSerializer> {
+    type Ok = Any;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> SerdeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> SerdeResult<Any> {
+        let serializer = self.inner.serializer;
+        serializer.wrap_variant(self.variant, self.inner.object.as_napi_any())
+    }
+}
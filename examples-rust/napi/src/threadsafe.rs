@@ -0,0 +1,232 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::ops::ControlFlow;
+use std::os::raw::c_void;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+use crate::env::Env;
+use crate::result::{Error, Result};
+use crate::sys;
+use crate::value::{String, Value};
+
+/// Runs a Rust callback on the JavaScript thread with values sent from other
+/// threads. The underlying threadsafe function is released when this is
+/// dropped, and the callback is dropped once pending calls have run.
+struct ThreadsafeCallback<T> {
+    function: sys::napi_threadsafe_function,
+    marker: PhantomData<fn(T)>,
+}
+
+unsafe impl<T: Send> Send for ThreadsafeCallback<T> {}
+
+impl<T: Send + 'static> ThreadsafeCallback<T> {
+    fn new<F>(env: Env, name: &str, callback: F) -> Result<ThreadsafeCallback<T>>
+    where
+        F: FnMut(Env, T) -> Result<()> + 'static,
+    {
+        unsafe extern "C" fn call_js<T, F>(
+            env: sys::napi_env,
+            _js_callback: sys::napi_value,
+            context: *mut c_void,
+            data: *mut c_void,
+        ) where
+            F: FnMut(Env, T) -> Result<()> + 'static,
+        {
+            let data = *Box::from_raw(data as *mut T);
+            if env.is_null() {
+                return;
+            }
+
+            let env = Env::from(env);
+            let callback = &mut *(context as *mut F);
+            if let Err(error) = callback(env, data) {
+                error.throw(env);
+            }
+        }
+
+        unsafe extern "C" fn finalize<F>(
+            _env: sys::napi_env,
+            data: *mut c_void,
+            _hint: *mut c_void,
+        ) {
+            drop(Box::from_raw(data as *mut F));
+        }
+
+        let name = String::from_str(env, name)?;
+        let context = Box::into_raw(Box::new(callback)) as *mut c_void;
+        let mut function = ptr::null_mut();
+
+        let status = unsafe {
+            sys::napi_create_threadsafe_function(
+                env.as_sys_env(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                name.as_sys_value(),
+                0,
+                1,
+                context,
+                Some(finalize::<F>),
+                context,
+                Some(call_js::<T, F>),
+                &mut function,
+            )
+        };
+
+        if let Err(error) = env.handle_status(status) {
+            drop(unsafe { Box::from_raw(context as *mut F) });
+            return Err(error);
+        }
+
+        Ok(ThreadsafeCallback {
+            function,
+            marker: PhantomData,
+        })
+    }
+
+    /// Queues a call with `data`, returning `false` if the function is
+    /// closing and `data` was dropped instead.
+    fn call(&self, data: T) -> bool {
+        let data = Box::into_raw(Box::new(data));
+        let status = unsafe {
+            sys::napi_call_threadsafe_function(
+                self.function,
+                data as *mut c_void,
+                sys::napi_threadsafe_function_call_mode::napi_tsfn_blocking,
+            )
+        };
+
+        if status != sys::napi_status::napi_ok {
+            drop(unsafe { Box::from_raw(data) });
+            return false;
+        }
+
+        true
+    }
+}
+
+impl<T> Drop for ThreadsafeCallback<T> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::napi_release_threadsafe_function(
+                self.function,
+                sys::napi_threadsafe_function_release_mode::napi_tsfn_release,
+            );
+        }
+    }
+}
+
+/// Sets its flag when the threadsafe function's callback is dropped, i.e.
+/// when the function has been finalized.
+struct Closed(Rc<Cell<bool>>);
+
+impl Drop for Closed {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+/// A background thread serving requests from the JavaScript thread one at a
+/// time and delivering each reply back on the JavaScript thread.
+///
+/// The event loop is only kept alive while a request is pending. Dropping
+/// the worker stops the thread after the request in progress, if any.
+pub(crate) struct Worker<R> {
+    requests: Sender<R>,
+    function: sys::napi_threadsafe_function,
+    closed: Rc<Cell<bool>>,
+    env: Env,
+}
+
+impl<R: Send + 'static> Worker<R> {
+    /// Spawns a thread that passes each request to `work` and its reply to
+    /// `reply`. The thread stops after `work` breaks, once the final reply
+    /// has been sent.
+    pub(crate) fn spawn<T, W, F>(
+        env: Env,
+        name: &str,
+        mut work: W,
+        mut reply: F,
+    ) -> Result<Worker<R>>
+    where
+        T: Send + 'static,
+        W: FnMut(R) -> ControlFlow<T, T> + Send + 'static,
+        F: FnMut(Env, T) -> Result<()> + 'static,
+    {
+        let closed = Rc::new(Cell::new(false));
+        let guard = Closed(closed.clone());
+        let callback = ThreadsafeCallback::new(env, name, move |env, value| {
+            let _guard = &guard;
+            reply(env, value)
+        })?;
+
+        let function = callback.function;
+        let (requests, receiver) = mpsc::channel();
+        let worker = Worker {
+            requests,
+            function,
+            closed,
+            env,
+        };
+        worker.set_referenced(false)?;
+
+        thread::spawn(move || {
+            for request in receiver {
+                let (value, last) = match work(request) {
+                    ControlFlow::Continue(value) => (value, false),
+                    ControlFlow::Break(value) => (value, true),
+                };
+
+                if !callback.call(value) || last {
+                    break;
+                }
+            }
+        });
+
+        Ok(worker)
+    }
+
+    /// Sends `request` to the thread and keeps the event loop alive until
+    /// `idle` is called, normally when the reply arrives. Fails if the thread
+    /// has stopped, in which case no reply will come.
+    pub(crate) fn request(&self, request: R) -> Result<()> {
+        if self.requests.send(request).is_err() {
+            let message = String::from_str(self.env, "Worker thread has stopped")?;
+            return Err(Error::error(self.env, &message));
+        }
+        self.set_referenced(true)
+    }
+
+    pub(crate) fn idle(&self) -> Result<()> {
+        self.set_referenced(false)
+    }
+
+    fn set_referenced(&self, referenced: bool) -> Result<()> {
+        if self.closed.get() {
+            return Ok(());
+        }
+
+        let env = self.env;
+        let status = unsafe {
+            if referenced {
+                sys::napi_ref_threadsafe_function(env.as_sys_env(), self.function)
+            } else {
+                sys::napi_unref_threadsafe_function(env.as_sys_env(), self.function)
+            }
+        };
+
+        env.handle_status(status)
+    }
+}
+
+impl<R> Drop for Worker<R> {
+    fn drop(&mut self) {
+        if !self.closed.get() {
+            unsafe {
+                sys::napi_unref_threadsafe_function(self.env.as_sys_env(), self.function);
+            }
+        }
+    }
+}
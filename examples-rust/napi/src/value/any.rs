@@ -4,8 +4,11 @@ use crate::env::Env;
 use crate::result::{Error, Result};
 use crate::sys;
 
+#[cfg(feature = "napi6")]
+use super::BigInt;
 use super::{
-    Array, Boolean, Null, Number, Object, String, Undefined, Value, ValueInternal, ValueType,
+    Array, Boolean, Function, Null, Number, Object, String, Undefined, Value, ValueInternal,
+    ValueType,
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -76,6 +79,17 @@ impl Any {
         }
     }
 
+    #[cfg(feature = "napi6")]
+    pub fn as_bigint(&self) -> Result<BigInt> {
+        match self.value_type()? {
+            ValueType::BigInt => Ok(BigInt::construct(self.env(), self.as_sys_value())),
+            _ => Err(Error::type_error(
+                self.env(),
+                &String::from_str(self.env(), "bigint expected")?,
+            )),
+        }
+    }
+
     pub fn as_object(&self) -> Result<Object> {
         match self.value_type()? {
             ValueType::Object | ValueType::String | ValueType::Function => {
@@ -88,6 +102,16 @@ impl Any {
         }
     }
 
+    pub fn as_function(&self) -> Result<Function> {
+        match self.value_type()? {
+            ValueType::Function => Ok(Function::construct(self.env(), self.as_sys_value())),
+            _ => Err(Error::type_error(
+                self.env(),
+                &String::from_str(self.env(), "function expected")?,
+            )),
+        }
+    }
+
     pub fn as_array(&self) -> Result<Array> {
         if self.is_array()? {
             Ok(Array::construct(self.env(), self.as_sys_value()))
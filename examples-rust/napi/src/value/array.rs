@@ -1,10 +1,12 @@
 use std::ptr;
 
+use crate::convert::IntoJs;
 use crate::env::Env;
 use crate::result::{Error, Result};
+use crate::scope::HandleScope;
 use crate::sys;
 
-use super::{Any, AsObject, String, Value, ValueInternal};
+use super::{Any, ArrayIter, AsObject, String, Value, ValueInternal};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Array {
@@ -53,6 +55,52 @@ impl Array {
     {
         self.as_napi_object().set_element(index, value)
     }
+
+    pub fn iter(&self) -> Result<ArrayIter> {
+        ArrayIter::new(*self)
+    }
+
+    /// Calls `f` with every element, each in its own handle scope, so that
+    /// walking a large array takes a constant number of handles. An element
+    /// must not be used after `f` returns; keep it alive with a `Ref`.
+    pub fn for_each<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(Any) -> Result<()>,
+    {
+        for index in 0..self.len()? {
+            let _scope = HandleScope::new(self.env)?;
+            f(self.get(index)?)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn from_iter<I>(env: Env, iter: I) -> Result<Array>
+    where
+        I: IntoIterator,
+        I::Item: IntoJs,
+    {
+        let array = Array::new(env)?;
+        array.extend(iter)?;
+        Ok(array)
+    }
+
+    /// Appends the elements of `iter`, converting each of them in its own
+    /// handle scope.
+    pub fn extend<I>(&self, iter: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: IntoJs,
+    {
+        let len = self.len()?;
+
+        for (index, element) in (len..).zip(iter) {
+            let _scope = HandleScope::new(self.env)?;
+            self.set(index, &element.into_js(self.env)?)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Value for Array {
@@ -1,8 +1,11 @@
+use std::os::raw::c_void;
 use std::ptr;
 use std::slice;
 
 use crate::env::Env;
-use crate::result::{Error, Result};
+use crate::external_memory::ExternalMemory;
+use crate::native::Native;
+use crate::result::{Error, ErrorKind, Result};
 use crate::sys;
 
 use super::{Any, AsObject, String, Value};
@@ -30,6 +33,47 @@ impl<'buf> ArrayBuffer<'buf> {
         })
     }
 
+    /// Hands `bytes` over to the engine without copying them, reporting their
+    /// memory until the ArrayBuffer is garbage-collected. The bytes are copied
+    /// instead if the runtime doesn't allow external buffers.
+    pub fn from_vec(env: Env, mut bytes: Vec<u8>) -> Result<ArrayBuffer<'buf>> {
+        let len = bytes.len();
+        let data = bytes.as_mut_ptr();
+        let memory = ExternalMemory::for_value(env, &bytes)?;
+        let native = Native::into_raw(bytes, Some(memory));
+        let mut value = ptr::null_mut();
+
+        let status = unsafe {
+            sys::napi_create_external_arraybuffer(
+                env.as_sys_env(),
+                data as *mut c_void,
+                len,
+                Some(Native::<Vec<u8>>::finalize_hint),
+                native,
+                &mut value,
+            )
+        };
+
+        match env.handle_status(status) {
+            Ok(()) => Ok(ArrayBuffer {
+                value,
+                data: unsafe { slice::from_raw_parts_mut(data, len) },
+                env,
+            }),
+            Err(error) => {
+                let bytes = unsafe { Native::<Vec<u8>>::from_raw(native) };
+                match error.kind {
+                    ErrorKind::NoExternalBuffersAllowed => {
+                        let mut buffer = ArrayBuffer::new(env, bytes.len())?;
+                        buffer.as_mut().copy_from_slice(&bytes);
+                        Ok(buffer)
+                    }
+                    _ => Err(error),
+                }
+            }
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.data.len()
     }
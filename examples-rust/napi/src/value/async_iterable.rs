@@ -0,0 +1,241 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::mem;
+use std::ops::ControlFlow;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+
+use futures_core::Stream;
+
+use crate::convert::IntoJs;
+use crate::env::Env;
+use crate::promise::Deferred;
+use crate::result::{Error, Result};
+use crate::sys;
+use crate::threadsafe::Worker;
+
+use super::js_iterator::iterator_result;
+use super::{Any, AsObject, Function, Object, String, Value, ValueInternal, ValueType};
+
+/// A JavaScript async iterable backed by a Rust stream, which lets
+/// `for await...of` consume items produced off the JavaScript thread.
+#[derive(Clone, Copy, Debug)]
+pub struct AsyncIterable {
+    value: sys::napi_value,
+    env: Env,
+}
+
+enum Pulled<T> {
+    Item(T),
+    End,
+    Panicked,
+}
+
+/// The JavaScript side of the iterator.
+#[derive(Default)]
+struct Pulls {
+    /// Promises returned by `next()` that wait for an item, oldest first.
+    /// Only the first one has been requested from the worker thread.
+    waiting: VecDeque<Deferred>,
+    /// Set until the stream ends or `return()` is called.
+    worker: Option<Worker<()>>,
+}
+
+impl AsyncIterable {
+    /// Polls `stream` on a background thread, one item per `next()` call:
+    /// the next item is not pulled until the previous one has been delivered.
+    /// `return()`, e.g. from a `break` out of a `for await...of` loop, drops
+    /// the stream once the pull in progress, if any, completes.
+    pub fn from_stream<S>(env: Env, stream: S) -> Result<AsyncIterable>
+    where
+        S: Stream + Send + 'static,
+        S::Item: IntoJs + Send + 'static,
+    {
+        let pulls = Rc::new(RefCell::new(Pulls::default()));
+
+        let mut stream = Box::pin(stream);
+        let weak = Rc::downgrade(&pulls);
+        let worker = Worker::spawn(
+            env,
+            "AsyncIterable",
+            move |()| {
+                let polled = panic::catch_unwind(AssertUnwindSafe(|| next_item(stream.as_mut())));
+                match polled {
+                    Ok(Some(item)) => ControlFlow::Continue(Pulled::Item(item)),
+                    Ok(None) => ControlFlow::Break(Pulled::End),
+                    Err(_) => ControlFlow::Break(Pulled::Panicked),
+                }
+            },
+            move |env, pulled| match weak.upgrade() {
+                Some(pulls) => pulls.borrow_mut().complete(env, pulled),
+                None => Ok(()),
+            },
+        )?;
+        pulls.borrow_mut().worker = Some(worker);
+
+        let object = Object::new(env)?;
+
+        let state = pulls.clone();
+        let next = Function::from_fn(env, "next", move |env, _this, _args| {
+            let (deferred, promise) = Deferred::new(env)?;
+            state.borrow_mut().next(env, deferred)?;
+            Ok(promise)
+        })?;
+
+        let return_ = Function::from_fn(env, "return", move |env, _this, args| {
+            let (deferred, promise) = Deferred::new(env)?;
+            pulls.borrow_mut().finish(env)?;
+            let value = match args.first() {
+                Some(value) => *value,
+                None => Any::new(env)?,
+            };
+            deferred.resolve(&iterator_result(env, value, true)?)?;
+            Ok(promise)
+        })?;
+
+        let iterator =
+            Function::from_fn(env, "[Symbol.asyncIterator]", |_env, this, _args| Ok(this))?;
+        let symbol = env
+            .symbol_constructor()?
+            .as_napi_object()
+            .get_named_property("asyncIterator")?;
+
+        object.set_named_property("next", &next)?;
+        object.set_named_property("return", &return_)?;
+        object.set_property(&symbol, &iterator)?;
+
+        Ok(AsyncIterable::construct(env, object.as_sys_value()))
+    }
+}
+
+impl Pulls {
+    fn next(&mut self, env: Env, deferred: Deferred) -> Result<()> {
+        if self.worker.is_none() {
+            return deferred.resolve(&iterator_result(env, Any::new(env)?, true)?);
+        }
+
+        self.waiting.push_back(deferred);
+        if self.waiting.len() == 1 {
+            self.request(env)?;
+        }
+        Ok(())
+    }
+
+    fn complete<T: IntoJs>(&mut self, env: Env, pulled: Pulled<T>) -> Result<()> {
+        let deferred = match self.waiting.pop_front() {
+            Some(deferred) => deferred,
+            None => return Ok(()),
+        };
+
+        match pulled {
+            Pulled::Item(item) => {
+                match item.into_js(env) {
+                    Ok(value) => deferred.resolve(&iterator_result(env, value, false)?)?,
+                    Err(error) => deferred.reject_with_error(error)?,
+                }
+
+                if self.waiting.is_empty() {
+                    self.idle()
+                } else {
+                    self.request(env)
+                }
+            }
+            Pulled::End => {
+                deferred.resolve(&iterator_result(env, Any::new(env)?, true)?)?;
+                self.finish(env)
+            }
+            Pulled::Panicked => {
+                let message = String::from_str(env, "Stream panicked")?;
+                deferred.reject_with_error(Error::error(env, &message))?;
+                self.finish(env)
+            }
+        }
+    }
+
+    /// Asks the worker thread for the item the oldest waiting promise is for,
+    /// or rejects it if the thread has stopped.
+    fn request(&mut self, env: Env) -> Result<()> {
+        let error = match &self.worker {
+            Some(worker) => match worker.request(()) {
+                Ok(()) => return Ok(()),
+                Err(error) => error,
+            },
+            None => return Ok(()),
+        };
+
+        if let Some(deferred) = self.waiting.pop_front() {
+            deferred.reject_with_error(error)?;
+        }
+        self.finish(env)
+    }
+
+    /// Stops the worker thread and settles the promises still waiting as
+    /// done.
+    fn finish(&mut self, env: Env) -> Result<()> {
+        self.worker = None;
+
+        for deferred in mem::take(&mut self.waiting) {
+            deferred.resolve(&iterator_result(env, Any::new(env)?, true)?)?;
+        }
+        Ok(())
+    }
+
+    fn idle(&self) -> Result<()> {
+        match &self.worker {
+            Some(worker) => worker.idle(),
+            None => Ok(()),
+        }
+    }
+}
+
+struct ThreadWaker(thread::Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Blocks the current thread until the stream yields its next item.
+fn next_item<S: Stream + ?Sized>(mut stream: Pin<&mut S>) -> Option<S::Item> {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(item) = stream.as_mut().poll_next(&mut context) {
+            return item;
+        }
+        thread::park();
+    }
+}
+
+impl Value for AsyncIterable {
+    fn as_sys_value(&self) -> sys::napi_value {
+        self.value
+    }
+
+    fn env(&self) -> Env {
+        self.env
+    }
+
+    fn from_sys_checked(env: Env, value: sys::napi_value) -> Result<AsyncIterable> {
+        if Any::with_value(env, value).value_type()? != ValueType::Object {
+            let message = String::from_str(env, "Async iterable expected")?;
+            return Err(Error::type_error(env, &message));
+        }
+
+        Ok(AsyncIterable { env, value })
+    }
+}
+
+impl ValueInternal for AsyncIterable {
+    fn construct(env: Env, value: sys::napi_value) -> AsyncIterable {
+        AsyncIterable { env, value }
+    }
+}
+
+impl AsObject for AsyncIterable {}
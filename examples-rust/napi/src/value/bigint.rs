@@ -0,0 +1,156 @@
+use std::ptr;
+
+use crate::env::Env;
+use crate::result::{Error, Result};
+use crate::sys;
+
+use super::{Any, String, Value, ValueInternal, ValueType};
+
+#[derive(Clone, Copy, Debug)]
+pub struct BigInt {
+    value: sys::napi_value,
+    env: Env,
+}
+
+impl BigInt {
+    pub fn from_i64(env: Env, value: i64) -> Result<BigInt> {
+        let mut sys_value = ptr::null_mut();
+        env.handle_status(unsafe {
+            sys::napi_create_bigint_int64(env.as_sys_env(), value, &mut sys_value)
+        })?;
+
+        Ok(BigInt {
+            value: sys_value,
+            env,
+        })
+    }
+
+    pub fn from_u64(env: Env, value: u64) -> Result<BigInt> {
+        let mut sys_value = ptr::null_mut();
+        env.handle_status(unsafe {
+            sys::napi_create_bigint_uint64(env.as_sys_env(), value, &mut sys_value)
+        })?;
+
+        Ok(BigInt {
+            value: sys_value,
+            env,
+        })
+    }
+
+    pub fn from_words(env: Env, negative: bool, words: &[u64]) -> Result<BigInt> {
+        let mut sys_value = ptr::null_mut();
+        env.handle_status(unsafe {
+            sys::napi_create_bigint_words(
+                env.as_sys_env(),
+                negative as i32,
+                words.len(),
+                words.as_ptr(),
+                &mut sys_value,
+            )
+        })?;
+
+        Ok(BigInt {
+            value: sys_value,
+            env,
+        })
+    }
+
+    pub fn from_i128(env: Env, value: i128) -> Result<BigInt> {
+        let magnitude = value.unsigned_abs();
+        BigInt::from_words(
+            env,
+            value < 0,
+            &[magnitude as u64, (magnitude >> 64) as u64],
+        )
+    }
+
+    pub fn from_u128(env: Env, value: u128) -> Result<BigInt> {
+        BigInt::from_words(env, false, &[value as u64, (value >> 64) as u64])
+    }
+
+    pub fn to_i64(&self) -> Result<(i64, bool)> {
+        let mut result = 0;
+        let mut lossless = false;
+
+        self.env.handle_status(unsafe {
+            sys::napi_get_value_bigint_int64(
+                self.env.as_sys_env(),
+                self.value,
+                &mut result,
+                &mut lossless,
+            )
+        })?;
+
+        Ok((result, lossless))
+    }
+
+    pub fn to_u64(&self) -> Result<(u64, bool)> {
+        let mut result = 0;
+        let mut lossless = false;
+
+        self.env.handle_status(unsafe {
+            sys::napi_get_value_bigint_uint64(
+                self.env.as_sys_env(),
+                self.value,
+                &mut result,
+                &mut lossless,
+            )
+        })?;
+
+        Ok((result, lossless))
+    }
+
+    pub fn to_words(&self) -> Result<(bool, Vec<u64>)> {
+        let mut word_count = 0;
+
+        self.env.handle_status(unsafe {
+            sys::napi_get_value_bigint_words(
+                self.env.as_sys_env(),
+                self.value,
+                ptr::null_mut(),
+                &mut word_count,
+                ptr::null_mut(),
+            )
+        })?;
+
+        let mut sign_bit = 0;
+        let mut words = vec![0; word_count];
+
+        self.env.handle_status(unsafe {
+            sys::napi_get_value_bigint_words(
+                self.env.as_sys_env(),
+                self.value,
+                &mut sign_bit,
+                &mut word_count,
+                words.as_mut_ptr(),
+            )
+        })?;
+
+        Ok((sign_bit != 0, words))
+    }
+}
+
+impl Value for BigInt {
+    fn as_sys_value(&self) -> sys::napi_value {
+        self.value
+    }
+
+    fn env(&self) -> Env {
+        self.env
+    }
+
+    fn from_sys_checked(env: Env, value: sys::napi_value) -> Result<BigInt> {
+        if Any::with_value(env, value).value_type()? != ValueType::BigInt {
+            let message = String::from_str(env, "BigInt expected")?;
+            return Err(Error::type_error(env, &message));
+        }
+
+        Ok(BigInt { env, value })
+    }
+}
+
+impl ValueInternal for BigInt {
+    fn construct(env: Env, value: sys::napi_value) -> BigInt {
+        BigInt { env, value }
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+use std::ptr;
+
+use crate::env::Env;
+use crate::external_memory::{ExternalMemory, SizeHint};
+use crate::native::{has_native_tag, tag_native, Native};
+use crate::result::{Error, Result};
+use crate::sys;
+
+use super::{Any, String, Value, ValueType};
+
+/// A JavaScript value owning a Rust `T`, which is dropped when the value is
+/// garbage-collected.
+pub struct External<T> {
+    value: sys::napi_value,
+    data: *const T,
+    env: Env,
+}
+
+impl<T: 'static> External<T> {
+    /// Creates an external owning `data`, reporting the memory `data` owns to
+    /// the engine until the value is garbage-collected.
+    pub fn new(env: Env, data: T) -> Result<External<T>>
+    where
+        T: SizeHint,
+    {
+        let memory = ExternalMemory::for_value(env, &data)?;
+        External::create(env, data, Some(memory))
+    }
+
+    /// Like `new`, for data whose memory isn't reported to the engine.
+    pub fn without_size_hint(env: Env, data: T) -> Result<External<T>> {
+        External::create(env, data, None)
+    }
+
+    fn create(env: Env, data: T, memory: Option<ExternalMemory>) -> Result<External<T>> {
+        let native = Native::into_raw(data, memory);
+        let mut value = ptr::null_mut();
+
+        let status = unsafe {
+            sys::napi_create_external(
+                env.as_sys_env(),
+                native,
+                Some(Native::<T>::finalize),
+                ptr::null_mut(),
+                &mut value,
+            )
+        };
+
+        if let Err(error) = env.handle_status(status) {
+            drop(unsafe { Native::<T>::from_raw(native) });
+            return Err(error);
+        }
+
+        let external = External {
+            value,
+            data: unsafe { Native::<T>::get(native) }.unwrap(),
+            env,
+        };
+        tag_native(&external)?;
+        Ok(external)
+    }
+
+    pub fn get(&self) -> &T {
+        unsafe { &*self.data }
+    }
+}
+
+impl<T: 'static> Value for External<T> {
+    fn as_sys_value(&self) -> sys::napi_value {
+        self.value
+    }
+
+    fn env(&self) -> Env {
+        self.env
+    }
+
+    /// Externals created by other native code are rejected by their type
+    /// tag before their data is looked at.
+    fn from_sys_checked(env: Env, value: sys::napi_value) -> Result<External<T>> {
+        let any = Any::with_value(env, value);
+        if any.value_type()? != ValueType::External || !has_native_tag(&any)? {
+            let message = String::from_str(env, "External expected")?;
+            return Err(Error::type_error(env, &message));
+        }
+
+        let mut native = ptr::null_mut();
+
+        env.handle_status(unsafe {
+            sys::napi_get_value_external(env.as_sys_env(), any.as_sys_value(), &mut native)
+        })?;
+
+        match unsafe { Native::<T>::get(native) } {
+            Some(data) => Ok(External { value, data, env }),
+            None => {
+                let message = String::from_str(env, "External of a different type")?;
+                Err(Error::type_error(env, &message))
+            }
+        }
+    }
+}
+
+impl<T> Clone for External<T> {
+    fn clone(&self) -> External<T> {
+        *self
+    }
+}
+
+impl<T> Copy for External<T> {}
+
+impl<T> fmt::Debug for External<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("External")
+            .field("value", &self.value)
+            .field("env", &self.env)
+            .finish()
+    }
+}
@@ -0,0 +1,247 @@
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+
+use crate::async_context::AsyncContext;
+use crate::convert::IntoJs;
+use crate::env::Env;
+use crate::native::Native;
+use crate::property::{Callback, PropertyDescriptor};
+use crate::result::{Error, Result};
+use crate::sys;
+
+use super::{Any, AsObject, Object, String, Value, ValueInternal, ValueType};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Function {
+    value: sys::napi_value,
+    env: Env,
+}
+
+impl Function {
+    /// Creates a function that calls `callback` with the receiver and the
+    /// arguments. Errors are thrown into JavaScript, and `callback` is dropped
+    /// when the function is garbage-collected.
+    ///
+    /// The closure is attached with `napi_wrap`, so the function itself can't
+    /// be wrapped again.
+    pub fn from_fn<F, R>(env: Env, name: &str, callback: F) -> Result<Function>
+    where
+        F: Fn(Env, Any, &[Any]) -> Result<R> + 'static,
+        R: IntoJs,
+    {
+        unsafe extern "C" fn call<F, R>(
+            env: sys::napi_env,
+            cb_info: sys::napi_callback_info,
+        ) -> sys::napi_value
+        where
+            F: Fn(Env, Any, &[Any]) -> Result<R> + 'static,
+            R: IntoJs,
+        {
+            let env = Env::from(env);
+            match callback_args(env, cb_info).and_then(|(this, args, data)| {
+                let callback = Native::<F>::get(data).unwrap();
+                callback(env, this, &args)?.into_js(env)
+            }) {
+                Ok(value) => value.as_sys_value(),
+                Err(error) => {
+                    error.throw(env);
+                    ptr::null_mut()
+                }
+            }
+        }
+
+        let data = Native::into_raw(callback, None);
+        let mut value = ptr::null_mut();
+
+        let status = unsafe {
+            sys::napi_create_function(
+                env.as_sys_env(),
+                name.as_ptr() as *const c_char,
+                name.len(),
+                Some(call::<F, R>),
+                data,
+                &mut value,
+            )
+        };
+
+        if let Err(error) = env.handle_status(status) {
+            drop(unsafe { Native::<F>::from_raw(data) });
+            return Err(error);
+        }
+
+        let status = unsafe {
+            sys::napi_wrap(
+                env.as_sys_env(),
+                value,
+                data,
+                Some(Native::<F>::finalize),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+
+        if let Err(error) = env.handle_status(status) {
+            drop(unsafe { Native::<F>::from_raw(data) });
+            return Err(error);
+        }
+
+        Ok(Function { value, env })
+    }
+
+    /// Defines a class whose constructor calls `constructor` with the new
+    /// instance as `this`, usually to wrap native data into it. `properties`
+    /// are defined on the prototype, or on the class itself if they are
+    /// static members.
+    pub fn define_class(
+        env: Env,
+        name: &str,
+        constructor: Callback,
+        properties: &[PropertyDescriptor],
+    ) -> Result<Function> {
+        let descriptors = properties
+            .iter()
+            .map(|property| property.to_sys_descriptor(env))
+            .collect::<Result<Vec<_>>>()?;
+        let mut value = ptr::null_mut();
+
+        env.handle_status(unsafe {
+            sys::napi_define_class(
+                env.as_sys_env(),
+                name.as_ptr() as *const c_char,
+                name.len(),
+                Some(constructor),
+                ptr::null_mut(),
+                descriptors.len(),
+                descriptors.as_ptr(),
+                &mut value,
+            )
+        })?;
+
+        Ok(Function { value, env })
+    }
+
+    pub fn call<T>(&self, this: &T, args: &[Any]) -> Result<Any>
+    where
+        T: Value,
+    {
+        let args = args.iter().map(Value::as_sys_value).collect::<Vec<_>>();
+        let mut result = ptr::null_mut();
+
+        self.env.handle_status(unsafe {
+            sys::napi_call_function(
+                self.env.as_sys_env(),
+                this.as_sys_value(),
+                self.value,
+                args.len(),
+                args.as_ptr(),
+                &mut result,
+            )
+        })?;
+
+        Ok(Any::with_value(self.env, result))
+    }
+
+    /// Calls the function from native code that isn't itself called from
+    /// JavaScript, e.g. a completion callback, restoring the async context of
+    /// `context` and running the microtask queue afterwards.
+    pub fn make_callback<T>(&self, context: &AsyncContext, this: &T, args: &[Any]) -> Result<Any>
+    where
+        T: Value,
+    {
+        let args = args.iter().map(Value::as_sys_value).collect::<Vec<_>>();
+        let mut result = ptr::null_mut();
+
+        self.env.handle_status(unsafe {
+            sys::napi_make_callback(
+                self.env.as_sys_env(),
+                context.as_sys_context(),
+                this.as_sys_value(),
+                self.value,
+                args.len(),
+                args.as_ptr(),
+                &mut result,
+            )
+        })?;
+
+        Ok(Any::with_value(self.env, result))
+    }
+
+    pub fn new_instance(&self, args: &[Any]) -> Result<Object> {
+        let args = args.iter().map(Value::as_sys_value).collect::<Vec<_>>();
+        let mut result = ptr::null_mut();
+
+        self.env.handle_status(unsafe {
+            sys::napi_new_instance(
+                self.env.as_sys_env(),
+                self.value,
+                args.len(),
+                args.as_ptr(),
+                &mut result,
+            )
+        })?;
+
+        Ok(Object::construct(self.env, result))
+    }
+}
+
+unsafe fn callback_args(
+    env: Env,
+    cb_info: sys::napi_callback_info,
+) -> Result<(Any, Vec<Any>, *mut c_void)> {
+    let mut argc = 0;
+    let mut this = ptr::null_mut();
+    let mut data = ptr::null_mut();
+
+    env.handle_status(sys::napi_get_cb_info(
+        env.as_sys_env(),
+        cb_info,
+        &mut argc,
+        ptr::null_mut(),
+        &mut this,
+        &mut data,
+    ))?;
+
+    let mut argv = vec![ptr::null_mut(); argc];
+    env.handle_status(sys::napi_get_cb_info(
+        env.as_sys_env(),
+        cb_info,
+        &mut argc,
+        argv.as_mut_ptr(),
+        ptr::null_mut(),
+        ptr::null_mut(),
+    ))?;
+
+    let args = argv
+        .into_iter()
+        .map(|arg| Any::with_value(env, arg))
+        .collect();
+
+    Ok((Any::with_value(env, this), args, data))
+}
+
+impl Value for Function {
+    fn as_sys_value(&self) -> sys::napi_value {
+        self.value
+    }
+
+    fn env(&self) -> Env {
+        self.env
+    }
+
+    fn from_sys_checked(env: Env, value: sys::napi_value) -> Result<Function> {
+        if Any::with_value(env, value).value_type()? != ValueType::Function {
+            let message = String::from_str(env, "Function expected")?;
+            return Err(Error::type_error(env, &message));
+        }
+
+        Ok(Function { env, value })
+    }
+}
+
+impl ValueInternal for Function {
+    fn construct(env: Env, value: sys::napi_value) -> Function {
+        Function { env, value }
+    }
+}
+
+impl AsObject for Function {}
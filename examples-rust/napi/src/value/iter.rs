@@ -0,0 +1,106 @@
+use crate::result::Result;
+
+use super::{Any, Array, Object, String, Value};
+
+/// Iterator over the elements of an `Array`.
+///
+/// Elements are created in the caller's handle scope, so they stay valid
+/// after the iterator advances, but walking a large array takes a handle per
+/// element. `Array::for_each` visits every element in its own handle scope
+/// instead.
+#[derive(Debug)]
+pub struct ArrayIter {
+    array: Array,
+    index: u32,
+    len: u32,
+}
+
+impl ArrayIter {
+    pub(crate) fn new(array: Array) -> Result<ArrayIter> {
+        Ok(ArrayIter {
+            len: array.len()?,
+            array,
+            index: 0,
+        })
+    }
+}
+
+impl Iterator for ArrayIter {
+    type Item = Result<Any>;
+
+    fn next(&mut self) -> Option<Result<Any>> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        let index = self.index;
+        self.index += 1;
+        Some(self.array.get(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.len - self.index) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Iterator over the enumerable property names of an `Object`, creating them
+/// in the caller's handle scope like `ArrayIter`. `Object::for_each_key`
+/// visits every name in its own handle scope instead.
+#[derive(Debug)]
+pub struct Keys {
+    names: ArrayIter,
+}
+
+impl Keys {
+    pub(crate) fn new(object: &Object) -> Result<Keys> {
+        Ok(Keys {
+            names: ArrayIter::new(object.property_names()?)?,
+        })
+    }
+}
+
+impl Iterator for Keys {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Result<String>> {
+        let name = self.names.next()?;
+        Some(name.and_then(|name| name.to_napi_string()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.names.size_hint()
+    }
+}
+
+/// Iterator over the enumerable properties of an `Object` as key-value pairs,
+/// creating them in the caller's handle scope like `ArrayIter`.
+/// `Object::for_each_entry` visits every pair in its own handle scope
+/// instead.
+#[derive(Debug)]
+pub struct Entries {
+    object: Object,
+    keys: Keys,
+}
+
+impl Entries {
+    pub(crate) fn new(object: &Object) -> Result<Entries> {
+        Ok(Entries {
+            object: *object,
+            keys: Keys::new(object)?,
+        })
+    }
+}
+
+impl Iterator for Entries {
+    type Item = Result<(String, Any)>;
+
+    fn next(&mut self) -> Option<Result<(String, Any)>> {
+        let key = self.keys.next()?;
+        Some(key.and_then(|key| Ok((key, self.object.get_property(&key)?))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
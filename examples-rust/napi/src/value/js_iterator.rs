@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::convert::IntoJs;
+use crate::env::Env;
+use crate::result::{Error, Result};
+use crate::sys;
+
+use super::{Any, AsObject, Function, Object, String, Value, ValueInternal, ValueType};
+
+/// A JavaScript iterator object backed by a Rust iterator, which lets
+/// `for...of` walk Rust data without collecting it into an array first.
+#[derive(Clone, Copy, Debug)]
+pub struct JsIterator {
+    value: sys::napi_value,
+    env: Env,
+}
+
+impl JsIterator {
+    /// Wraps `iter` in an object implementing the iterator protocol. The Rust
+    /// iterator is dropped as soon as it is exhausted or `return()` is
+    /// called, e.g. by a `break` out of a `for...of` loop.
+    pub fn from_rust<I>(env: Env, iter: I) -> Result<JsIterator>
+    where
+        I: Iterator + 'static,
+        I::Item: IntoJs,
+    {
+        let state = Rc::new(RefCell::new(Some(iter)));
+        let object = Object::new(env)?;
+
+        let iter = state.clone();
+        let next = Function::from_fn(env, "next", move |env, _this, _args| {
+            let mut iter = iter.try_borrow_mut().map_err(|_| already_running(env))?;
+            let value = iter.as_mut().and_then(Iterator::next);
+            match value {
+                Some(value) => iterator_result(env, value.into_js(env)?, false),
+                None => {
+                    *iter = None;
+                    iterator_result(env, Any::new(env)?, true)
+                }
+            }
+        })?;
+
+        let return_ = Function::from_fn(env, "return", move |env, _this, args| {
+            let mut iter = state.try_borrow_mut().map_err(|_| already_running(env))?;
+            *iter = None;
+            let value = match args.first() {
+                Some(value) => *value,
+                None => Any::new(env)?,
+            };
+            iterator_result(env, value, true)
+        })?;
+
+        let iterator = Function::from_fn(env, "[Symbol.iterator]", |_env, this, _args| Ok(this))?;
+        let symbol = env
+            .symbol_constructor()?
+            .as_napi_object()
+            .get_named_property("iterator")?;
+
+        object.set_named_property("next", &next)?;
+        object.set_named_property("return", &return_)?;
+        object.set_property(&symbol, &iterator)?;
+
+        Ok(JsIterator::construct(env, object.as_sys_value()))
+    }
+}
+
+pub(super) fn iterator_result(env: Env, value: Any, done: bool) -> Result<Object> {
+    let result = Object::new(env)?;
+    result.set_named_property("value", &value)?;
+    result.set("done", done)?;
+    Ok(result)
+}
+
+fn already_running(env: Env) -> Error {
+    match String::from_str(env, "Iterator is already running") {
+        Ok(message) => Error::type_error(env, &message),
+        Err(error) => error,
+    }
+}
+
+impl Value for JsIterator {
+    fn as_sys_value(&self) -> sys::napi_value {
+        self.value
+    }
+
+    fn env(&self) -> Env {
+        self.env
+    }
+
+    fn from_sys_checked(env: Env, value: sys::napi_value) -> Result<JsIterator> {
+        if Any::with_value(env, value).value_type()? != ValueType::Object {
+            let message = String::from_str(env, "Iterator expected")?;
+            return Err(Error::type_error(env, &message));
+        }
+
+        Ok(JsIterator { env, value })
+    }
+}
+
+impl ValueInternal for JsIterator {
+    fn construct(env: Env, value: sys::napi_value) -> JsIterator {
+        JsIterator { env, value }
+    }
+}
+
+impl AsObject for JsIterator {}
@@ -0,0 +1,105 @@
+use crate::env::Env;
+use crate::result::{Error, Result};
+use crate::sys;
+
+use super::{collect_iterator, Any, AsObject, String, Value, ValueInternal};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Map {
+    value: sys::napi_value,
+    env: Env,
+}
+
+impl Map {
+    pub fn new(env: Env) -> Result<Map> {
+        let map = env.map_constructor()?.new_instance(&[])?;
+        Ok(Map::construct(env, map.as_sys_value()))
+    }
+
+    pub fn get<K>(&self, key: &K) -> Result<Any>
+    where
+        K: Value,
+    {
+        self.env.map_get()?.call(self, &[key.as_napi_any()])
+    }
+
+    pub fn set<K, V>(&self, key: &K, value: &V) -> Result<()>
+    where
+        K: Value,
+        V: Value,
+    {
+        self.env
+            .map_set()?
+            .call(self, &[key.as_napi_any(), value.as_napi_any()])?;
+        Ok(())
+    }
+
+    pub fn has<K>(&self, key: &K) -> Result<bool>
+    where
+        K: Value,
+    {
+        self.env
+            .map_has()?
+            .call(self, &[key.as_napi_any()])?
+            .as_boolean()?
+            .to_bool()
+    }
+
+    pub fn delete<K>(&self, key: &K) -> Result<bool>
+    where
+        K: Value,
+    {
+        self.env
+            .map_delete()?
+            .call(self, &[key.as_napi_any()])?
+            .as_boolean()?
+            .to_bool()
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        self.env.map_clear()?.call(self, &[])?;
+        Ok(())
+    }
+
+    pub fn size(&self) -> Result<u32> {
+        self.env.map_size()?.call(self, &[])?.as_number()?.to_u32()
+    }
+
+    pub fn entries(&self) -> Result<Vec<(Any, Any)>> {
+        collect_iterator(self.env.map_entries()?.call(self, &[])?)?
+            .into_iter()
+            .map(|entry| {
+                let entry = entry.as_array()?;
+                Ok((entry.get(0)?, entry.get(1)?))
+            })
+            .collect()
+    }
+}
+
+impl Value for Map {
+    fn as_sys_value(&self) -> sys::napi_value {
+        self.value
+    }
+
+    fn env(&self) -> Env {
+        self.env
+    }
+
+    fn from_sys_checked(env: Env, value: sys::napi_value) -> Result<Map> {
+        let constructor = env.map_constructor()?.as_napi_object();
+        if !Any::with_value(env, value).instanceof(&constructor)? {
+            let message = String::from_str(env, "Map expected")?;
+            return Err(Error::type_error(env, &message));
+        }
+
+        Ok(Map { env, value })
+    }
+}
+
+impl ValueInternal for Map {
+    fn construct(env: Env, value: sys::napi_value) -> Map {
+        Map { env, value }
+    }
+}
+
+impl AsObject for Map {}
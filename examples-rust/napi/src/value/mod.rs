@@ -7,11 +7,24 @@ use crate::sys;
 mod any;
 mod array;
 mod array_buffer;
+#[cfg(feature = "async")]
+mod async_iterable;
+#[cfg(feature = "napi6")]
+mod bigint;
 mod boolean;
 mod buffer;
+#[cfg(feature = "napi8")]
+mod external;
+mod function;
+mod iter;
+mod js_iterator;
+mod map;
 mod null;
 mod number;
 mod object;
+mod set;
+#[cfg(feature = "napi4")]
+mod stream;
 mod string;
 mod typed_array;
 mod undefined;
@@ -19,11 +32,24 @@ mod undefined;
 pub use self::any::Any;
 pub use self::array::Array;
 pub use self::array_buffer::ArrayBuffer;
+#[cfg(feature = "async")]
+pub use self::async_iterable::AsyncIterable;
+#[cfg(feature = "napi6")]
+pub use self::bigint::BigInt;
 pub use self::boolean::Boolean;
 pub use self::buffer::Buffer;
+#[cfg(feature = "napi8")]
+pub use self::external::External;
+pub use self::function::Function;
+pub use self::iter::{ArrayIter, Entries, Keys};
+pub use self::js_iterator::JsIterator;
+pub use self::map::Map;
 pub use self::null::Null;
 pub use self::number::Number;
 pub use self::object::Object;
+pub use self::set::Set;
+#[cfg(feature = "napi4")]
+pub use self::stream::{Readable, Writable};
 pub use self::string::String;
 pub use self::typed_array::*;
 pub use self::undefined::Undefined;
@@ -40,6 +66,8 @@ pub enum ValueType {
     Function,
     External,
     BigInt,
+    /// A type added by a newer engine than this crate knows about.
+    Unknown(u32),
 }
 
 pub trait Value: Sized {
@@ -88,6 +116,7 @@ pub trait Value: Sized {
             sys::napi_valuetype::napi_function => ValueType::Function,
             sys::napi_valuetype::napi_external => ValueType::External,
             sys::napi_valuetype::napi_bigint => ValueType::BigInt,
+            value_type => ValueType::Unknown(value_type.0),
         })
     }
 
@@ -197,3 +226,27 @@ where
 
     Ok(result)
 }
+
+pub(crate) fn call_method<T>(value: &T, name: &str, args: &[Any]) -> Result<Any>
+where
+    T: Value,
+{
+    Any::with_value(value.env(), value.as_sys_value())
+        .as_object()?
+        .get_named_property(name)?
+        .as_function()?
+        .call(value, args)
+}
+
+/// Drains a JS iterator object by calling `next` until it is done.
+fn collect_iterator(iterator: Any) -> Result<Vec<Any>> {
+    let mut values = Vec::new();
+
+    loop {
+        let result = call_method(&iterator, "next", &[])?.as_object()?;
+        if result.get::<bool>("done")? {
+            return Ok(values);
+        }
+        values.push(result.get_named_property("value")?);
+    }
+}
@@ -25,6 +25,18 @@ impl Number {
         })
     }
 
+    pub fn from_u32(env: Env, value: u32) -> Result<Number> {
+        let mut sys_value = ptr::null_mut();
+        env.handle_status(unsafe {
+            sys::napi_create_uint32(env.as_sys_env(), value, &mut sys_value)
+        })?;
+
+        Ok(Number {
+            value: sys_value,
+            env,
+        })
+    }
+
     pub fn from_i64(env: Env, value: i64) -> Result<Number> {
         let mut sys_value = ptr::null_mut();
         env.handle_status(unsafe {
@@ -59,6 +71,16 @@ impl Number {
         Ok(result)
     }
 
+    pub fn to_u32(&self) -> Result<u32> {
+        let mut result = 0;
+
+        self.env.handle_status(unsafe {
+            sys::napi_get_value_uint32(self.env.as_sys_env(), self.value, &mut result)
+        })?;
+
+        Ok(result)
+    }
+
     pub fn to_i64(&self) -> Result<i64> {
         let mut result = 0;
 
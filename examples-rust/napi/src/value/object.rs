@@ -1,10 +1,25 @@
+#[cfg(feature = "napi5")]
+use std::cell::Cell;
+#[cfg(feature = "napi5")]
+use std::os::raw::c_void;
 use std::ptr;
+#[cfg(feature = "napi5")]
+use std::rc::Rc;
 
+use crate::convert::{FromJs, IntoJs};
 use crate::env::Env;
+#[cfg(feature = "napi8")]
+use crate::external_memory::{ExternalMemory, SizeHint};
+#[cfg(feature = "napi8")]
+use crate::native::{has_native_tag, tag_native, Native};
+use crate::property::PropertyDescriptor;
+#[cfg(feature = "napi5")]
+use crate::reference::WeakRef;
 use crate::result::{Error, Result};
+use crate::scope::HandleScope;
 use crate::sys;
 
-use super::{Any, Array, String, Value, ValueInternal, ValueType};
+use super::{Any, Array, Entries, Keys, String, Value, ValueInternal, ValueType};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Object {
@@ -40,6 +55,39 @@ impl Object {
         Ok(Array::construct(self.env, result))
     }
 
+    pub fn keys(&self) -> Result<Keys> {
+        Keys::new(self)
+    }
+
+    pub fn entries(&self) -> Result<Entries> {
+        Entries::new(self)
+    }
+
+    /// Calls `f` with the name of every enumerable property, each in its own
+    /// handle scope like `Array::for_each`.
+    pub fn for_each_key<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(String) -> Result<()>,
+    {
+        let _scope = HandleScope::new(self.env)?;
+        self.property_names()?
+            .for_each(|name| f(name.to_napi_string()?))
+    }
+
+    /// Calls `f` with every enumerable property as a key-value pair, each in
+    /// its own handle scope like `Array::for_each`.
+    pub fn for_each_entry<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(String, Any) -> Result<()>,
+    {
+        let _scope = HandleScope::new(self.env)?;
+        self.property_names()?.for_each(|name| {
+            let key = name.to_napi_string()?;
+            let value = self.get_property(&key)?;
+            f(key, value)
+        })
+    }
+
     pub fn set_property<T, U>(&self, key: &T, value: &U) -> Result<()>
     where
         T: Value,
@@ -140,6 +188,30 @@ impl Object {
         self.get_property(&key)
     }
 
+    pub fn get<T>(&self, key: &str) -> Result<T>
+    where
+        T: FromJs,
+    {
+        self.get_named_property(key)
+            .and_then(T::from_js)
+            .map_err(|error| error.in_property(self.env, key))
+    }
+
+    pub fn get_opt<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: FromJs,
+    {
+        self.get::<Option<T>>(key)
+    }
+
+    pub fn set<T>(&self, key: &str, value: T) -> Result<()>
+    where
+        T: IntoJs,
+    {
+        let value = value.into_js(self.env)?;
+        self.set_named_property(key, &value)
+    }
+
     pub fn has_named_property(&self, name: &str) -> Result<bool> {
         let key = String::from_str(self.env, name)?;
         self.has_property(&key)
@@ -150,6 +222,22 @@ impl Object {
         self.del_property(&key)
     }
 
+    pub fn define_properties(&self, properties: &[PropertyDescriptor]) -> Result<()> {
+        let descriptors = properties
+            .iter()
+            .map(|property| property.to_sys_descriptor(self.env))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.env.handle_status(unsafe {
+            sys::napi_define_properties(
+                self.env.as_sys_env(),
+                self.value,
+                descriptors.len(),
+                descriptors.as_ptr(),
+            )
+        })
+    }
+
     pub fn set_element<T>(&self, index: u32, value: &T) -> Result<()>
     where
         T: Value,
@@ -193,6 +281,177 @@ impl Object {
 
         Ok(result)
     }
+
+    /// Associates `data` with the object until it is garbage-collected or
+    /// `remove_wrap` is called, reporting the memory `data` owns to the
+    /// engine for as long.
+    #[cfg(feature = "napi8")]
+    pub fn wrap<T>(&self, data: T) -> Result<()>
+    where
+        T: SizeHint + 'static,
+    {
+        let memory = ExternalMemory::for_value(self.env, &data)?;
+        self.wrap_native(data, Some(memory))
+    }
+
+    /// Like `wrap`, for data whose memory isn't reported to the engine.
+    #[cfg(feature = "napi8")]
+    pub fn wrap_without_size_hint<T: 'static>(&self, data: T) -> Result<()> {
+        self.wrap_native(data, None)
+    }
+
+    #[cfg(feature = "napi8")]
+    fn wrap_native<T: 'static>(&self, data: T, memory: Option<ExternalMemory>) -> Result<()> {
+        tag_native(self)?;
+
+        let native = Native::into_raw(data, memory);
+        let status = unsafe {
+            sys::napi_wrap(
+                self.env.as_sys_env(),
+                self.value,
+                native,
+                Some(Native::<T>::finalize),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+
+        if let Err(error) = self.env.handle_status(status) {
+            drop(unsafe { Native::<T>::from_raw(native) });
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the data wrapped into the object with `wrap`. Objects wrapped
+    /// by other native code are rejected by their type tag.
+    #[cfg(feature = "napi8")]
+    pub fn unwrap<T: 'static>(&self) -> Result<&T> {
+        let native = self.wrapped::<T>()?;
+        Ok(unsafe { Native::<T>::get(native) }.unwrap())
+    }
+
+    /// Takes the data wrapped into the object back out of it.
+    ///
+    /// # Safety
+    ///
+    /// No reference returned by `unwrap` on this object or a copy of it may
+    /// be used afterwards, since the data is moved out. The object keeps its
+    /// type tag, so it must not be wrapped again by other native code either.
+    #[cfg(feature = "napi8")]
+    pub unsafe fn remove_wrap<T: 'static>(&self) -> Result<T> {
+        self.wrapped::<T>()?;
+
+        let mut native = ptr::null_mut();
+        self.env.handle_status(sys::napi_remove_wrap(
+            self.env.as_sys_env(),
+            self.value,
+            &mut native,
+        ))?;
+
+        Ok(Native::<T>::from_raw(native))
+    }
+
+    #[cfg(feature = "napi8")]
+    fn wrapped<T: 'static>(&self) -> Result<*mut c_void> {
+        if !has_native_tag(self)? {
+            let message = String::from_str(self.env, "Object doesn't wrap native data")?;
+            return Err(Error::type_error(self.env, &message));
+        }
+
+        let mut native = ptr::null_mut();
+        self.env.handle_status(unsafe {
+            sys::napi_unwrap(self.env.as_sys_env(), self.value, &mut native)
+        })?;
+
+        if unsafe { Native::<T>::get(native) }.is_none() {
+            let message = String::from_str(self.env, "Object wraps a different type")?;
+            return Err(Error::type_error(self.env, &message));
+        }
+
+        Ok(native)
+    }
+
+    /// Attaches `data` to the object and calls `finalize` with it once the
+    /// object is garbage-collected.
+    #[cfg(feature = "napi5")]
+    pub fn add_finalizer<T, F>(&self, data: T, finalize: F) -> Result<()>
+    where
+        T: 'static,
+        F: FnOnce(Env, T) + 'static,
+    {
+        self.attach_finalizer(data, finalize, None)
+    }
+
+    /// Like `add_finalizer`, but also returns a weak reference to the object.
+    #[cfg(feature = "napi5")]
+    pub fn add_finalizer_with_ref<T, F>(&self, data: T, finalize: F) -> Result<WeakRef<Object>>
+    where
+        T: 'static,
+        F: FnOnce(Env, T) + 'static,
+    {
+        let reference = Rc::new(Cell::new(ptr::null_mut()));
+        self.attach_finalizer(data, finalize, Some(&reference))?;
+        Ok(WeakRef::from_shared(self.env, reference))
+    }
+
+    /// Attaches the finalizer, and if `reference` is given, creates a weak
+    /// reference that the finalizer deletes, as N-API requires.
+    #[cfg(feature = "napi5")]
+    fn attach_finalizer<T, F>(
+        &self,
+        data: T,
+        finalize: F,
+        reference: Option<&Rc<Cell<sys::napi_ref>>>,
+    ) -> Result<()>
+    where
+        T: 'static,
+        F: FnOnce(Env, T) + 'static,
+    {
+        type Data<T, F> = (T, F, Option<Rc<Cell<sys::napi_ref>>>);
+
+        unsafe extern "C" fn finalize_data<T, F>(
+            env: sys::napi_env,
+            data: *mut c_void,
+            _hint: *mut c_void,
+        ) where
+            F: FnOnce(Env, T),
+        {
+            let (data, finalize, reference) = *Box::from_raw(data as *mut Data<T, F>);
+            if let Some(reference) = reference {
+                sys::napi_delete_reference(env, reference.replace(ptr::null_mut()));
+            }
+            finalize(Env::from(env), data);
+        }
+
+        let mut result = ptr::null_mut();
+        let result_ptr = match reference {
+            Some(_) => &mut result as *mut sys::napi_ref,
+            None => ptr::null_mut(),
+        };
+        let data: *mut Data<T, F> = Box::into_raw(Box::new((data, finalize, reference.cloned())));
+        let status = unsafe {
+            sys::napi_add_finalizer(
+                self.env.as_sys_env(),
+                self.value,
+                data as *mut c_void,
+                Some(finalize_data::<T, F>),
+                ptr::null_mut(),
+                result_ptr,
+            )
+        };
+
+        if let Err(error) = self.env.handle_status(status) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(error);
+        }
+
+        if let Some(reference) = reference {
+            reference.set(result);
+        }
+        Ok(())
+    }
 }
 
 impl Value for Object {
@@ -0,0 +1,89 @@
+use crate::env::Env;
+use crate::result::{Error, Result};
+use crate::sys;
+
+use super::{collect_iterator, Any, AsObject, String, Value, ValueInternal};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Set {
+    value: sys::napi_value,
+    env: Env,
+}
+
+impl Set {
+    pub fn new(env: Env) -> Result<Set> {
+        let set = env.set_constructor()?.new_instance(&[])?;
+        Ok(Set::construct(env, set.as_sys_value()))
+    }
+
+    pub fn add<T>(&self, value: &T) -> Result<()>
+    where
+        T: Value,
+    {
+        self.env.set_add()?.call(self, &[value.as_napi_any()])?;
+        Ok(())
+    }
+
+    pub fn has<T>(&self, value: &T) -> Result<bool>
+    where
+        T: Value,
+    {
+        self.env
+            .set_has()?
+            .call(self, &[value.as_napi_any()])?
+            .as_boolean()?
+            .to_bool()
+    }
+
+    pub fn delete<T>(&self, value: &T) -> Result<bool>
+    where
+        T: Value,
+    {
+        self.env
+            .set_delete()?
+            .call(self, &[value.as_napi_any()])?
+            .as_boolean()?
+            .to_bool()
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        self.env.set_clear()?.call(self, &[])?;
+        Ok(())
+    }
+
+    pub fn size(&self) -> Result<u32> {
+        self.env.set_size()?.call(self, &[])?.as_number()?.to_u32()
+    }
+
+    pub fn values(&self) -> Result<Vec<Any>> {
+        collect_iterator(self.env.set_values()?.call(self, &[])?)
+    }
+}
+
+impl Value for Set {
+    fn as_sys_value(&self) -> sys::napi_value {
+        self.value
+    }
+
+    fn env(&self) -> Env {
+        self.env
+    }
+
+    fn from_sys_checked(env: Env, value: sys::napi_value) -> Result<Set> {
+        let constructor = env.set_constructor()?.as_napi_object();
+        if !Any::with_value(env, value).instanceof(&constructor)? {
+            let message = String::from_str(env, "Set expected")?;
+            return Err(Error::type_error(env, &message));
+        }
+
+        Ok(Set { env, value })
+    }
+}
+
+impl ValueInternal for Set {
+    fn construct(env: Env, value: sys::napi_value) -> Set {
+        Set { env, value }
+    }
+}
+
+impl AsObject for Set {}
@@ -0,0 +1,408 @@
+use std::cell::RefCell;
+use std::io::{self, Read, Write};
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use crate::env::Env;
+use crate::reference::Ref;
+use crate::result::{Error, Result};
+use crate::sys;
+use crate::threadsafe::Worker;
+
+use super::{
+    call_method, Any, AsObject, Buffer, Function, Null, Number, Object, String, Value,
+    ValueInternal, ValueType,
+};
+
+/// The chunk size used when Node doesn't pass one to `_read`.
+const DEFAULT_CHUNK_SIZE: usize = 16 * 1024;
+
+/// A Node.js `stream.Readable` fed from a Rust reader.
+#[derive(Clone, Copy, Debug)]
+pub struct Readable {
+    value: sys::napi_value,
+    env: Env,
+}
+
+/// A Node.js `stream.Writable` draining into a Rust writer.
+#[derive(Clone, Copy, Debug)]
+pub struct Writable {
+    value: sys::napi_value,
+    env: Env,
+}
+
+enum Chunk {
+    Data(Vec<u8>),
+    End,
+    Failed(io::Error),
+}
+
+struct Reads {
+    /// The stream a read is in flight for, kept alive until the chunk is
+    /// pushed.
+    stream: Option<Ref<Object>>,
+    /// Set until the reader is exhausted or fails, or the stream is
+    /// destroyed.
+    worker: Option<Worker<usize>>,
+}
+
+enum Request {
+    Write(Vec<u8>),
+    Flush,
+}
+
+struct Writes {
+    /// The callback of the write or final flush in flight.
+    callback: Option<Ref<Function>>,
+    /// Set until the stream is destroyed.
+    worker: Option<Worker<Request>>,
+}
+
+impl Readable {
+    /// Creates a readable stream whose `_read` reads a chunk of up to the
+    /// requested size from `reader` on a background thread. Node only asks
+    /// for more while less than `highWaterMark` bytes are buffered, and only
+    /// one read is in flight at a time.
+    ///
+    /// Requires `process.getBuiltinModule`, i.e. Node.js 20.16 or 22.3.
+    pub fn from_reader<R>(env: Env, reader: R) -> Result<Readable>
+    where
+        R: Read + Send + 'static,
+    {
+        Readable::create(env, reader, None)
+    }
+
+    pub fn from_reader_with_high_water_mark<R>(
+        env: Env,
+        reader: R,
+        high_water_mark: usize,
+    ) -> Result<Readable>
+    where
+        R: Read + Send + 'static,
+    {
+        Readable::create(env, reader, Some(high_water_mark))
+    }
+
+    fn create<R>(env: Env, mut reader: R, high_water_mark: Option<usize>) -> Result<Readable>
+    where
+        R: Read + Send + 'static,
+    {
+        let reads = Rc::new(RefCell::new(Reads {
+            stream: None,
+            worker: None,
+        }));
+
+        let weak = Rc::downgrade(&reads);
+        let worker = Worker::spawn(
+            env,
+            "Readable",
+            move |size| read_chunk(&mut reader, size),
+            move |env, chunk| match weak.upgrade() {
+                Some(reads) => push_chunk(&reads, env, chunk),
+                None => Ok(()),
+            },
+        )?;
+        reads.borrow_mut().worker = Some(worker);
+
+        let state = reads.clone();
+        let read = Function::from_fn(env, "read", move |_env, this, args| {
+            let size = match args.first() {
+                Some(size) => size.as_number()?.to_u32()? as usize,
+                None => DEFAULT_CHUNK_SIZE,
+            };
+
+            let mut reads = state.borrow_mut();
+            if reads.stream.is_some() {
+                return Ok(());
+            }
+
+            if let Some(worker) = &reads.worker {
+                if let Err(error) = worker.request(size.max(1)) {
+                    reads.worker = None;
+                    return Err(error);
+                }
+                reads.stream = Some(Ref::new(&this.as_object()?, 1)?);
+            }
+            Ok(())
+        })?;
+
+        let destroy = Function::from_fn(env, "destroy", move |env, _this, args| {
+            {
+                let mut reads = reads.borrow_mut();
+                reads.stream = None;
+                reads.worker = None;
+            }
+            finish_destroy(env, args)
+        })?;
+
+        let options = Object::new(env)?;
+        options.set_named_property("read", &read)?;
+        options.set_named_property("destroy", &destroy)?;
+        set_high_water_mark(&options, high_water_mark)?;
+
+        let stream = stream_class(env, "Readable")?.new_instance(&[options.as_napi_any()])?;
+        Ok(Readable::construct(env, stream.as_sys_value()))
+    }
+}
+
+impl Writable {
+    /// Creates a writable stream that writes every chunk to `writer` on a
+    /// background thread and flushes it when the stream ends. The next chunk
+    /// is only written once the previous one is, so `write()` returns
+    /// `false` once `highWaterMark` bytes are waiting.
+    ///
+    /// Requires `process.getBuiltinModule`, i.e. Node.js 20.16 or 22.3.
+    pub fn from_writer<W>(env: Env, writer: W) -> Result<Writable>
+    where
+        W: Write + Send + 'static,
+    {
+        Writable::create(env, writer, None)
+    }
+
+    pub fn from_writer_with_high_water_mark<W>(
+        env: Env,
+        writer: W,
+        high_water_mark: usize,
+    ) -> Result<Writable>
+    where
+        W: Write + Send + 'static,
+    {
+        Writable::create(env, writer, Some(high_water_mark))
+    }
+
+    fn create<W>(env: Env, mut writer: W, high_water_mark: Option<usize>) -> Result<Writable>
+    where
+        W: Write + Send + 'static,
+    {
+        let writes = Rc::new(RefCell::new(Writes {
+            callback: None,
+            worker: None,
+        }));
+
+        let weak = Rc::downgrade(&writes);
+        let worker = Worker::spawn(
+            env,
+            "Writable",
+            move |request| {
+                let result = match request {
+                    Request::Write(bytes) => writer.write_all(&bytes),
+                    Request::Flush => return ControlFlow::Break(writer.flush()),
+                };
+                match result {
+                    Ok(()) => ControlFlow::Continue(Ok(())),
+                    Err(error) => ControlFlow::Break(Err(error)),
+                }
+            },
+            move |env, result| match weak.upgrade() {
+                Some(writes) => complete_write(&writes, env, result),
+                None => Ok(()),
+            },
+        )?;
+        writes.borrow_mut().worker = Some(worker);
+
+        let state = writes.clone();
+        let write = Function::from_fn(env, "write", move |env, _this, args| {
+            let chunk = Buffer::from_sys_checked(env, arg(env, args, 0)?.as_sys_value())?;
+            let bytes = chunk.as_ref().to_vec();
+            let callback = arg(env, args, 2)?.as_function()?;
+            request(&state, env, Request::Write(bytes), callback)
+        })?;
+
+        let state = writes.clone();
+        let final_ = Function::from_fn(env, "final", move |env, _this, args| {
+            let callback = arg(env, args, 0)?.as_function()?;
+            request(&state, env, Request::Flush, callback)
+        })?;
+
+        let destroy = Function::from_fn(env, "destroy", move |env, _this, args| {
+            {
+                let mut writes = writes.borrow_mut();
+                writes.callback = None;
+                writes.worker = None;
+            }
+            finish_destroy(env, args)
+        })?;
+
+        let options = Object::new(env)?;
+        options.set_named_property("write", &write)?;
+        options.set_named_property("final", &final_)?;
+        options.set_named_property("destroy", &destroy)?;
+        set_high_water_mark(&options, high_water_mark)?;
+
+        let stream = stream_class(env, "Writable")?.new_instance(&[options.as_napi_any()])?;
+        Ok(Writable::construct(env, stream.as_sys_value()))
+    }
+}
+
+fn read_chunk<R: Read>(reader: &mut R, size: usize) -> ControlFlow<Chunk, Chunk> {
+    let mut chunk = vec![0; size];
+    loop {
+        match reader.read(&mut chunk) {
+            Ok(0) => return ControlFlow::Break(Chunk::End),
+            Ok(len) => {
+                chunk.truncate(len);
+                return ControlFlow::Continue(Chunk::Data(chunk));
+            }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return ControlFlow::Break(Chunk::Failed(error)),
+        }
+    }
+}
+
+/// Hands a chunk read on the worker thread to the stream. Pushing may call
+/// `_read` synchronously, so the state isn't borrowed while it runs.
+fn push_chunk(reads: &RefCell<Reads>, env: Env, chunk: Chunk) -> Result<()> {
+    let stream = {
+        let mut reads = reads.borrow_mut();
+        match chunk {
+            Chunk::Data(_) => {
+                if let Some(worker) = &reads.worker {
+                    worker.idle()?;
+                }
+            }
+            Chunk::End | Chunk::Failed(_) => reads.worker = None,
+        }
+        reads.stream.take()
+    };
+
+    let stream = match stream {
+        Some(stream) => stream.value()?.unwrap(),
+        None => return Ok(()),
+    };
+
+    match chunk {
+        Chunk::Data(bytes) => {
+            let buffer = Buffer::from_vec(env, bytes)?;
+            call_method(&stream, "push", &[buffer.as_napi_any()])?;
+        }
+        Chunk::End => {
+            call_method(&stream, "push", &[Null::new(env)?.as_napi_any()])?;
+        }
+        Chunk::Failed(error) => {
+            let exception = Error::wrap(error).into_exception(env)?;
+            call_method(&stream, "destroy", &[exception])?;
+        }
+    }
+    Ok(())
+}
+
+fn request(writes: &RefCell<Writes>, env: Env, request: Request, callback: Function) -> Result<()> {
+    let mut writes = writes.borrow_mut();
+    let error = match &writes.worker {
+        Some(worker) => match worker.request(request) {
+            Ok(()) => {
+                writes.callback = Some(Ref::new(&callback, 1)?);
+                return Ok(());
+            }
+            Err(error) => {
+                writes.worker = None;
+                error
+            }
+        },
+        None => {
+            let message = String::from_str(env, "Stream is destroyed")?;
+            Error::error(env, &message)
+        }
+    };
+
+    drop(writes);
+    let exception = error.into_exception(env)?;
+    callback.call(&Any::new(env)?, &[exception])?;
+    Ok(())
+}
+
+/// Calls back the write or final flush in flight once the worker thread is
+/// done with it.
+fn complete_write(writes: &RefCell<Writes>, env: Env, result: io::Result<()>) -> Result<()> {
+    let callback = {
+        let mut writes = writes.borrow_mut();
+        if let Some(worker) = &writes.worker {
+            worker.idle()?;
+        }
+        writes.callback.take()
+    };
+
+    let callback = match callback {
+        Some(callback) => callback.value()?.unwrap(),
+        None => return Ok(()),
+    };
+
+    let args = match result {
+        Ok(()) => Vec::new(),
+        Err(error) => vec![Error::wrap(error).into_exception(env)?],
+    };
+    callback.call(&Any::new(env)?, &args)?;
+    Ok(())
+}
+
+/// Calls the callback passed to `_destroy` with the error the stream was
+/// destroyed with, if any.
+fn finish_destroy(env: Env, args: &[Any]) -> Result<()> {
+    let error = arg(env, args, 0)?;
+    arg(env, args, 1)?
+        .as_function()?
+        .call(&Any::new(env)?, &[error])?;
+    Ok(())
+}
+
+fn arg(env: Env, args: &[Any], index: usize) -> Result<Any> {
+    match args.get(index) {
+        Some(arg) => Ok(*arg),
+        None => Any::new(env),
+    }
+}
+
+fn set_high_water_mark(options: &Object, high_water_mark: Option<usize>) -> Result<()> {
+    match high_water_mark {
+        Some(high_water_mark) => {
+            let high_water_mark = Number::from_f64(options.env(), high_water_mark as f64)?;
+            options.set_named_property("highWaterMark", &high_water_mark)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Looks up a class of the `stream` module, which isn't reachable from the
+/// global scope otherwise.
+fn stream_class(env: Env, name: &str) -> Result<Function> {
+    let process = env.global()?.get_named_property("process")?;
+    let module = String::from_str(env, "stream")?;
+    call_method(&process, "getBuiltinModule", &[module.as_napi_any()])?
+        .as_object()?
+        .get_named_property(name)?
+        .as_function()
+}
+
+macro_rules! stream_value {
+    ($ty:ident, $message:literal) => {
+        impl Value for $ty {
+            fn as_sys_value(&self) -> sys::napi_value {
+                self.value
+            }
+
+            fn env(&self) -> Env {
+                self.env
+            }
+
+            fn from_sys_checked(env: Env, value: sys::napi_value) -> Result<$ty> {
+                if Any::with_value(env, value).value_type()? != ValueType::Object {
+                    let message = String::from_str(env, $message)?;
+                    return Err(Error::type_error(env, &message));
+                }
+
+                Ok($ty { env, value })
+            }
+        }
+
+        impl ValueInternal for $ty {
+            fn construct(env: Env, value: sys::napi_value) -> $ty {
+                $ty { env, value }
+            }
+        }
+
+        impl AsObject for $ty {}
+    };
+}
+
+stream_value!(Readable, "Readable expected");
+stream_value!(Writable, "Writable expected");